@@ -0,0 +1,121 @@
+use crate::building_block::hasher::{
+  hasher::Hasher,
+  sha256::Sha256,
+};
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+// implementation based on: https://en.bitcoin.it/wiki/Base58Check_encoding
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+pub struct Base58();
+
+impl Base58 {
+  pub fn encode(data: &[u8]) -> String {
+    // each leading zero byte is encoded as '1'
+    let num_leading_zeros = data.iter().take_while(|x| **x == 0).count();
+
+    let mut n = BigUint::from_bytes_be(data);
+    let base = BigUint::from(58u8);
+    let mut s: Vec<u8> = vec![];
+    while !n.is_zero() {
+      let r = (&n % &base).to_u32_digits();
+      let r = if r.is_empty() { 0 } else { r[0] as usize };
+      s.push(ALPHABET[r]);
+      n /= &base;
+    }
+    s.extend(vec![ALPHABET[0]; num_leading_zeros]);
+    s.reverse();
+    String::from_utf8(s).unwrap()
+  }
+
+  pub fn decode(s: &str) -> Result<Vec<u8>, String> {
+    let num_leading_ones = s.bytes().take_while(|x| *x == ALPHABET[0]).count();
+
+    let mut n = BigUint::zero();
+    for c in s.bytes() {
+      let digit = match ALPHABET.iter().position(|x| *x == c) {
+        Some(x) => x,
+        None => return Err(format!("Invalid base58 character: {}", c as char)),
+      };
+      n = n * 58u8 + digit;
+    }
+    let mut data = vec![0u8; num_leading_ones];
+    if !n.is_zero() {
+      data.extend(n.to_bytes_be());
+    }
+    Ok(data)
+  }
+
+  // first 4 bytes of double sha256 of the data
+  fn checksum(data: &[u8]) -> [u8; 4] {
+    let hasher = Sha256();
+    let digest = hasher.get_digest(&hasher.get_digest(data));
+    digest[0..4].try_into().unwrap()
+  }
+
+  pub fn check_encode(data: &[u8]) -> String {
+    let checksum = Self::checksum(data);
+    Self::encode(&[data, &checksum].concat())
+  }
+
+  pub fn check_decode(s: &str) -> Result<Vec<u8>, String> {
+    let buf = Self::decode(s)?;
+    if buf.len() < 4 {
+      return Err("Data is too short to contain checksum".to_string());
+    }
+    let (data, checksum) = buf.split_at(buf.len() - 4);
+    if Self::checksum(data) != checksum {
+      return Err("Checksum mismatch".to_string());
+    }
+    Ok(data.to_vec())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn encode_decode() {
+    let test_cases = [
+      ("", ""),
+      ("61", "2g"),
+      ("626262", "a3gV"),
+      ("636363", "aPEr"),
+      ("73696d706c792061206c6f6e6720737472696e67", "2cFupjhnEsSn59qHXstmK2ffpLv2"),
+      ("00eb15231dfceb60925886b67d065299925915aeb172c06647", "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L"),
+      ("516b6fcd0f", "ABnLTmg"),
+      ("bf4f89001e670274dd", "3SEo3LWLoPntC"),
+      ("572e4794", "3EFU7m"),
+      ("ecac89cad93923c02321", "EJDM8drfXA6uyA"),
+      ("10c8511e", "Rt5zm"),
+      ("00000000000000000000", "1111111111"),
+    ];
+    for (hex_data, exp) in test_cases {
+      let data = hex::decode(hex_data).unwrap();
+      assert_eq!(Base58::encode(&data), exp);
+      assert_eq!(Base58::decode(exp).unwrap(), data);
+    }
+  }
+
+  #[test]
+  fn decode_invalid_char() {
+    assert!(Base58::decode("0OIl").is_err());
+  }
+
+  #[test]
+  fn check_encode_decode() {
+    // version byte 0 + hash160 of a public key
+    let data = hex::decode("00f54a5851e9372b87810a8e60cdd2e7cfd80b6e31").unwrap();
+    let s = Base58::check_encode(&data);
+    assert_eq!(s, "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs");
+    assert_eq!(Base58::check_decode(&s).unwrap(), data);
+  }
+
+  #[test]
+  fn check_decode_bad_checksum() {
+    assert!(Base58::check_decode("1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAt").is_err());
+  }
+}
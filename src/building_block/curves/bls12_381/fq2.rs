@@ -34,6 +34,31 @@ impl Fq2 {
   pub fn sq(&self) -> Self {
     self * self
   }
+
+  // square root in Fq w/ q = 3 mod 4
+  fn fq1_sqrt(a: &Fq1) -> Option<Fq1> {
+    let x = a.pow(&((a.f.order_ref() + 1u8) / 4u8));
+    if &x.sq() == a { Some(x) } else { None }
+  }
+
+  // square root computed from the square roots of the norm u0^2 + u1^2 and (u0 +/- norm) / 2 in Fq.
+  // returns None if self is not a square
+  pub fn sqrt(&self) -> Option<Fq2> {
+    if self.u1.is_zero() {
+      // u0 or -u0 is a square in Fq since -1 is not a square
+      return match Self::fq1_sqrt(&self.u0) {
+        Some(x0) => Some(Fq2::new(&Fq1::fq1_zero(), &x0)),
+        None => Self::fq1_sqrt(&self.u0.negate()).map(|x1| Fq2::new(&x1, &Fq1::fq1_zero())),
+      };
+    }
+    let norm = Self::fq1_sqrt(&(self.u0.sq() + self.u1.sq()))?;
+    let x0 = Self::fq1_sqrt(&((&self.u0 + &norm) / 2u8))
+      .or_else(|| Self::fq1_sqrt(&((&self.u0 - &norm) / 2u8)))?;
+    let x1 = &self.u1 / (&x0 + &x0);
+
+    let x = Fq2::new(&x1, &x0);
+    if &x.sq() == self { Some(x) } else { None }
+  }
 }
 
 impl Zero<Fq2> for Fq2 {
@@ -225,6 +250,23 @@ mod tests {
     assert_eq!(u0, "4002409555221667393417789825735904156556882819939007885332058136124031650490837864442687629129015664037894272559749");
   }
 
+  #[test]
+  fn test_sqrt() {
+    let (a1, b1, c1, d1) = &get_fq2_values();
+    for x in [a1, b1, c1, d1, &Fq2::zero(), &Fq1::from_to_biguint(&5u8).into_fq2()] {
+      let x_sq = x.sq();
+      let root = x_sq.sqrt().unwrap();
+      assert!(&root == x || root == -x);
+    }
+    // -1 is not a square in Fq, but is in Fq2
+    let minus_one = -Fq1::from_to_biguint(&1u8).into_fq2();
+    assert_eq!(minus_one.sqrt().unwrap().sq(), minus_one);
+
+    // u + 2 is not a square in Fq2 since its norm 5 is not a square in Fq
+    let non_square = Fq2::new(&Fq1::from_to_biguint(&1u8), &Fq1::from_to_biguint(&2u8));
+    assert!(non_square.sqrt().is_none());
+  }
+
   #[test]
   fn test_neg() {
     let (a1, b1, c1, d1) = &get_fq2_values();
@@ -26,7 +26,7 @@ use std::{
   sync::Arc,
 };
 use once_cell::sync::Lazy;
-use rand::SeedableRng;
+use rand::{CryptoRng, RngCore, SeedableRng};
 use rand_chacha::ChaCha12Rng;
 
 #[derive(Clone)]
@@ -81,7 +81,10 @@ impl G1Point {
   }
 
   pub fn get_random_point() -> AffinePoint {
-    let mut rng = ChaCha12Rng::from_entropy();
+    Self::get_random_point_with_rng(&mut ChaCha12Rng::from_entropy())
+  }
+
+  pub fn get_random_point_with_rng(rng: &mut (impl RngCore + CryptoRng)) -> AffinePoint {
     let subgroup = &P::subgroup();
     let n = rng.gen_biguint_range(&NumTraitsZero::zero(), subgroup.order_ref());
     G1Point::g() * &subgroup.elem(&n)
@@ -91,6 +94,26 @@ impl G1Point {
   pub fn curve_group() -> Arc<PrimeField> {
     P::subgroup()
   }
+
+  // 48-byte big-endian x-coordinate w/ the 3 most significant bits used as flags:
+  // compressed, point at infinity and y is lexicographically the larger of y and -y
+  pub fn to_compressed_bytes(&self) -> [u8; 48] {
+    let mut buf = [0u8; 48];
+    match self {
+      G1Point::AtInfinity => {
+        buf[0] = 0b1100_0000;
+      },
+      G1Point::Rational { x, y } => {
+        let x_bytes = x.e.to_bytes_be();
+        buf[48 - x_bytes.len()..].copy_from_slice(&x_bytes);
+        buf[0] |= 0b1000_0000;
+        if y.e > y.negate().e {
+          buf[0] |= 0b0010_0000;
+        }
+      },
+    }
+    buf
+  }
 }
 
 impl RationalPoint for G1Point {
@@ -236,6 +259,20 @@ mod tests {
     }
   }
 
+  #[test]
+  fn to_compressed_bytes() {
+    let g = G1Point::g();
+    assert_eq!(
+      g.to_compressed_bytes().to_vec(),
+      hex::decode("97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb").unwrap(),
+    );
+    assert_eq!(
+      (-&g).to_compressed_bytes().to_vec(),
+      hex::decode("b7f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb").unwrap(),
+    );
+    assert_eq!(G1Point::AtInfinity.to_compressed_bytes()[0], 0b1100_0000);
+  }
+
   #[test]
   fn negate() {
     let g = &G1Point::g();
@@ -19,7 +19,7 @@ use crate::{
 };
 use num_bigint::{BigUint, RandBigInt};
 use num_traits::Zero as NumTraitsZero;
-use rand::SeedableRng;
+use rand::{CryptoRng, RngCore, SeedableRng};
 use std::{
   ops::{Add, Mul, Neg, AddAssign},
   sync::Arc,
@@ -62,7 +62,10 @@ impl G2Point {
   }
 
   pub fn get_random_point() -> AffinePoint {
-    let mut rng = ChaCha12Rng::from_entropy();
+    Self::get_random_point_with_rng(&mut ChaCha12Rng::from_entropy())
+  }
+
+  pub fn get_random_point_with_rng(rng: &mut (impl RngCore + CryptoRng)) -> AffinePoint {
     let subgroup = &P::subgroup();
     let n = rng.gen_biguint_range(&NumTraitsZero::zero(), subgroup.order_ref());
     G2Point::g() * subgroup.elem(&n)
@@ -80,7 +83,9 @@ impl G2Point {
     lhs == rhs
   }
 
-  // TODO implement properly with hash-and-check or SWU map
+  // maps buf to g * (buf mod r). the discrete log of the result is known to anyone, so this
+  // must not be used where the point needs to be a random oracle output such as in signatures.
+  // use hash_to_curve::hash_to_curve for that purpose
   pub fn hash_to_g2point(buf: &Vec<u8>) -> G2Point {
     let n = BigUint::from_bytes_be(buf);
     let n = P::subgroup().elem(&n);
@@ -0,0 +1,288 @@
+#![allow(non_snake_case)]
+use crate::building_block::{
+  curves::{
+    bls12_381::{
+      fq1::Fq1,
+      fq2::Fq2,
+      g2_point::G2Point,
+      params::Params as P,
+    },
+    multi_scalar_mul::multi_scalar_mul,
+  },
+  hasher::{
+    hasher::Hasher,
+    sha256::Sha256,
+  },
+  zero::Zero,
+};
+use num_bigint::BigUint;
+use once_cell::sync::Lazy;
+
+// implementation of the BLS12381G2_XMD:SHA-256_SSWU_RO_ suite based on:
+// https://datatracker.ietf.org/doc/html/rfc9380
+//
+// the message is hashed to 2 elements of Fq2, each of which is mapped to the 3-isogenous curve
+// E': y^2 = x^3 + A'x + B' w/ simplified SWU and then to E: y^2 = x^3 + 4(1 + u) w/ the isogeny.
+// multiplying the sum of the 2 points by h_eff moves it into the r-order subgroup
+
+const B_IN_BYTES: usize = 32;  // output size of SHA-256
+const S_IN_BYTES: usize = 64;  // input block size of SHA-256
+const L: usize = 64;  // ceil((ceil(log2(q)) + k) / 8) w/ security level k = 128
+
+// a + b * u w/ a and b in hex
+fn fq2(a: &[u8], b: &[u8]) -> Fq2 {
+  Fq2::new(&Fq1::from_u8_slice(b), &Fq1::from_u8_slice(a))
+}
+
+// A' = 240 * u, B' = 1012 * (1 + u) and Z = -(2 + u)
+static ISO_A: Lazy<Fq2> = Lazy::new(|| fq2(b"0", b"f0"));
+static ISO_B: Lazy<Fq2> = Lazy::new(|| fq2(b"3f4", b"3f4"));
+static SSWU_Z: Lazy<Fq2> = Lazy::new(|| -fq2(b"2", b"1"));
+
+// coefficients of the isogeny map polynomials in ascending order of degree (Appendix E.3)
+static X_NUM: Lazy<Vec<Fq2>> = Lazy::new(|| vec![
+  fq2(
+    b"5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6",
+    b"5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97d6",
+  ),
+  fq2(
+    b"0",
+    b"11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71a",
+  ),
+  fq2(
+    b"11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71e",
+    b"8ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38d",
+  ),
+  fq2(
+    b"171d6541fa38ccfaed6dea691f5fb614cb14b4e7f4e810aa22d6108f142b85757098e38d0f671c7188e2aaaaaaaa5ed1",
+    b"0",
+  ),
+]);
+
+static X_DEN: Lazy<Vec<Fq2>> = Lazy::new(|| vec![
+  fq2(
+    b"0",
+    b"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa63",
+  ),
+  fq2(
+    b"c",
+    b"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa9f",
+  ),
+  fq2(b"1", b"0"),
+]);
+
+static Y_NUM: Lazy<Vec<Fq2>> = Lazy::new(|| vec![
+  fq2(
+    b"1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706",
+    b"1530477c7ab4113b59a4c18b076d11930f7da5d4a07f649bf54439d87d27e500fc8c25ebf8c92f6812cfc71c71c6d706",
+  ),
+  fq2(
+    b"0",
+    b"5c759507e8e333ebb5b7a9a47d7ed8532c52d39fd3a042a88b58423c50ae15d5c2638e343d9c71c6238aaaaaaaa97be",
+  ),
+  fq2(
+    b"11560bf17baa99bc32126fced787c88f984f87adf7ae0c7f9a208c6b4f20a4181472aaa9cb8d555526a9ffffffffc71c",
+    b"8ab05f8bdd54cde190937e76bc3e447cc27c3d6fbd7063fcd104635a790520c0a395554e5c6aaaa9354ffffffffe38f",
+  ),
+  fq2(
+    b"124c9ad43b6cf79bfbf7043de3811ad0761b0f37a1e26286b0e977c69aa274524e79097a56dc4bd9e1b371c71c718b10",
+    b"0",
+  ),
+]);
+
+static Y_DEN: Lazy<Vec<Fq2>> = Lazy::new(|| vec![
+  fq2(
+    b"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb",
+    b"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa8fb",
+  ),
+  fq2(
+    b"0",
+    b"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffa9d3",
+  ),
+  fq2(
+    b"12",
+    b"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaa99",
+  ),
+  fq2(b"1", b"0"),
+]);
+
+// h_eff of the suite (Section 8.8.2)
+static H_EFF: Lazy<BigUint> = Lazy::new(|| {
+  BigUint::parse_bytes(
+    b"bc69f08f2ee75b3584c6a0ea91b352888e2a8e9145ad7689986ff031508ffe1329c2f178731db956d82bf015d1212b02ec0ec69d7477c1ae954cbc06689f6a359894c0adebbf6b4e8020005aaa95551",
+    16,
+  ).unwrap()
+});
+
+// expand_message_xmd w/ SHA-256 (Section 5.3.1)
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Result<Vec<u8>, String> {
+  let hasher = Sha256();
+
+  // DST longer than 255 bytes is replaced by its hash (Section 5.3.3)
+  let dst = if dst.len() > 255 {
+    hasher.get_digest(&[b"H2C-OVERSIZE-DST-", dst].concat()).to_vec()
+  } else {
+    dst.to_vec()
+  };
+
+  let ell = len_in_bytes.div_ceil(B_IN_BYTES);
+  if ell > 255 || len_in_bytes > 65535 {
+    return Err(format!("Requested output length is too large: {}", len_in_bytes));
+  }
+  let dst_prime = [&dst[..], &[dst.len() as u8]].concat();
+  let z_pad = [0u8; S_IN_BYTES];
+  let l_i_b_str = (len_in_bytes as u16).to_be_bytes();
+
+  let msg_prime = [&z_pad[..], msg, &l_i_b_str, &[0u8], &dst_prime].concat();
+  let b_0 = hasher.get_digest(&msg_prime);
+  let mut b_i = hasher.get_digest(&[&b_0[..], &[1u8], &dst_prime].concat());
+
+  let mut uniform_bytes = b_i.to_vec();
+  for i in 2..=ell {
+    let b_0_xor_b_i: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+    b_i = hasher.get_digest(&[&b_0_xor_b_i[..], &[i as u8], &dst_prime].concat());
+    uniform_bytes.extend_from_slice(&b_i);
+  }
+  uniform_bytes.truncate(len_in_bytes);
+  Ok(uniform_bytes)
+}
+
+// hash_to_field w/ count = 2 and m = 2 (Section 5.2)
+fn hash_to_field(msg: &[u8], dst: &[u8]) -> [Fq2; 2] {
+  let f = P::base_prime_field();
+  let uniform_bytes = expand_message_xmd(msg, dst, 2 * 2 * L).unwrap();
+
+  let elem = |offset: usize| f.elem(&BigUint::from_bytes_be(&uniform_bytes[offset..offset + L]));
+  let u0 = Fq2::new(&elem(L), &elem(0));
+  let u1 = Fq2::new(&elem(3 * L), &elem(2 * L));
+  [u0, u1]
+}
+
+// sign of Fq2 element (Section 4.1)
+fn sgn0(x: &Fq2) -> bool {
+  let sign_0 = x.u0.e.bit(0);
+  let zero_0 = x.u0.is_zero();
+  let sign_1 = x.u1.e.bit(0);
+  sign_0 || (zero_0 && sign_1)
+}
+
+// simplified SWU map to E' (Section 6.6.2)
+fn map_to_curve_simple_swu(u: &Fq2) -> (Fq2, Fq2) {
+  let (A, B, Z) = (&*ISO_A, &*ISO_B, &*SSWU_Z);
+  let g = |x: &Fq2| x.sq() * x + A * x + B;
+
+  let u_sq = u.sq();
+  let tv1 = Z.sq() * u_sq.sq() + Z * &u_sq;
+  let x1 = if tv1.is_zero() {
+    B * (Z * A).inv()
+  } else {
+    -B * A.inv() * (tv1.inv() + Fq1::from_to_biguint(&1u8).into_fq2())
+  };
+  let (x, y) = match g(&x1).sqrt() {
+    Some(y) => (x1, y),
+    None => {
+      // g(x2) = Z^3 u^6 g(x1) is a square if g(x1) is not
+      let x2 = Z * &u_sq * &x1;
+      let y = g(&x2).sqrt().unwrap();
+      (x2, y)
+    },
+  };
+  let y = if sgn0(u) != sgn0(&y) { -y } else { y };
+  (x, y)
+}
+
+fn eval_poly(coeffs: &[Fq2], x: &Fq2) -> Fq2 {
+  coeffs.iter().rev().fold(Fq2::zero(), |acc, c| acc * x + c)
+}
+
+// 3-isogeny map from E' to E (Appendix E.3)
+fn iso_map(x: &Fq2, y: &Fq2) -> G2Point {
+  let x_den = eval_poly(&X_DEN, x);
+  let y_den = eval_poly(&Y_DEN, x);
+  if x_den.is_zero() || y_den.is_zero() {
+    return G2Point::zero();
+  }
+  let iso_x = eval_poly(&X_NUM, x) * x_den.inv();
+  let iso_y = y * eval_poly(&Y_NUM, x) * y_den.inv();
+  G2Point::new(&iso_x, &iso_y)
+}
+
+fn map_to_curve(u: &Fq2) -> G2Point {
+  let (x, y) = map_to_curve_simple_swu(u);
+  iso_map(&x, &y)
+}
+
+fn clear_cofactor(p: &G2Point) -> G2Point {
+  multi_scalar_mul(std::slice::from_ref(p), std::slice::from_ref(&H_EFF))
+}
+
+// hashes msg to a point in the r-order subgroup of G2 w/ domain separation tag dst
+pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> G2Point {
+  let [u0, u1] = hash_to_field(msg, dst);
+  let q0 = map_to_curve(&u0);
+  let q1 = map_to_curve(&u1);
+  clear_cofactor(&(q0 + q1))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn to_hex(buf: &[u8]) -> String {
+    buf.iter().map(|x| format!("{:02x}", x)).collect()
+  }
+
+  // test vectors from RFC 9380 Appendix K.1
+  #[test]
+  fn expand_message_xmd_sha256() {
+    let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    let test_cases: [(&[u8], &str); 2] = [
+      (b"", "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"),
+      (b"abc", "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"),
+    ];
+    for (msg, exp) in test_cases {
+      let act = expand_message_xmd(msg, dst, 0x20).unwrap();
+      assert_eq!(to_hex(&act), exp);
+    }
+    assert!(expand_message_xmd(b"", dst, 255 * 32 + 1).is_err());
+  }
+
+  // test vectors from RFC 9380 Appendix J.10.1
+  #[test]
+  fn bls12381g2_xmd_sha256_sswu_ro() {
+    let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+    let test_cases: [(&[u8], [&[u8]; 4]); 2] = [
+      (b"", [
+        b"0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a",
+        b"05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d",
+        b"0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92",
+        b"12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6",
+      ]),
+      (b"abc", [
+        b"02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6",
+        b"139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8",
+        b"1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48",
+        b"00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16",
+      ]),
+    ];
+    for (msg, [x0, x1, y0, y1]) in test_cases {
+      let act = hash_to_curve(msg, dst);
+      let exp = G2Point::new(&fq2(x0, x1), &fq2(y0, y1));
+      assert_eq!(act, exp);
+    }
+  }
+
+  #[test]
+  fn hash_to_curve_output_in_subgroup() {
+    let p = hash_to_curve(b"chili crab", b"TEST_DST");
+    assert!(!p.is_zero());
+    if let G2Point::Rational { x, y } = &p {
+      assert!(G2Point::is_on_curve(x, y));
+    }
+    let r = P::subgroup().order();
+    assert!(multi_scalar_mul(&[p], &[r]).is_zero());
+
+    // different DSTs give independent points
+    assert_ne!(hash_to_curve(b"chili crab", b"TEST_DST"), hash_to_curve(b"chili crab", b"OTHER_DST"));
+  }
+}
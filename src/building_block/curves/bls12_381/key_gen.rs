@@ -0,0 +1,178 @@
+use crate::building_block::{
+  curves::bls12_381::{
+    params::Params as P,
+    private_key::PrivateKey,
+  },
+  hasher::{
+    hasher::Hasher,
+    hkdf::Hkdf,
+    sha256::Sha256,
+  },
+};
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+// implementation based on:
+// - https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-2.3 (KeyGen)
+// - https://eips.ethereum.org/EIPS/eip-2333
+
+const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+const L: usize = 48;  // ceil((3 * ceil(log2(r))) / 16)
+const LAMPORT_CHUNKS: usize = 255;
+
+// HKDF w/ HMAC-SHA256
+pub struct BlsKeyGen {
+  hkdf: Hkdf<32>,
+}
+
+impl BlsKeyGen {
+  pub fn new() -> Self {
+    BlsKeyGen { hkdf: Hkdf::new(Box::new(Sha256())) }
+  }
+
+  // KeyGen(IKM, key_info) of the IETF BLS signature draft
+  pub fn key_gen(&self, ikm: &[u8], key_info: &[u8]) -> Result<PrivateKey, String> {
+    if ikm.len() < 32 {
+      return Err("IKM needs to be at least 32 bytes".to_string());
+    }
+    let r = P::subgroup().order();
+    let mut salt = KEYGEN_SALT.to_vec();
+    let mut value = BigUint::zero();
+
+    while value.is_zero() {
+      salt = Sha256().get_digest(&salt).to_vec();
+      let prk = self.hkdf.extract(&salt, &[ikm, &[0u8]].concat());
+      // L is well below the output length limit of 255 * 32
+      let okm = self.hkdf.expand(&prk, &[key_info, &(L as u16).to_be_bytes()].concat(), L).unwrap();
+      value = BigUint::from_bytes_be(&okm) % &r;
+    }
+    Ok(PrivateKey { value })
+  }
+
+  // 255 32-byte chunks derived from IKM
+  fn ikm_to_lamport_sk(&self, ikm: &[u8], salt: &[u8]) -> Vec<Vec<u8>> {
+    let prk = self.hkdf.extract(salt, ikm);
+    // 32 * 255 is exactly the output length limit
+    let okm = self.hkdf.expand(&prk, b"", 32 * LAMPORT_CHUNKS).unwrap();
+    okm.chunks(32).map(|x| x.to_vec()).collect()
+  }
+
+  // compressed Lamport public key derived from the parent private key
+  fn parent_sk_to_lamport_pk(&self, parent_sk: &PrivateKey, index: u32) -> [u8; 32] {
+    let salt = index.to_be_bytes();
+    let ikm = parent_sk.to_bytes();
+    let lamport_0 = self.ikm_to_lamport_sk(&ikm, &salt);
+
+    let not_ikm: Vec<u8> = ikm.iter().map(|x| !x).collect();
+    let lamport_1 = self.ikm_to_lamport_sk(&not_ikm, &salt);
+
+    let lamport_pk: Vec<u8> = lamport_0.iter().chain(lamport_1.iter())
+      .flat_map(|x| Sha256().get_digest(x))
+      .collect();
+    Sha256().get_digest(&lamport_pk)
+  }
+
+  pub fn derive_master_sk(&self, seed: &[u8]) -> Result<PrivateKey, String> {
+    self.key_gen(seed, b"")
+  }
+
+  pub fn derive_child_sk(&self, parent_sk: &PrivateKey, index: u32) -> PrivateKey {
+    let lamport_pk = self.parent_sk_to_lamport_pk(parent_sk, index);
+    // lamport_pk is 32 bytes long, so key_gen never fails
+    self.key_gen(&lamport_pk, b"").unwrap()
+  }
+
+  // derives private key for a path like m/12381/3600/0/0/0
+  pub fn derive_path(&self, seed: &[u8], path: &str) -> Result<PrivateKey, String> {
+    let mut nodes = path.split('/');
+    if nodes.next() != Some("m") {
+      return Err(format!("Path needs to start w/ m: {}", path));
+    }
+    let mut sk = self.derive_master_sk(seed)?;
+    for node in nodes {
+      let index = node.parse::<u32>().map_err(|_| format!("Invalid path node: {}", node))?;
+      sk = self.derive_child_sk(&sk, index);
+    }
+    Ok(sk)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn biguint(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 10).unwrap()
+  }
+
+  #[test]
+  fn eip2333_test_vectors() {
+    let key_gen = BlsKeyGen::new();
+    let test_cases = [
+      (
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+        0,
+        "20397789859736650942317412262472558107875392172444076792671091975210932703118",
+      ),
+      (
+        "3141592653589793238462643383279502884197169399375105820974944592",
+        "29757020647961307431480504535336562678282505419141012933316116377660817309383",
+        3141592653,
+        "25457201688850691947727629385191704516744796114925897962676248250929345014287",
+      ),
+      (
+        "0099FF991111002299DD7744EE3355BBDD8844115566CC55663355668888CC00",
+        "27580842291869792442942448775674722299803720648445448686099262467207037398656",
+        4294967295,
+        "29358610794459428860402234341874281240803786294062035874021252734817515685787",
+      ),
+      (
+        "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+        "19022158461524446591288038168518313374041767046816487870552872741050760015818",
+        42,
+        "31372231650479070279774297061823572166496564838472787488249775572789064611981",
+      ),
+    ];
+    for (seed, master_sk, index, child_sk) in test_cases {
+      let seed = hex::decode(seed).unwrap();
+      let master = key_gen.derive_master_sk(&seed).unwrap();
+      assert_eq!(master.value, biguint(master_sk));
+
+      let child = key_gen.derive_child_sk(&master, index);
+      assert_eq!(child.value, biguint(child_sk));
+    }
+  }
+
+  #[test]
+  fn derive_path() {
+    let key_gen = BlsKeyGen::new();
+    let seed = hex::decode("3141592653589793238462643383279502884197169399375105820974944592").unwrap();
+
+    let sk = key_gen.derive_path(&seed, "m/3141592653").unwrap();
+    assert_eq!(sk.value, biguint("25457201688850691947727629385191704516744796114925897962676248250929345014287"));
+
+    let master = key_gen.derive_path(&seed, "m").unwrap();
+    assert_eq!(master.value, biguint("29757020647961307431480504535336562678282505419141012933316116377660817309383"));
+
+    assert!(key_gen.derive_path(&seed, "12381/3600").is_err());
+    assert!(key_gen.derive_path(&seed, "m/12381'/3600").is_err());
+    assert!(key_gen.derive_path(&seed, "m/4294967296").is_err());
+  }
+
+  #[test]
+  fn key_gen_requires_32_byte_ikm() {
+    let key_gen = BlsKeyGen::new();
+    assert!(key_gen.key_gen(&[0u8; 31], b"").is_err());
+    assert!(key_gen.key_gen(&[0u8; 32], b"").is_ok());
+  }
+
+  #[test]
+  fn key_info_changes_key() {
+    let key_gen = BlsKeyGen::new();
+    let ikm = [1u8; 32];
+    let sk1 = key_gen.key_gen(&ikm, b"").unwrap();
+    let sk2 = key_gen.key_gen(&ikm, b"validator").unwrap();
+    assert_ne!(sk1.value, sk2.value);
+  }
+}
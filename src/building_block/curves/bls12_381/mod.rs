@@ -7,10 +7,13 @@ pub mod g1_point;
 pub mod g2_point;
 pub mod g12_point;
 pub mod gt_point;
+pub mod hash_to_curve;
+pub mod key_gen;
 pub mod pairing;
 pub mod params;
 pub mod private_key;
 pub mod rational_function;
 pub mod reduce;
 pub mod signature;
+pub mod threshold;
 
@@ -88,9 +88,21 @@ impl Pairing {
     println!("Running Miller loop G1-G2...");
 
     let intmed = self.calc_g1_g2(&p1, &p2);
-
-    // apply final exponentiation
     println!("Applying final exponentiation...");
+    Self::final_exponentiation(&intmed)
+  }
+
+  // product of tate pairings of the given pairs. the results of the Miller loops
+  // are multiplied first so that the final exponentiation is applied only once
+  pub fn tate_product(&self, pairs: &[(&G1Point, &G2Point)]) -> GTPoint {
+    let mut intmed = Fq12::from(&1u8 as &dyn ToBigUint);
+    for (p1, p2) in pairs {
+      intmed = intmed * self.calc_g1_g2(p1, p2);
+    }
+    Self::final_exponentiation(&intmed)
+  }
+
+  fn final_exponentiation(intmed: &Fq12) -> GTPoint {
     let one = BigUint::from(1u8);
     let q_to_12 = P::base_prime_field().order_ref().pow(P::embedding_degree());
     let r = P::subgroup().order();
@@ -3,8 +3,8 @@ use num_bigint::{
   BigUint,
   RandBigInt,
 };
-use num_traits::One;
-use rand::SeedableRng;
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore, SeedableRng};
 use rand_chacha::ChaCha12Rng;
 
 pub struct PrivateKey {
@@ -13,8 +13,10 @@ pub struct PrivateKey {
 
 impl PrivateKey {
   pub fn new() -> Self {
-    let mut rng = ChaCha12Rng::from_entropy();
+    Self::new_with_rng(&mut ChaCha12Rng::from_entropy())
+  }
 
+  pub fn new_with_rng(rng: &mut (impl RngCore + CryptoRng)) -> Self {
     // integer between 1 and r-1 where r is the subgroup order
     let value = rng.gen_biguint_range(
       &BigUint::one(),
@@ -23,5 +25,45 @@ impl PrivateKey {
 
     Self { value }
   }
+
+  // 32-byte big-endian integer in [1, r-1]
+  pub fn from_bytes(buf: &[u8; 32]) -> Result<Self, String> {
+    let value = BigUint::from_bytes_be(buf);
+    if value.is_zero() || &value >= P::subgroup().order_ref() {
+      return Err("Private key needs to be in [1, r-1]".to_string());
+    }
+    Ok(Self { value })
+  }
+
+  pub fn to_bytes(&self) -> [u8; 32] {
+    let bytes_be = self.value.to_bytes_be();
+    let mut buf = [0u8; 32];
+    buf[32 - bytes_be.len()..].copy_from_slice(&bytes_be);
+    buf
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn to_and_from_bytes() {
+    let sk = PrivateKey::new();
+    let sk2 = PrivateKey::from_bytes(&sk.to_bytes()).unwrap();
+    assert_eq!(sk.value, sk2.value);
+
+    let one = PrivateKey::from_bytes(&{ let mut buf = [0u8; 32]; buf[31] = 1; buf }).unwrap();
+    assert_eq!(one.value, BigUint::one());
+  }
+
+  #[test]
+  fn out_of_range_bytes() {
+    assert!(PrivateKey::from_bytes(&[0u8; 32]).is_err());
+
+    let r: [u8; 32] = P::subgroup().order_ref().to_bytes_be().try_into().unwrap();
+    assert!(PrivateKey::from_bytes(&r).is_err());
+    assert!(PrivateKey::from_bytes(&[0xffu8; 32]).is_err());
+  }
 }
 
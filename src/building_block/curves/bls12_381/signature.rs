@@ -1,23 +1,73 @@
-use crate::building_block::curves::bls12_381::{
-  g1_point::G1Point,
-  g2_point::G2Point,
-  pairing::Pairing,
-  private_key::PrivateKey,
+use crate::building_block::{
+  curves::bls12_381::{
+    g1_point::G1Point,
+    g2_point::G2Point,
+    hash_to_curve::hash_to_curve,
+    pairing::Pairing,
+    private_key::PrivateKey,
+  },
+  zero::Zero,
 };
+use std::collections::HashSet;
+
+// schemes based on: https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05
+// public keys are in G1 and signatures are in G2 (minimal-pubkey-size variant).
+// messages are hashed to G2 w/ the BLS12381G2_XMD:SHA-256_SSWU_RO_ suite of RFC 9380
+// using the DST of the scheme
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Scheme {
+  // aggregate verification requires all messages to be distinct
+  Basic,
+  // public key is prepended to the message before signing
+  MessageAugmentation,
+  // public keys come w/ proofs of possession that prevent rogue key attacks
+  ProofOfPossession,
+}
+
+impl Scheme {
+  pub fn dst(&self) -> &'static [u8] {
+    match self {
+      Scheme::Basic => b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_",
+      Scheme::MessageAugmentation => b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_",
+      Scheme::ProofOfPossession => b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_",
+    }
+  }
+}
+
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 
 pub struct Signer {
   g1: G1Point,
   pairing: Pairing,
+  scheme: Scheme,
 }
 
 impl Signer {
   pub fn new() -> Self {
+    Signer::with_scheme(Scheme::Basic)
+  }
+
+  pub fn with_scheme(scheme: Scheme) -> Self {
     let g1 = G1Point::g();
     let pairing = Pairing::new();
 
     Signer {
       g1,
       pairing,
+      scheme,
+    }
+  }
+
+  fn hash_to_point(dst: &[u8], m: &[u8]) -> G2Point {
+    hash_to_curve(m, dst)
+  }
+
+  // message actually signed under the scheme
+  fn augment(&self, m: &[u8], pk: &G1Point) -> Vec<u8> {
+    match self.scheme {
+      Scheme::MessageAugmentation => [&pk.to_compressed_bytes()[..], m].concat(),
+      _ => m.to_vec(),
     }
   }
 
@@ -27,16 +77,93 @@ impl Signer {
   }
 
   pub fn sign(&self, m: &Vec<u8>, sk: &PrivateKey) -> G2Point {
-    let hash_m = &G2Point::hash_to_g2point(m);
+    // public key is derived only when it needs to be prepended to the message
+    let m = match self.scheme {
+      Scheme::MessageAugmentation => self.augment(m, &self.gen_public_key(sk)),
+      _ => m.clone(),
+    };
+    let hash_m = &Self::hash_to_point(self.scheme.dst(), &m);
     hash_m * sk
   }
 
   pub fn verify(&self, m: &Vec<u8>, sig: &G2Point, pk: &G1Point) -> bool {
-    let hash_m = &G2Point::hash_to_g2point(m);
-    let lhs = self.pairing.tate(&self.g1, sig); 
-    let rhs = self.pairing.tate(pk, hash_m); 
+    if pk.is_zero() || sig.is_zero() {
+      return false;
+    }
+    let hash_m = &Self::hash_to_point(self.scheme.dst(), &self.augment(m, pk));
+    let lhs = self.pairing.tate(&self.g1, sig);
+    let rhs = self.pairing.tate(pk, hash_m);
+    lhs == rhs
+  }
+
+  pub fn aggregate(&self, sigs: &[G2Point]) -> Result<G2Point, String> {
+    if sigs.is_empty() {
+      return Err("No signatures to aggregate".to_string());
+    }
+    let mut agg_sig = G2Point::zero();
+    for sig in sigs {
+      agg_sig += sig.clone();
+    }
+    Ok(agg_sig)
+  }
+
+  // e(g1, sig) = e(pk_1, H(m_1)) * ... * e(pk_n, H(m_n))
+  pub fn aggregate_verify(&self, pks: &[G1Point], ms: &[Vec<u8>], agg_sig: &G2Point) -> bool {
+    if pks.is_empty() || pks.len() != ms.len() || agg_sig.is_zero() {
+      return false;
+    }
+    if pks.iter().any(|pk| pk.is_zero()) {
+      return false;
+    }
+    if self.scheme == Scheme::Basic {
+      let distinct_ms: HashSet<&Vec<u8>> = ms.iter().collect();
+      if distinct_ms.len() != ms.len() {
+        return false;
+      }
+    }
+
+    let hash_ms: Vec<G2Point> = pks.iter().zip(ms.iter()).map(|(pk, m)| {
+      Self::hash_to_point(self.scheme.dst(), &self.augment(m, pk))
+    }).collect();
+    let pairs: Vec<(&G1Point, &G2Point)> = pks.iter().zip(hash_ms.iter()).collect();
+
+    let lhs = self.pairing.tate(&self.g1, agg_sig);
+    let rhs = self.pairing.tate_product(&pairs);
     lhs == rhs
   }
+
+  // signature on the serialized public key w/ a dedicated DST
+  pub fn pop_prove(&self, sk: &PrivateKey) -> Result<G2Point, String> {
+    if self.scheme != Scheme::ProofOfPossession {
+      return Err("Proof of possession requires ProofOfPossession scheme".to_string());
+    }
+    let pk = self.gen_public_key(sk);
+    let hash_pk = &Self::hash_to_point(POP_DST, &pk.to_compressed_bytes());
+    Ok(hash_pk * sk)
+  }
+
+  pub fn pop_verify(&self, pk: &G1Point, proof: &G2Point) -> bool {
+    if self.scheme != Scheme::ProofOfPossession || pk.is_zero() || proof.is_zero() {
+      return false;
+    }
+    let hash_pk = &Self::hash_to_point(POP_DST, &pk.to_compressed_bytes());
+    let lhs = self.pairing.tate(&self.g1, proof);
+    let rhs = self.pairing.tate(pk, hash_pk);
+    lhs == rhs
+  }
+
+  // verifies aggregate signature on a common message w/ a single pair of pairings.
+  // the public keys need to have been checked w/ pop_verify beforehand
+  pub fn fast_aggregate_verify(&self, pks: &[G1Point], m: &Vec<u8>, agg_sig: &G2Point) -> bool {
+    if self.scheme != Scheme::ProofOfPossession || pks.is_empty() {
+      return false;
+    }
+    let mut agg_pk = G1Point::zero();
+    for pk in pks {
+      agg_pk += pk.clone();
+    }
+    self.verify(m, agg_sig, &agg_pk)
+  }
 }
 
 #[cfg(test)]
@@ -56,5 +183,77 @@ mod tests {
     let is_valid_sig = signer.verify(m, sig, pk);
     assert!(is_valid_sig);
   }
-}
 
+  #[test]
+  fn aggregate_verify_distinct_messages() {
+    for scheme in [Scheme::Basic, Scheme::MessageAugmentation] {
+      let signer = Signer::with_scheme(scheme);
+      let sks = [PrivateKey::new(), PrivateKey::new()];
+      let pks: Vec<G1Point> = sks.iter().map(|sk| signer.gen_public_key(sk)).collect();
+      let ms = vec![b"chili crab".to_vec(), b"laksa".to_vec()];
+
+      let sigs: Vec<G2Point> = sks.iter().zip(ms.iter()).map(|(sk, m)| signer.sign(m, sk)).collect();
+      let agg_sig = signer.aggregate(&sigs).unwrap();
+      assert!(signer.aggregate_verify(&pks, &ms, &agg_sig));
+
+      // swapped messages
+      let swapped_ms = vec![ms[1].clone(), ms[0].clone()];
+      assert!(!signer.aggregate_verify(&pks, &swapped_ms, &agg_sig));
+    }
+  }
+
+  #[test]
+  fn basic_scheme_rejects_duplicate_messages() {
+    let signer = Signer::new();
+    let sks = [PrivateKey::new(), PrivateKey::new()];
+    let pks: Vec<G1Point> = sks.iter().map(|sk| signer.gen_public_key(sk)).collect();
+    let ms = vec![b"chili crab".to_vec(), b"chili crab".to_vec()];
+
+    let sigs: Vec<G2Point> = sks.iter().zip(ms.iter()).map(|(sk, m)| signer.sign(m, sk)).collect();
+    let agg_sig = signer.aggregate(&sigs).unwrap();
+    assert!(!signer.aggregate_verify(&pks, &ms, &agg_sig));
+  }
+
+  #[test]
+  fn empty_aggregation() {
+    let signer = Signer::new();
+    assert!(signer.aggregate(&[]).is_err());
+    assert!(!signer.aggregate_verify(&[], &[], &G2Point::g()));
+  }
+
+  #[test]
+  fn proof_of_possession() {
+    let signer = Signer::with_scheme(Scheme::ProofOfPossession);
+    let sk = PrivateKey::new();
+    let pk = signer.gen_public_key(&sk);
+
+    let proof = signer.pop_prove(&sk).unwrap();
+    assert!(signer.pop_verify(&pk, &proof));
+
+    // proof is not a valid signature on the serialized public key
+    assert!(!signer.verify(&pk.to_compressed_bytes().to_vec(), &proof, &pk));
+
+    // proof of other key
+    let other_pk = signer.gen_public_key(&PrivateKey::new());
+    assert!(!signer.pop_verify(&other_pk, &proof));
+
+    assert!(Signer::new().pop_prove(&sk).is_err());
+  }
+
+  #[test]
+  fn fast_aggregate_verify() {
+    let signer = Signer::with_scheme(Scheme::ProofOfPossession);
+    let sks = [PrivateKey::new(), PrivateKey::new(), PrivateKey::new()];
+    let pks: Vec<G1Point> = sks.iter().map(|sk| signer.gen_public_key(sk)).collect();
+    let m = b"chili crab".to_vec();
+
+    let sigs: Vec<G2Point> = sks.iter().map(|sk| signer.sign(&m, sk)).collect();
+    let agg_sig = signer.aggregate(&sigs).unwrap();
+    assert!(signer.fast_aggregate_verify(&pks, &m, &agg_sig));
+    assert!(!signer.fast_aggregate_verify(&pks[0..2], &m, &agg_sig));
+    assert!(!signer.fast_aggregate_verify(&pks, &b"laksa".to_vec(), &agg_sig));
+
+    // not available w/o proof of possession
+    assert!(!Signer::new().fast_aggregate_verify(&pks, &m, &agg_sig));
+  }
+}
@@ -0,0 +1,289 @@
+use crate::building_block::{
+  curves::bls12_381::{
+    g1_point::G1Point,
+    g2_point::G2Point,
+    params::Params as P,
+    private_key::PrivateKey,
+    signature::Signer,
+  },
+  field::{
+    polynomial::Polynomial,
+    prime_field_elem::PrimeFieldElem,
+  },
+  random_number::RandomNumber,
+  zero::Zero,
+};
+use rand::{CryptoRng, RngCore};
+use std::collections::HashSet;
+
+// t-of-n threshold BLS signatures. the private key is split w/ Shamir secret sharing
+// and t partial signatures are combined w/ Lagrange interpolation at x = 0 in the exponent
+
+pub struct KeyShare {
+  pub index: u32,  // x-coordinate of the share. needs to be non-zero
+  pub priv_key: PrivateKey,
+}
+
+pub struct PublicKeyShare {
+  pub index: u32,
+  pub pub_key: G1Point,
+}
+
+#[derive(Clone)]
+pub struct PartialSignature {
+  pub index: u32,
+  pub sig: G2Point,
+}
+
+pub struct ThresholdSigner {
+  pub t: usize,
+  pub n: usize,
+  signer: Signer,
+}
+
+impl ThresholdSigner {
+  pub fn new(t: usize, n: usize) -> Result<Self, String> {
+    if t == 0 || t > n {
+      return Err(format!("Threshold needs to be in [1, {}], but got {}", n, t));
+    }
+    if n > u32::MAX as usize {
+      return Err("Too many shares".to_string());
+    }
+    Ok(ThresholdSigner { t, n, signer: Signer::new() })
+  }
+
+  // shares are f(1), ..., f(n) of a random degree t-1 polynomial f w/ f(0) = priv_key
+  pub fn split(&self, priv_key: &PrivateKey) -> Vec<KeyShare> {
+    self.split_with_rng(priv_key, &mut RandomNumber::new().gen)
+  }
+
+  pub fn split_with_rng(&self, priv_key: &PrivateKey, rng: &mut (impl RngCore + CryptoRng)) -> Vec<KeyShare> {
+    let f = P::subgroup();
+    let mut coeffs = vec![f.elem(&priv_key.value)];
+    for _ in 1..self.t {
+      coeffs.push(f.rand_elem_with_rng(false, rng));
+    }
+    let poly = Polynomial::new(&f, &coeffs);
+
+    (1..=self.n as u32).map(|index| {
+      let share = poly.eval_at(&f.elem(&index));
+      KeyShare { index, priv_key: PrivateKey { value: share.e } }
+    }).collect()
+  }
+
+  pub fn gen_public_key(&self, priv_key: &PrivateKey) -> G1Point {
+    self.signer.gen_public_key(priv_key)
+  }
+
+  pub fn gen_public_key_share(&self, share: &KeyShare) -> PublicKeyShare {
+    PublicKeyShare {
+      index: share.index,
+      pub_key: self.signer.gen_public_key(&share.priv_key),
+    }
+  }
+
+  pub fn partial_sign(&self, m: &Vec<u8>, share: &KeyShare) -> PartialSignature {
+    PartialSignature {
+      index: share.index,
+      sig: self.signer.sign(m, &share.priv_key),
+    }
+  }
+
+  pub fn partial_verify(&self, m: &Vec<u8>, partial_sig: &PartialSignature, pub_key_share: &PublicKeyShare) -> bool {
+    if partial_sig.index != pub_key_share.index {
+      return false;
+    }
+    self.signer.verify(m, &partial_sig.sig, &pub_key_share.pub_key)
+  }
+
+  // Lagrange basis polynomials for the given x-coordinates evaluated at x = 0
+  // checks that there are at least t indices and that all of them are distinct and in [1, n]
+  fn validate_indices(&self, indices: &[u32]) -> Result<(), String> {
+    if indices.len() < self.t {
+      return Err(format!("At least {} shares are required, but got {}", self.t, indices.len()));
+    }
+    let distinct_indices: HashSet<&u32> = indices.iter().collect();
+    if distinct_indices.len() != indices.len() {
+      return Err("Share indices need to be distinct".to_string());
+    }
+    if indices.iter().any(|i| *i == 0 || *i as usize > self.n) {
+      return Err(format!("Share indices need to be in [1, {}]", self.n));
+    }
+    Ok(())
+  }
+
+  fn lagrange_coeffs_at_zero(&self, indices: &[u32]) -> Result<Vec<PrimeFieldElem>, String> {
+    self.validate_indices(indices)?;
+
+    // l_i(0) = prod_{j != i} x_j / (x_j - x_i)
+    let f = P::subgroup();
+    let coeffs = indices.iter().map(|i| {
+      let x_i = f.elem(i);
+      let mut l_i = f.elem(&1u8);
+      for j in indices.iter().filter(|j| *j != i) {
+        let x_j = f.elem(j);
+        l_i = l_i * &x_j / (&x_j - &x_i);
+      }
+      l_i
+    }).collect();
+    Ok(coeffs)
+  }
+
+  // combines the first t partial signatures into the signature of the group private key.
+  // the indices of all the given partial signatures are validated, not only of the first t
+  pub fn combine(&self, partial_sigs: &[PartialSignature]) -> Result<G2Point, String> {
+    let indices: Vec<u32> = partial_sigs.iter().map(|x| x.index).collect();
+    self.validate_indices(&indices)?;
+
+    let partial_sigs = &partial_sigs[0..self.t];
+    let indices = &indices[0..self.t];
+    let lagrange_coeffs = self.lagrange_coeffs_at_zero(indices)?;
+
+    let mut sig = G2Point::zero();
+    for (partial_sig, l_i) in partial_sigs.iter().zip(lagrange_coeffs.iter()) {
+      sig += &partial_sig.sig * l_i;
+    }
+    Ok(sig)
+  }
+
+  // recovers the group private key from the first t shares.
+  // the indices of all the given shares are validated, not only of the first t
+  pub fn reconstruct(&self, shares: &[KeyShare]) -> Result<PrivateKey, String> {
+    let indices: Vec<u32> = shares.iter().map(|x| x.index).collect();
+    self.validate_indices(&indices)?;
+
+    let shares = &shares[0..self.t];
+    let indices = &indices[0..self.t];
+    let lagrange_coeffs = self.lagrange_coeffs_at_zero(indices)?;
+
+    let f = P::subgroup();
+    let mut priv_key = f.elem(&0u8);
+    for (share, l_i) in shares.iter().zip(lagrange_coeffs.iter()) {
+      priv_key += f.elem(&share.priv_key.value) * l_i;
+    }
+    Ok(PrivateKey { value: priv_key.e })
+  }
+
+  pub fn verify(&self, m: &Vec<u8>, sig: &G2Point, pub_key: &G1Point) -> bool {
+    self.signer.verify(m, sig, pub_key)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn split_and_reconstruct() {
+    let signer = ThresholdSigner::new(3, 5).unwrap();
+    let priv_key = PrivateKey::new();
+    let shares = signer.split(&priv_key);
+    assert_eq!(shares.len(), 5);
+
+    let subsets: [&[usize]; 3] = [&[0, 1, 2], &[4, 2, 0], &[1, 3, 4]];
+    for subset in subsets {
+      let subset_shares: Vec<KeyShare> = subset.iter().map(|i| KeyShare {
+        index: shares[*i].index,
+        priv_key: PrivateKey { value: shares[*i].priv_key.value.clone() },
+      }).collect();
+      let recovered = signer.reconstruct(&subset_shares).unwrap();
+      assert_eq!(recovered.value, priv_key.value);
+    }
+
+    // less than t shares
+    assert!(signer.reconstruct(&shares[0..2]).is_err());
+  }
+
+  #[test]
+  fn split_with_seeded_rng() {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha12Rng;
+
+    let signer = ThresholdSigner::new(2, 3).unwrap();
+    let priv_key = PrivateKey::new_with_rng(&mut ChaCha12Rng::seed_from_u64(1));
+    let shares1 = signer.split_with_rng(&priv_key, &mut ChaCha12Rng::seed_from_u64(2));
+    let shares2 = signer.split_with_rng(&priv_key, &mut ChaCha12Rng::seed_from_u64(2));
+    for (a, b) in shares1.iter().zip(shares2.iter()) {
+      assert_eq!(a.priv_key.value, b.priv_key.value);
+    }
+    assert_eq!(signer.reconstruct(&shares1[1..]).unwrap().value, priv_key.value);
+  }
+
+  #[test]
+  fn invalid_parameters() {
+    assert!(ThresholdSigner::new(0, 3).is_err());
+    assert!(ThresholdSigner::new(4, 3).is_err());
+    assert!(ThresholdSigner::new(3, 3).is_ok());
+  }
+
+  #[test]
+  fn invalid_indices() {
+    let signer = ThresholdSigner::new(2, 3).unwrap();
+    assert!(signer.lagrange_coeffs_at_zero(&[1, 1]).is_err());
+    assert!(signer.lagrange_coeffs_at_zero(&[0, 1]).is_err());
+    assert!(signer.lagrange_coeffs_at_zero(&[1, 4]).is_err());
+    assert!(signer.lagrange_coeffs_at_zero(&[3, 1]).is_ok());
+  }
+
+  #[test]
+  fn combine_partial_signatures() {
+    let signer = ThresholdSigner::new(2, 3).unwrap();
+    let priv_key = PrivateKey::new();
+    let pub_key = signer.gen_public_key(&priv_key);
+    let shares = signer.split(&priv_key);
+    let m = b"chili crab".to_vec();
+
+    let partial_sigs: Vec<PartialSignature> = shares.iter().map(|share| signer.partial_sign(&m, share)).collect();
+
+    // any 2 partial signatures result in the signature of the group private key
+    let exp = Signer::new().sign(&m, &priv_key);
+    let sig_12 = signer.combine(&partial_sigs[0..2]).unwrap();
+    let sig_31 = signer.combine(&[partial_sigs[2].clone(), partial_sigs[0].clone()]).unwrap();
+    assert_eq!(sig_12, exp);
+    assert_eq!(sig_31, exp);
+    assert!(signer.verify(&m, &sig_12, &pub_key));
+
+    assert!(signer.combine(&partial_sigs[0..1]).is_err());
+
+    // shares beyond the first t are validated as well
+    assert_eq!(signer.combine(&partial_sigs).unwrap(), exp);
+    let dup_tail = [partial_sigs[0].clone(), partial_sigs[1].clone(), partial_sigs[0].clone()];
+    assert!(signer.combine(&dup_tail).is_err());
+    let out_of_range_tail = [
+      partial_sigs[0].clone(),
+      partial_sigs[1].clone(),
+      PartialSignature { index: 4, sig: partial_sigs[2].sig.clone() },
+    ];
+    assert!(signer.combine(&out_of_range_tail).is_err());
+  }
+
+  #[test]
+  fn reconstruct_validates_all_shares() {
+    let signer = ThresholdSigner::new(2, 3).unwrap();
+    let priv_key = PrivateKey::new();
+    let shares = signer.split(&priv_key);
+    let copy = |i: usize| KeyShare {
+      index: shares[i].index,
+      priv_key: PrivateKey { value: shares[i].priv_key.value.clone() },
+    };
+
+    assert_eq!(signer.reconstruct(&[copy(0), copy(1), copy(2)]).unwrap().value, priv_key.value);
+    assert!(signer.reconstruct(&[copy(0), copy(1), copy(1)]).is_err());
+  }
+
+  #[test]
+  fn verify_partial_signatures() {
+    let signer = ThresholdSigner::new(2, 3).unwrap();
+    let shares = signer.split(&PrivateKey::new());
+    let pub_key_shares: Vec<PublicKeyShare> = shares.iter().map(|x| signer.gen_public_key_share(x)).collect();
+    let m = b"chili crab".to_vec();
+
+    let partial_sig = signer.partial_sign(&m, &shares[1]);
+    assert!(signer.partial_verify(&m, &partial_sig, &pub_key_shares[1]));
+    assert!(!signer.partial_verify(&m, &partial_sig, &pub_key_shares[2]));
+
+    // valid signature labeled w/ an index different from the public key share
+    let mislabeled = PartialSignature { index: 3, sig: partial_sig.sig.clone() };
+    assert!(!signer.partial_verify(&m, &mislabeled, &pub_key_shares[1]));
+  }
+}
@@ -1,6 +1,7 @@
 use crate::{
   impl_scalar_mul_point,
   building_block::{
+    curves::multi_scalar_mul,
     field::{
       prime_field::PrimeField,
       prime_field_elem::PrimeFieldElem,
@@ -10,13 +11,24 @@ use crate::{
 };
 use std::{
   fmt,
-  ops::{Add, Sub, Mul},
+  ops::{Add, Sub, Mul, Neg},
   sync::Arc,
 };
 use once_cell::sync::Lazy;
 use num_bigint::BigUint;
 use num_traits::Zero as NumTraitsZero;
 
+// writes n as a little-endian integer to a 32-byte buffer w/ 0 padding on higher index side
+// e.g. 0xab in little-endian in 4 byte buffer is ab 00 00 00
+pub fn write_biguint_to_32_byte_buf_as_le_integer(n: &BigUint) -> [u8; 32] {
+  let bytes_le = n.to_bytes_le();
+  assert!(bytes_le.len() <= 32);
+
+  let mut buf = [0u8; 32];
+  buf[0..bytes_le.len()].copy_from_slice(&bytes_le);
+  buf
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Parity {
   Even,
@@ -110,6 +122,18 @@ impl AffinePoint {
     let d = -f.elem(&121665u32) / 121666u32;
     d
   }
+
+  // [8]P
+  pub fn mul_by_cofactor(&self) -> AffinePoint {
+    let p2 = self + self;
+    let p4 = &p2 + &p2;
+    &p4 + &p4
+  }
+
+  // sum of scalars[i] * points[i] w/ interleaved double-and-add
+  pub fn multi_scalar_mul(points: &[AffinePoint], scalars: &[BigUint]) -> AffinePoint {
+    multi_scalar_mul::multi_scalar_mul(points, scalars)
+  }
 }
 
 impl_scalar_mul_point!(PrimeFieldElem, AffinePoint);
@@ -163,6 +187,26 @@ impl PartialEq for AffinePoint {
 
 impl Eq for AffinePoint {}
 
+// -(x, y) = (-x, y) on twisted Edwards curve
+macro_rules! impl_neg {
+  ($target: ty) => {
+    impl Neg for $target {
+      type Output = AffinePoint;
+
+      fn neg(self) -> Self::Output {
+        match self {
+          AffinePoint::AtInfinity => AffinePoint::AtInfinity,
+          AffinePoint::Rational { x, y } => {
+            AffinePoint::new(&x.negate(), &y)
+          }
+        }
+      }
+    }
+  }
+}
+impl_neg!(AffinePoint);
+impl_neg!(&AffinePoint);
+
 impl Zero<AffinePoint> for AffinePoint {
   fn zero() -> AffinePoint {
       let f = AffinePoint::base_field();
@@ -237,4 +281,37 @@ mod tests {
       assert_eq!(act, exp);
     }
   }
+
+  #[test]
+  #[allow(non_snake_case)]
+  fn multi_scalar_mul() {
+    let B = &AffinePoint::B();
+    let f = &AffinePoint::curve_group();
+    let P = B * f.elem(&5u8);
+    let Q = B * f.elem(&11u8);
+
+    let act = AffinePoint::multi_scalar_mul(
+      &[B.clone(), P.clone(), Q.clone()],
+      &[BigUint::from(3u8), BigUint::from(0u8), BigUint::from(1234u32)],
+    );
+    let exp = B * f.elem(&3u8) + &Q * f.elem(&1234u32);
+    assert_eq!(act, exp);
+
+    assert_eq!(AffinePoint::multi_scalar_mul(&[], &[]), AffinePoint::zero());
+  }
+
+  #[test]
+  #[allow(non_snake_case)]
+  fn neg() {
+    let B = &AffinePoint::B();
+    assert_eq!(B + -B, AffinePoint::zero());
+  }
+
+  #[test]
+  #[allow(non_snake_case)]
+  fn mul_by_cofactor() {
+    let B = &AffinePoint::B();
+    let f = &AffinePoint::curve_group();
+    assert_eq!(B.mul_by_cofactor(), B * f.elem(&8u8));
+  }
 }
@@ -3,13 +3,17 @@ use crate::building_block::{
   curves::curve25519::affine_point::{
     AffinePoint,
     Parity,
+    write_biguint_to_32_byte_buf_as_le_integer,
   },
   field::prime_field_elem::PrimeFieldElem,
   hasher::{
     hasher::Hasher,
     sha512::Sha512,
   },
+  random_number::RandomNumber,
+  zero::Zero as _,
 };
+use rand::{CryptoRng, RngCore};
 use num_bigint::BigUint;
 use num_traits::Zero;
 use std::ops::Rem;
@@ -18,6 +22,19 @@ use std::ops::Rem;
 // - https://ed25519.cr.yp.to/ed25519-20110926.pdf
 // - https://datatracker.ietf.org/doc/html/rfc8032
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VerificationMode {
+  // [8][S]B = [8]R + [8][k]A w/ canonical encodings (RFC 8032)
+  Cofactored,
+  // [S]B = R + [k]A w/ canonical encodings
+  Cofactorless,
+  // cofactored equation accepting non-canonical encodings of R and A
+  // https://zips.z.cash/zip-0215
+  Zip215,
+  // cofactorless equation rejecting small-order R and A
+  Strict,
+}
+
 #[derive(Debug)]
 pub struct KeyPair {
   pub prv_key: [u8; 32],
@@ -44,18 +61,6 @@ impl Ed25519Sha512 {
   //   f.elem(&0u8)
   // }
 
-  fn write_biguint_to_32_byte_buf_as_le_integer(n: &BigUint) -> [u8; 32] {
-    // serialize n to little-endian integer
-    let bytes_le = n.to_bytes_le();
-    assert!(bytes_le.len() <= 32);
-
-    // then write to 32-byte buffer w/ 0 padding on higher index side
-    // e.g. 0xab in little-endian in 4 byte buffer is ab 00 00 00
-    let mut buf = [0u8; 32];
-    buf[0..bytes_le.len()].copy_from_slice(&bytes_le);
-    buf
-  }
-
   fn get_parity(e: &PrimeFieldElem) -> Parity {
     if (&e.e % 2u8).is_zero() { Parity::Even } else { Parity::Odd }
   }
@@ -68,7 +73,7 @@ impl Ed25519Sha512 {
         let x_parity = Ed25519Sha512::get_parity(&x);
 
         // write y to 32-byte buffer as little-endian integer
-        let mut buf = Self::write_biguint_to_32_byte_buf_as_le_integer(&y.e);
+        let mut buf = write_biguint_to_32_byte_buf_as_le_integer(&y.e);
 
         // the most significant bit of the last octet (=parity bit) should be 0
         assert_eq!(buf[31] & 0b1000_0000, 0);
@@ -82,7 +87,11 @@ impl Ed25519Sha512 {
     }
   }
 
-  fn decode_point(&self, pt_buf: &[u8; 32]) -> AffinePoint {
+  // non-canonical encodings (y >= q, or x = 0 w/ parity bit set) are rejected
+  // unless allow_non_canonical is true
+  fn decode_point(&self, pt_buf: &[u8; 32], allow_non_canonical: bool) -> Result<AffinePoint, String> {
+    let f = AffinePoint::base_field();
+    let d = AffinePoint::d();
     let mut pt_buf = pt_buf.clone();
 
     // get parity of x
@@ -91,10 +100,28 @@ impl Ed25519Sha512 {
     // clear parity bit
     pt_buf[31] &= 0b0111_1111;
 
-    let y = AffinePoint::base_field().elem(&BigUint::from_bytes_le(&pt_buf));
-    let x = AffinePoint::recover_x(&AffinePoint::d(), &y, x_parity);
+    let y = BigUint::from_bytes_le(&pt_buf);
+    if &y >= f.order_ref() && !allow_non_canonical {
+      return Err("Non-canonical encoding of y-coordinate".to_string());
+    }
+    let y = f.elem(&y);
+
+    let x_is_odd = x_parity == Parity::Odd;
+    let x = AffinePoint::recover_x(&d, &y, x_parity);
+
+    // recover_x returns a wrong value if x^2 has no square root
+    let xx = (y.sq() - 1u8) / ((&d * y.sq()) + 1u8);
+    if x.sq() != xx {
+      return Err("Not a point on the curve".to_string());
+    }
+    if x.is_zero() && x_is_odd && !allow_non_canonical {
+      return Err("Non-canonical encoding of x = 0".to_string());
+    }
+    Ok(AffinePoint::new(&x, &y))
+  }
 
-    AffinePoint::new(&x, &y)
+  fn is_small_order(pt: &AffinePoint) -> bool {
+    pt.mul_by_cofactor() == AffinePoint::zero()
   }
 
   fn prune_32_byte_buf(buf: &mut [u8; 32]) {
@@ -124,7 +151,34 @@ impl Ed25519Sha512 {
     pub_key
   }
 
+  // dom2(F, C) = "SigEd25519 no Ed25519 collisions" || octet(F) || octet(OLEN(C)) || C
+  fn dom2(phflag: u8, ctx: &[u8]) -> Result<Vec<u8>, String> {
+    if ctx.len() > 255 {
+      return Err("Context needs to be at most 255 bytes".to_string());
+    }
+    Ok([b"SigEd25519 no Ed25519 collisions", &[phflag, ctx.len() as u8][..], ctx].concat())
+  }
+
   pub fn sign(&self, msg: &[u8], prv_key: &[u8; 32]) -> [u8; 64] {
+    self.sign_w_dom(&[], msg, prv_key)
+  }
+
+  // Ed25519ctx
+  pub fn sign_ctx(&self, msg: &[u8], prv_key: &[u8; 32], ctx: &[u8]) -> Result<[u8; 64], String> {
+    if ctx.is_empty() {
+      return Err("Context needs to be non-empty in Ed25519ctx".to_string());
+    }
+    let dom = Self::dom2(0, ctx)?;
+    Ok(self.sign_w_dom(&dom, msg, prv_key))
+  }
+
+  // Ed25519ph. message is pre-hashed w/ SHA512
+  pub fn sign_ph(&self, msg: &[u8], prv_key: &[u8; 32], ctx: &[u8]) -> Result<[u8; 64], String> {
+    let dom = Self::dom2(1, ctx)?;
+    Ok(self.sign_w_dom(&dom, &self.H.get_digest(msg), prv_key))
+  }
+
+  fn sign_w_dom(&self, dom: &[u8], msg: &[u8], prv_key: &[u8; 32]) -> [u8; 64] {
     let f = &AffinePoint::base_field();
     let curve_group = &AffinePoint::curve_group();
     let l = curve_group.order_ref();
@@ -137,7 +191,7 @@ impl Ed25519Sha512 {
     let A_pt = B * f.elem(&s);
     let A = self.encode_point(&A_pt);
 
-    let prefix_msg = [prefix, msg].concat();
+    let prefix_msg = [dom, prefix, msg].concat();
     let r = BigUint::from_bytes_le(
       &self.H.get_digest(&prefix_msg)
     );
@@ -145,44 +199,174 @@ impl Ed25519Sha512 {
     let R_pt = B * f.elem(&r);
     let R = self.encode_point(&R_pt);
 
-    let R_A_msg = [&R, &A, msg].concat();
+    let R_A_msg = [dom, &R, &A, msg].concat();
     let k = BigUint::from_bytes_le(
       &self.H.get_digest(&R_A_msg)
     );
     let k = k.rem(l);
 
     let S = (r + k * s).rem(l);
-    let S32 = Self::write_biguint_to_32_byte_buf_as_le_integer(&S);
+    let S32 = write_biguint_to_32_byte_buf_as_le_integer(&S);
 
     [R, S32].concat().try_into().unwrap()
   }
 
   pub fn verify(&self, sig: &[u8;64], pub_key: &[u8; 32], msg: &[u8]) -> bool {
-    let f_l = &AffinePoint::curve_group();
-    let B = &AffinePoint::B();
+    self.verify_with_mode(sig, pub_key, msg, VerificationMode::Cofactored).is_ok()
+  }
 
-    let S = BigUint::from_bytes_le(&sig[32..64]);
-    if &S >= f_l.order_ref() {
+  pub fn verify_ctx(&self, sig: &[u8; 64], pub_key: &[u8; 32], msg: &[u8], ctx: &[u8]) -> bool {
+    if ctx.is_empty() {
       return false;
     }
-    let R_pt = self.decode_point(&sig[0..32].try_into().unwrap());
-    let R = self.encode_point(&R_pt);
-    let R_pub_key_msg = [&R, pub_key, msg].concat();
+    match Self::dom2(0, ctx) {
+      Ok(dom) => self.verify_w_dom(sig, pub_key, msg, &dom, VerificationMode::Cofactored).is_ok(),
+      Err(_) => false,
+    }
+  }
+
+  pub fn verify_ph(&self, sig: &[u8; 64], pub_key: &[u8; 32], msg: &[u8], ctx: &[u8]) -> bool {
+    match Self::dom2(1, ctx) {
+      Ok(dom) => {
+        let ph_msg = self.H.get_digest(msg);
+        self.verify_w_dom(sig, pub_key, &ph_msg, &dom, VerificationMode::Cofactored).is_ok()
+      },
+      Err(_) => false,
+    }
+  }
+
+  // decodes (R, S, A) and computes k = H(dom || R || A || M) mod l under the rules of the mode
+  fn parse_sig(
+    &self,
+    sig: &[u8; 64],
+    pub_key: &[u8; 32],
+    msg: &[u8],
+    dom: &[u8],
+    mode: VerificationMode,
+  ) -> Result<(AffinePoint, BigUint, AffinePoint, BigUint), String> {
+    let l = AffinePoint::curve_group().order_ref().clone();
+
+    // S needs to be canonical in all modes
+    let S = BigUint::from_bytes_le(&sig[32..64]);
+    if S >= l {
+      return Err("Non-canonical S".to_string());
+    }
+
+    let allow_non_canonical = mode == VerificationMode::Zip215;
+    let R_buf: [u8; 32] = sig[0..32].try_into().unwrap();
+    let R = self.decode_point(&R_buf, allow_non_canonical)?;
+    let A = self.decode_point(pub_key, allow_non_canonical)?;
+
+    if mode == VerificationMode::Strict {
+      if Self::is_small_order(&A) {
+        return Err("Public key is of small order".to_string());
+      }
+      if Self::is_small_order(&R) {
+        return Err("R is of small order".to_string());
+      }
+    }
+
+    // encodings are hashed as given
     let k = BigUint::from_bytes_le(
-      &self.H.get_digest(&R_pub_key_msg)
-    );
-    let A_pt = self.decode_point(&pub_key);
+      &self.H.get_digest(&[dom, &R_buf, pub_key, msg].concat())
+    ).rem(&l);
+
+    Ok((R, S, A, k))
+  }
+
+  pub fn verify_with_mode(
+    &self,
+    sig: &[u8; 64],
+    pub_key: &[u8; 32],
+    msg: &[u8],
+    mode: VerificationMode,
+  ) -> Result<(), String> {
+    self.verify_w_dom(sig, pub_key, msg, &[], mode)
+  }
+
+  fn verify_w_dom(
+    &self,
+    sig: &[u8; 64],
+    pub_key: &[u8; 32],
+    msg: &[u8],
+    dom: &[u8],
+    mode: VerificationMode,
+  ) -> Result<(), String> {
+    let f_l = &AffinePoint::curve_group();
+    let B = &AffinePoint::B();
+
+    let (R, S, A, k) = self.parse_sig(sig, pub_key, msg, dom, mode)?;
 
-    let lhs_factor = f_l.elem(&(S * 8u8));
-    let lhs = B * lhs_factor;
+    // [S]B - [k]A - R
+    let diff = B * f_l.elem(&S) + -(&A * f_l.elem(&k)) + -R;
 
-    let rhs_term1 = &R_pt * f_l.elem(&8u8);
-    let rhs_term2_factor = f_l.elem(&(k * 8u8));
-    let rhs_term2 = &A_pt * &rhs_term2_factor;
+    let is_valid = match mode {
+      VerificationMode::Cofactored | VerificationMode::Zip215 => {
+        diff.mul_by_cofactor() == AffinePoint::zero()
+      },
+      VerificationMode::Cofactorless | VerificationMode::Strict => {
+        diff == AffinePoint::zero()
+      },
+    };
+    if is_valid { Ok(()) } else { Err("Signature verification failed".to_string()) }
+  }
+
+  // checks [8](-(sum z_i S_i)B + sum z_i R_i + sum (z_i k_i) A_i) = 0 for random 128-bit z_i
+  // w/ a single multi-scalar multiplication. only the cofactored equation is consistent w/
+  // batching, so Cofactorless and Strict modes are not supported
+  pub fn verify_batch(
+    &self,
+    sigs: &[[u8; 64]],
+    pub_keys: &[[u8; 32]],
+    msgs: &[&[u8]],
+    mode: VerificationMode,
+  ) -> Result<(), String> {
+    self.verify_batch_with_rng(sigs, pub_keys, msgs, mode, &mut RandomNumber::new().gen)
+  }
+
+  // z_i are drawn from rng
+  pub fn verify_batch_with_rng(
+    &self,
+    sigs: &[[u8; 64]],
+    pub_keys: &[[u8; 32]],
+    msgs: &[&[u8]],
+    mode: VerificationMode,
+    rng: &mut (impl RngCore + CryptoRng),
+  ) -> Result<(), String> {
+    if mode == VerificationMode::Cofactorless || mode == VerificationMode::Strict {
+      return Err(format!("Batch verification is not supported in {:?} mode", mode));
+    }
+    if sigs.len() != pub_keys.len() || sigs.len() != msgs.len() {
+      return Err("Numbers of signatures, public keys and messages differ".to_string());
+    }
+    let l = AffinePoint::curve_group().order_ref().clone();
+
+    let mut B_coeff = BigUint::zero();
+    let mut points = vec![AffinePoint::B()];
+    let mut scalars = vec![];
+
+    for i in 0..sigs.len() {
+      let (R, S, A, k) = self.parse_sig(&sigs[i], &pub_keys[i], msgs[i], &[], mode)?;
 
-    let rhs = &rhs_term1 + &rhs_term2;
+      let mut z_buf = [0u8; 16];
+      rng.fill_bytes(&mut z_buf);
+      let z = BigUint::from_bytes_le(&z_buf);
 
-    lhs == rhs
+      B_coeff = (B_coeff + &z * S).rem(&l);
+      points.push(R);
+      scalars.push(z.clone());
+      points.push(A);
+      scalars.push((z * k).rem(&l));
+    }
+    // -(sum z_i S_i) mod l
+    scalars.insert(0, (&l - B_coeff).rem(&l));
+
+    let sum = AffinePoint::multi_scalar_mul(&points, &scalars);
+    if sum.mul_by_cofactor() == AffinePoint::zero() {
+      Ok(())
+    } else {
+      Err("Batch verification failed".to_string())
+    }
   }
 }
 
@@ -250,4 +434,283 @@ mod tests {
     let exp_sig = "dc2a4459e7369633a52b1bf277839a00201009a3efbf3ecb69bea2186c26b58909351fc9ac90b3ecfdfbc7c66431e0303dca179c138ac17ad9bef1177331a704";
     run_rfc8032_test(prv_key, exp_pub_key, &hex::decode(msg).unwrap(), exp_sig);
   }
+
+  fn decode32(s: &str) -> [u8; 32] {
+    hex::decode(s).unwrap().try_into().unwrap()
+  }
+
+  fn rfc8032_test1_sig() -> ([u8; 64], [u8; 32]) {
+    let sig = hex::decode("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b").unwrap();
+    let pub_key = decode32("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    (sig.try_into().unwrap(), pub_key)
+  }
+
+  const ALL_MODES: [VerificationMode; 4] = [
+    VerificationMode::Cofactored,
+    VerificationMode::Cofactorless,
+    VerificationMode::Zip215,
+    VerificationMode::Strict,
+  ];
+
+  // signature by public key A = [a]B + T where T is a point of order 8
+  fn sign_w_mixed_order_key(msg: &[u8]) -> ([u8; 64], [u8; 32]) {
+    let curve = Ed25519Sha512::new();
+    let f_l = AffinePoint::curve_group();
+    let l = f_l.order_ref();
+    let B = &AffinePoint::B();
+
+    let T = curve.decode_point(&decode32("c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a"), false).unwrap();
+    let a = BigUint::from(123456789u32);
+    let r = BigUint::from(987654321u32);
+
+    let A = curve.encode_point(&(B * f_l.elem(&a) + &T));
+    let R = curve.encode_point(&(B * f_l.elem(&r)));
+    let k = BigUint::from_bytes_le(&curve.H.get_digest(&[&R, &A, msg].concat())).rem(l);
+    let S = (r + k * a).rem(l);
+    let S32 = write_biguint_to_32_byte_buf_as_le_integer(&S);
+
+    ([R, S32].concat().try_into().unwrap(), A)
+  }
+
+  #[test]
+  fn verify_valid_sig_in_all_modes() {
+    let curve = Ed25519Sha512::new();
+    let (sig, pub_key) = rfc8032_test1_sig();
+    for mode in ALL_MODES {
+      assert!(curve.verify_with_mode(&sig, &pub_key, &[], mode).is_ok());
+      assert!(curve.verify_with_mode(&sig, &pub_key, &[1], mode).is_err());
+    }
+  }
+
+  #[test]
+  fn verify_invalid_point_returns_error() {
+    let curve = Ed25519Sha512::new();
+    let (sig, pub_key) = rfc8032_test1_sig();
+
+    // y = 2 is not on the curve
+    let mut bad_pt = [0u8; 32];
+    bad_pt[0] = 2;
+    let mut bad_sig = sig.clone();
+    bad_sig[0..32].copy_from_slice(&bad_pt);
+
+    for mode in ALL_MODES {
+      assert!(curve.verify_with_mode(&sig, &bad_pt, &[], mode).is_err());
+      assert!(curve.verify_with_mode(&bad_sig, &pub_key, &[], mode).is_err());
+    }
+    assert!(!curve.verify(&sig, &bad_pt, &[]));
+  }
+
+  #[test]
+  fn reject_non_canonical_s() {
+    let curve = Ed25519Sha512::new();
+    let (sig, pub_key) = rfc8032_test1_sig();
+
+    // S + l is congruent to S, but non-canonical
+    let l = AffinePoint::curve_group().order_ref().clone();
+    let S = BigUint::from_bytes_le(&sig[32..64]) + l;
+    let mut bad_sig = sig.clone();
+    bad_sig[32..64].copy_from_slice(&write_biguint_to_32_byte_buf_as_le_integer(&S));
+
+    for mode in ALL_MODES {
+      assert!(curve.verify_with_mode(&bad_sig, &pub_key, &[], mode).is_err());
+    }
+  }
+
+  #[test]
+  fn small_order_pub_key() {
+    let curve = Ed25519Sha512::new();
+
+    // A = R = identity and S = 0 satisfies the equation for any message
+    let identity = decode32("0100000000000000000000000000000000000000000000000000000000000000");
+    let sig: [u8; 64] = [identity, [0u8; 32]].concat().try_into().unwrap();
+    let msg = b"any message";
+
+    assert!(curve.verify_with_mode(&sig, &identity, msg, VerificationMode::Cofactored).is_ok());
+    assert!(curve.verify_with_mode(&sig, &identity, msg, VerificationMode::Cofactorless).is_ok());
+    assert!(curve.verify_with_mode(&sig, &identity, msg, VerificationMode::Zip215).is_ok());
+    assert!(curve.verify_with_mode(&sig, &identity, msg, VerificationMode::Strict).is_err());
+  }
+
+  #[test]
+  fn non_canonical_encoding() {
+    let curve = Ed25519Sha512::new();
+
+    // identity w/ y = q + 1
+    let non_canonical_identity = decode32("eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f");
+    let identity = decode32("0100000000000000000000000000000000000000000000000000000000000000");
+    let sig: [u8; 64] = [identity, [0u8; 32]].concat().try_into().unwrap();
+    let msg = b"any message";
+
+    assert!(curve.verify_with_mode(&sig, &non_canonical_identity, msg, VerificationMode::Zip215).is_ok());
+    assert!(curve.verify_with_mode(&sig, &non_canonical_identity, msg, VerificationMode::Cofactored).is_err());
+    assert!(curve.verify_with_mode(&sig, &non_canonical_identity, msg, VerificationMode::Cofactorless).is_err());
+
+    // x = 0 w/ parity bit set
+    let negative_zero_identity = decode32("0100000000000000000000000000000000000000000000000000000000000080");
+    assert!(curve.verify_with_mode(&sig, &negative_zero_identity, msg, VerificationMode::Zip215).is_ok());
+    assert!(curve.verify_with_mode(&sig, &negative_zero_identity, msg, VerificationMode::Cofactored).is_err());
+  }
+
+  #[test]
+  fn mixed_order_pub_key() {
+    let curve = Ed25519Sha512::new();
+    // message is chosen so that k is not a multiple of 8 and [k]T doesn't vanish
+    let msg = b"mixed order key";
+    let (sig, pub_key) = sign_w_mixed_order_key(msg);
+
+    assert!(curve.verify_with_mode(&sig, &pub_key, msg, VerificationMode::Cofactored).is_ok());
+    assert!(curve.verify_with_mode(&sig, &pub_key, msg, VerificationMode::Zip215).is_ok());
+    assert!(curve.verify_with_mode(&sig, &pub_key, msg, VerificationMode::Cofactorless).is_err());
+    assert!(curve.verify_with_mode(&sig, &pub_key, msg, VerificationMode::Strict).is_err());
+  }
+
+  #[test]
+  fn batch_verification() {
+    let curve = Ed25519Sha512::new();
+    let prv_keys = [
+      "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+      "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+      "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+    ];
+    let msgs: [&[u8]; 3] = [b"", b"r", b"batch"];
+
+    let mut sigs = vec![];
+    let mut pub_keys = vec![];
+    for (prv_key, msg) in prv_keys.iter().zip(msgs.iter()) {
+      let prv_key = decode32(prv_key);
+      sigs.push(curve.sign(msg, &prv_key));
+      pub_keys.push(curve.gen_pub_key(&prv_key));
+    }
+    assert!(curve.verify_batch(&sigs, &pub_keys, &msgs, VerificationMode::Cofactored).is_ok());
+    assert!(curve.verify_batch(&sigs, &pub_keys, &msgs, VerificationMode::Zip215).is_ok());
+    assert!(curve.verify_batch(&[], &[], &[], VerificationMode::Cofactored).is_ok());
+
+    // batching w/ cofactorless equation is not supported
+    assert!(curve.verify_batch(&sigs, &pub_keys, &msgs, VerificationMode::Cofactorless).is_err());
+    assert!(curve.verify_batch(&sigs, &pub_keys, &msgs, VerificationMode::Strict).is_err());
+
+    // one of the messages is different
+    let bad_msgs: [&[u8]; 3] = [b"", b"x", b"batch"];
+    assert!(curve.verify_batch(&sigs, &pub_keys, &bad_msgs, VerificationMode::Cofactored).is_err());
+
+    // length mismatch
+    assert!(curve.verify_batch(&sigs, &pub_keys[0..2], &msgs, VerificationMode::Cofactored).is_err());
+  }
+
+  #[test]
+  fn batch_verification_w_mixed_order_pub_key() {
+    let curve = Ed25519Sha512::new();
+    let msg: &[u8] = b"mixed order key";
+    let (sig1, pub_key1) = sign_w_mixed_order_key(msg);
+    let (sig2, pub_key2) = rfc8032_test1_sig();
+
+    let sigs = [sig1, sig2];
+    let pub_keys = [pub_key1, pub_key2];
+    let msgs: [&[u8]; 2] = [msg, b""];
+
+    // batch verification agrees w/ cofactored single verification
+    assert!(curve.verify_batch(&sigs, &pub_keys, &msgs, VerificationMode::Cofactored).is_ok());
+  }
+
+  fn run_rfc8032_ctx_test(prv_key: &str, exp_pub_key: &str, msg: &str, ctx: &str, exp_sig: &str) {
+    let curve = Ed25519Sha512::new();
+    let prv_key = decode32(prv_key);
+    let pub_key = curve.gen_pub_key(&prv_key);
+    assert_eq!(pub_key, decode32(exp_pub_key));
+
+    let msg = hex::decode(msg).unwrap();
+    let ctx = hex::decode(ctx).unwrap();
+    let sig = curve.sign_ctx(&msg, &prv_key, &ctx).unwrap();
+    assert_eq!(sig.to_vec(), hex::decode(exp_sig).unwrap());
+    assert!(curve.verify_ctx(&sig, &pub_key, &msg, &ctx));
+
+    // signature is bound to the context and the variant
+    assert!(!curve.verify_ctx(&sig, &pub_key, &msg, b"baz"));
+    assert!(!curve.verify(&sig, &pub_key, &msg));
+    assert!(!curve.verify_ph(&sig, &pub_key, &msg, &ctx));
+  }
+
+  #[test]
+  fn rfc8032_ctx_foo() {
+    run_rfc8032_ctx_test(
+      "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+      "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+      "f726936d19c800494e3fdaff20b276a8",
+      "666f6f",
+      "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d",
+    );
+  }
+
+  #[test]
+  fn rfc8032_ctx_bar() {
+    run_rfc8032_ctx_test(
+      "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+      "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+      "f726936d19c800494e3fdaff20b276a8",
+      "626172",
+      "fc60d5872fc46b3aa69f8b5b4351d5808f92bcc044606db097abab6dbcb1aee3216c48e8b3b66431b5b186d1d28f8ee15a5ca2df6668346291c2043d4eb3e90d",
+    );
+  }
+
+  #[test]
+  fn rfc8032_ctx_foo2() {
+    run_rfc8032_ctx_test(
+      "0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6",
+      "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292",
+      "508e9e6882b979fea900f62adceaca35",
+      "666f6f",
+      "8b70c1cc8310e1de20ac53ce28ae6e7207f33c3295e03bb5c0732a1d20dc64908922a8b052cf99b7c4fe107a5abb5b2c4085ae75890d02df26269d8945f84b0b",
+    );
+  }
+
+  #[test]
+  fn rfc8032_ctx_foo3() {
+    run_rfc8032_ctx_test(
+      "ab9c2853ce297ddab85c993b3ae14bcad39b2c682beabc27d6d4eb20711d6560",
+      "0f1d1274943b91415889152e893d80e93275a1fc0b65fd71b4b0dda10ad7d772",
+      "f726936d19c800494e3fdaff20b276a8",
+      "666f6f",
+      "21655b5f1aa965996b3f97b3c849eafba922a0a62992f73b3d1b73106a84ad85e9b86a7b6005ea868337ff2d20a7f5fbd4cd10b0be49a68da2b2e0dc0ad8960f",
+    );
+  }
+
+  #[test]
+  fn rfc8032_ph_abc() {
+    let curve = Ed25519Sha512::new();
+    let prv_key = decode32("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42");
+    let pub_key = curve.gen_pub_key(&prv_key);
+    assert_eq!(pub_key, decode32("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf"));
+
+    let msg = b"abc";
+    let sig = curve.sign_ph(msg, &prv_key, &[]).unwrap();
+    let exp_sig = "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406";
+    assert_eq!(sig.to_vec(), hex::decode(exp_sig).unwrap());
+    assert!(curve.verify_ph(&sig, &pub_key, msg, &[]));
+
+    assert!(!curve.verify_ph(&sig, &pub_key, b"abd", &[]));
+    assert!(!curve.verify_ph(&sig, &pub_key, msg, b"foo"));
+    assert!(!curve.verify(&sig, &pub_key, msg));
+  }
+
+  #[test]
+  fn context_length() {
+    let curve = Ed25519Sha512::new();
+    let prv_key = decode32("0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6");
+    let pub_key = curve.gen_pub_key(&prv_key);
+    let msg = b"msg";
+
+    // empty context is not allowed in Ed25519ctx
+    assert!(curve.sign_ctx(msg, &prv_key, &[]).is_err());
+
+    let ctx = [0xabu8; 255];
+    let sig = curve.sign_ctx(msg, &prv_key, &ctx).unwrap();
+    assert!(curve.verify_ctx(&sig, &pub_key, msg, &ctx));
+    let sig = curve.sign_ph(msg, &prv_key, &ctx).unwrap();
+    assert!(curve.verify_ph(&sig, &pub_key, msg, &ctx));
+
+    let ctx = [0xabu8; 256];
+    assert!(curve.sign_ctx(msg, &prv_key, &ctx).is_err());
+    assert!(curve.sign_ph(msg, &prv_key, &ctx).is_err());
+    assert!(!curve.verify_ctx(&sig, &pub_key, msg, &ctx));
+  }
 }
@@ -1,2 +1,4 @@
 pub mod affine_point;
-pub mod ed25519_sha512;
\ No newline at end of file
+pub mod ed25519_sha512;
+pub mod x25519;
+pub mod ristretto255;
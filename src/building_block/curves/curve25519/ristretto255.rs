@@ -0,0 +1,422 @@
+#![allow(non_snake_case)]
+use crate::building_block::{
+  curves::curve25519::affine_point::{
+    AffinePoint,
+    write_biguint_to_32_byte_buf_as_le_integer,
+  },
+  field::prime_field_elem::PrimeFieldElem,
+  zero::Zero,
+};
+use std::ops::{Add, Sub, Mul, Neg};
+use once_cell::sync::Lazy;
+use num_bigint::BigUint;
+
+// implementation based on: https://datatracker.ietf.org/doc/html/rfc9496
+// points are kept in affine coordinates, so Z = 1 and T = xy in the formulas
+
+// sqrt(-1) = 2^((p-1)/4)
+static SQRT_M1: Lazy<PrimeFieldElem> = Lazy::new(|| {
+  let f = AffinePoint::base_field();
+  f.elem(&2u8).pow(&((f.order_ref() - 1u8) / 4u8))
+});
+
+// sqrt(a*d - 1) w/ a = -1. the negative root is used as in the spec
+static SQRT_AD_MINUS_ONE: Lazy<PrimeFieldElem> = Lazy::new(|| {
+  let f = AffinePoint::base_field();
+  let (_, r) = sqrt_ratio_m1(&(-AffinePoint::d() - 1u8), &f.elem(&1u8));
+  -r
+});
+
+// 1 / sqrt(a - d)
+static INVSQRT_A_MINUS_D: Lazy<PrimeFieldElem> = Lazy::new(|| {
+  let f = AffinePoint::base_field();
+  let (_, r) = sqrt_ratio_m1(&f.elem(&1u8), &(-AffinePoint::d() - 1u8));
+  r
+});
+
+// 1 - d^2
+static ONE_MINUS_D_SQ: Lazy<PrimeFieldElem> = Lazy::new(|| {
+  AffinePoint::base_field().elem(&1u8) - AffinePoint::d().sq()
+});
+
+// (d - 1)^2
+static D_MINUS_ONE_SQ: Lazy<PrimeFieldElem> = Lazy::new(|| {
+  (AffinePoint::d() - 1u8).sq()
+});
+
+fn is_negative(e: &PrimeFieldElem) -> bool {
+  e.e.bit(0)
+}
+
+fn ct_abs(e: &PrimeFieldElem) -> PrimeFieldElem {
+  if is_negative(e) { -e } else { e.clone() }
+}
+
+// returns (true, +sqrt(u/v)) if u/v is square, (true, 0) if u is zero,
+// (false, 0) if v is zero and (false, +sqrt(i*u/v)) otherwise
+fn sqrt_ratio_m1(u: &PrimeFieldElem, v: &PrimeFieldElem) -> (bool, PrimeFieldElem) {
+  let p = u.f.order_ref();
+  let v3 = v.sq() * v;
+  let v7 = v3.sq() * v;
+  let mut r = (u * &v3) * (u * &v7).pow(&((p - 5u8) / 8u8));
+  let check = v * r.sq();
+
+  let neg_u = -u;
+  let correct_sign_sqrt = &check == u;
+  let flipped_sign_sqrt = check == neg_u;
+  let flipped_sign_sqrt_i = check == &neg_u * &*SQRT_M1;
+
+  if flipped_sign_sqrt || flipped_sign_sqrt_i {
+    r = &r * &*SQRT_M1;
+  }
+  (correct_sign_sqrt || flipped_sign_sqrt, ct_abs(&r))
+}
+
+// element of the prime-order group of curve25519 points modulo the 8-torsion.
+// the wrapped Edwards point is one of the 8 representatives of the element. it is kept
+// private so that points can only come from the constants, decode, from_uniform_bytes and
+// the group operations
+#[derive(Debug, Clone)]
+pub struct RistrettoPoint(AffinePoint);
+
+impl RistrettoPoint {
+  pub fn identity() -> Self {
+    RistrettoPoint(AffinePoint::zero())
+  }
+
+  pub fn generator() -> Self {
+    RistrettoPoint(AffinePoint::B())
+  }
+
+  fn coords(&self) -> (PrimeFieldElem, PrimeFieldElem) {
+    match &self.0 {
+      AffinePoint::Rational { x, y } => (x.clone(), y.clone()),
+      AffinePoint::AtInfinity => {
+        let f = AffinePoint::base_field();
+        (f.elem(&0u8), f.elem(&1u8))
+      },
+    }
+  }
+
+  pub fn encode(&self) -> [u8; 32] {
+    let f = AffinePoint::base_field();
+    let one = f.elem(&1u8);
+    let (x0, y0) = self.coords();
+    let t0 = &x0 * &y0;
+
+    let u1 = (&one + &y0) * (&one - &y0);
+    let u2 = &x0 * &y0;
+    let (_, invsqrt) = sqrt_ratio_m1(&one, &(&u1 * u2.sq()));
+    let den1 = &invsqrt * &u1;
+    let den2 = &invsqrt * &u2;
+    let z_inv = &den1 * &den2 * &t0;
+
+    let ix0 = &x0 * &*SQRT_M1;
+    let iy0 = &y0 * &*SQRT_M1;
+    let enchanted_denominator = &den1 * &*INVSQRT_A_MINUS_D;
+
+    let rotate = is_negative(&(&t0 * &z_inv));
+    let (x, mut y, den_inv) = if rotate {
+      (iy0, ix0, enchanted_denominator)
+    } else {
+      (x0, y0, den2)
+    };
+    if is_negative(&(&x * &z_inv)) {
+      y = -y;
+    }
+    let s = ct_abs(&(den_inv * (&one - &y)));
+    write_biguint_to_32_byte_buf_as_le_integer(&s.e)
+  }
+
+  pub fn decode(buf: &[u8; 32]) -> Result<Self, String> {
+    let f = AffinePoint::base_field();
+    let s = BigUint::from_bytes_le(buf);
+    if &s >= f.order_ref() {
+      return Err("Non-canonical field element".to_string());
+    }
+    let s = f.elem(&s);
+    if is_negative(&s) {
+      return Err("Negative field element".to_string());
+    }
+
+    let one = f.elem(&1u8);
+    let ss = s.sq();
+    let u1 = &one - &ss;
+    let u2 = &one + &ss;
+    let u2_sqr = u2.sq();
+
+    // v = a*d*u1^2 - u2^2 w/ a = -1
+    let v = -(&AffinePoint::d() * u1.sq()) - &u2_sqr;
+    let (was_square, invsqrt) = sqrt_ratio_m1(&one, &(&v * &u2_sqr));
+
+    let den_x = &invsqrt * &u2;
+    let den_y = &invsqrt * &den_x * &v;
+
+    let x = ct_abs(&((&s + &s) * &den_x));
+    let y = &u1 * &den_y;
+    let t = &x * &y;
+
+    if !was_square || is_negative(&t) || y.is_zero() {
+      return Err("Invalid encoding".to_string());
+    }
+    Ok(RistrettoPoint(AffinePoint::new(&x, &y)))
+  }
+
+  // Elligator map from a field element to a curve point
+  fn map(t: &PrimeFieldElem) -> AffinePoint {
+    let f = AffinePoint::base_field();
+    let one = f.elem(&1u8);
+    let d = AffinePoint::d();
+
+    let r = &*SQRT_M1 * t.sq();
+    let u = (&r + &one) * &*ONE_MINUS_D_SQ;
+    let v = (-&one - &r * &d) * (&r + &d);
+
+    let (was_square, s) = sqrt_ratio_m1(&u, &v);
+    let s_prime = -ct_abs(&(&s * t));
+    let (s, c) = if was_square { (s, -&one) } else { (s_prime, r.clone()) };
+
+    let N = c * (&r - &one) * &*D_MINUS_ONE_SQ - &v;
+
+    let w0 = (&s + &s) * &v;
+    let w1 = N * &*SQRT_AD_MINUS_ONE;
+    let w2 = &one - s.sq();
+    let w3 = &one + s.sq();
+
+    // (x, y) = (w0 / w1, w2 / w3)
+    let x = &w0 / &w1;
+    let y = &w2 / &w3;
+    AffinePoint::new(&x, &y)
+  }
+
+  // hash-to-group from 64 uniformly random bytes
+  pub fn from_uniform_bytes(buf: &[u8; 64]) -> Self {
+    let f = AffinePoint::base_field();
+    let to_field_elem = |half: &[u8]| {
+      let mut half: [u8; 32] = half.try_into().unwrap();
+      half[31] &= 0b0111_1111;
+      f.elem(&BigUint::from_bytes_le(&half))
+    };
+    let P1 = Self::map(&to_field_elem(&buf[0..32]));
+    let P2 = Self::map(&to_field_elem(&buf[32..64]));
+    RistrettoPoint(P1 + P2)
+  }
+}
+
+impl PartialEq for RistrettoPoint {
+  // (x1, y1) and (x2, y2) represent the same element iff x1*y2 == y1*x2 or y1*y2 == x1*x2
+  fn eq(&self, rhs: &Self) -> bool {
+    let (x1, y1) = self.coords();
+    let (x2, y2) = rhs.coords();
+    &x1 * &y2 == &y1 * &x2 || &y1 * &y2 == &x1 * &x2
+  }
+}
+
+impl Eq for RistrettoPoint {}
+
+macro_rules! impl_add {
+  ($rhs: ty, $target: ty) => {
+    impl Add<$rhs> for $target {
+      type Output = RistrettoPoint;
+
+      fn add(self, rhs: $rhs) -> Self::Output {
+        RistrettoPoint(&self.0 + &rhs.0)
+      }
+    }
+  }
+}
+impl_add!(RistrettoPoint, RistrettoPoint);
+impl_add!(RistrettoPoint, &RistrettoPoint);
+impl_add!(&RistrettoPoint, RistrettoPoint);
+impl_add!(&RistrettoPoint, &RistrettoPoint);
+
+macro_rules! impl_sub {
+  ($rhs: ty, $target: ty) => {
+    impl Sub<$rhs> for $target {
+      type Output = RistrettoPoint;
+
+      fn sub(self, rhs: $rhs) -> Self::Output {
+        RistrettoPoint(&self.0 + -&rhs.0)
+      }
+    }
+  }
+}
+impl_sub!(RistrettoPoint, RistrettoPoint);
+impl_sub!(RistrettoPoint, &RistrettoPoint);
+impl_sub!(&RistrettoPoint, RistrettoPoint);
+impl_sub!(&RistrettoPoint, &RistrettoPoint);
+
+macro_rules! impl_neg {
+  ($target: ty) => {
+    impl Neg for $target {
+      type Output = RistrettoPoint;
+
+      fn neg(self) -> Self::Output {
+        RistrettoPoint(-&self.0)
+      }
+    }
+  }
+}
+impl_neg!(RistrettoPoint);
+impl_neg!(&RistrettoPoint);
+
+macro_rules! impl_mul {
+  ($rhs: ty, $target: ty) => {
+    impl Mul<$rhs> for $target {
+      type Output = RistrettoPoint;
+
+      fn mul(self, rhs: $rhs) -> Self::Output {
+        RistrettoPoint(&self.0 * rhs)
+      }
+    }
+  }
+}
+impl_mul!(PrimeFieldElem, RistrettoPoint);
+impl_mul!(PrimeFieldElem, &RistrettoPoint);
+impl_mul!(&PrimeFieldElem, RistrettoPoint);
+impl_mul!(&PrimeFieldElem, &RistrettoPoint);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn decode(s: &str) -> [u8; 32] {
+    hex::decode(s).unwrap().try_into().unwrap()
+  }
+
+  #[test]
+  fn constants() {
+    let f = AffinePoint::base_field();
+    let sqrt_ad_minus_one = BigUint::parse_bytes(b"25063068953384623474111414158702152701244531502492656460079210482610430750235", 10).unwrap();
+    let invsqrt_a_minus_d = BigUint::parse_bytes(b"54469307008909316920995813868745141605393597292927456921205312896311721017578", 10).unwrap();
+    assert_eq!(*SQRT_AD_MINUS_ONE, f.elem(&sqrt_ad_minus_one));
+    assert_eq!(*INVSQRT_A_MINUS_D, f.elem(&invsqrt_a_minus_d));
+  }
+
+  #[test]
+  fn encode_small_multiples_of_generator() {
+    let encodings = [
+      "0000000000000000000000000000000000000000000000000000000000000000",
+      "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+      "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+      "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+      "da80862773358b466ffadfe0b3293ab3d9fd53c5ea6c955358f568322daf6a57",
+      "e882b131016b52c1d3337080187cf768423efccbb517bb495ab812c4160ff44e",
+      "f64746d3c92b13050ed8d80236a7f0007c3b3f962f5ba793d19a601ebb1df403",
+      "44f53520926ec81fbd5a387845beb7df85a96a24ece18738bdcfa6a7822a176d",
+      "903293d8f2287ebe10e2374dc1a53e0bc887e592699f02d077d5263cdd55601c",
+      "02622ace8f7303a31cafc63f8fc48fdc16e1c8c8d234b2f0d6685282a9076031",
+      "20706fd788b2720a1ed2a5dad4952b01f413bcf0e7564de8cdc816689e2db95f",
+      "bce83f8ba5dd2fa572864c24ba1810f9522bc6004afe95877ac73241cafdab42",
+      "e4549ee16b9aa03099ca208c67adafcafa4c3f3e4e5303de6026e3ca8ff84460",
+      "aa52e000df2e16f55fb1032fc33bc42742dad6bd5a8fc0be0167436c5948501f",
+      "46376b80f409b29dc2b5f6f0c52591990896e5716f41477cd30085ab7f10301e",
+      "e0c418f7c8d9c4cdd7395b93ea124f3ad99021bb681dfc3302a9d99a2e53e64e",
+    ];
+    let G = RistrettoPoint::generator();
+    let mut P = RistrettoPoint::identity();
+    for exp in encodings {
+      assert_eq!(P.encode(), decode(exp));
+
+      let Q = RistrettoPoint::decode(&decode(exp)).unwrap();
+      assert_eq!(Q, P);
+      assert_eq!(Q.encode(), decode(exp));
+      P = &P + &G;
+    }
+  }
+
+  #[test]
+  fn decode_bad_encodings() {
+    let bad_encodings = [
+      // non-canonical field encodings
+      "00ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+      "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+      "f3ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+      "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+      // negative field elements
+      "0100000000000000000000000000000000000000000000000000000000000000",
+      "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+      "ed57ffd8c914fb201471d1c3d245ce3c746fcbe63a3679d51b6a516ebebe0e20",
+      // non-square x^2
+      "26948d35ca62e643e26a83177332e6b6afeb9d08e4268b650f1f5bbd8d81d371",
+      "4eac077a713c57b4f4397629a4145982c661f48044dd3f96427d40b147d9742f",
+      // negative xy value
+      "3eb858e78f5a7254d8c9731174a94f76755fd3941c0ac93735c07ba14579630e",
+      // s = -1, which causes y = 0
+      "ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+    ];
+    for s in bad_encodings {
+      assert!(RistrettoPoint::decode(&decode(s)).is_err(), "{} should be rejected", s);
+    }
+  }
+
+  #[test]
+  fn from_uniform_bytes() {
+    let input: [u8; 64] = hex::decode("5d1be09e3d0c82fc538112490e35701979d99e06ca3e2b5b54bffe8b4dc772c14d98b696a1bbfb5ca32c436cc61c16563790306c79eaca7705668b47dffe5bb6").unwrap().try_into().unwrap();
+    let P = RistrettoPoint::from_uniform_bytes(&input);
+    assert_eq!(P.encode(), decode("3066f82a1a747d45120d1740f14358531a8f04bbffe6a819f86dfe50f44a0a46"));
+  }
+
+  #[test]
+  fn elligator_map() {
+    // from ristretto.sage
+    let test_cases = [
+      (
+        "b8f98731fd7b597143a006ef0769d329c0f9b939096646c60f7f071aa0668647",
+        "b09ded61421d8ca6a85e1a9dd4d8e5a0c3f6e8efa9703fc1402098450bbef656",
+      ),
+      (
+        "e50ef1e34b09763c8099e215b7d95b886200e79c7c4d528b8e86a4a9a93efa34",
+        "ea8d4dcbb5e1fa4aab3e0f764ed49613830ebceec2f48d8aa6a2537ae4c9131a",
+      ),
+      (
+        "736d24dcb4df6306cca9131da9445417156dbd957fcd5b66ac2370238645ba22",
+        "e8e7335c05a85024adb36844ba9544288caa1b67638c15f22b3efa86d0ff3d59",
+      ),
+      (
+        "1031606babc7a4098110403ef13f84add1a070d769329d51fd69019ae5197853",
+        "d0788c81b1b3ed9ffca01c0dce05d3f1c0da016182f114a9772ef61d4f504d54",
+      ),
+    ];
+    for (t, exp) in test_cases {
+      let t = AffinePoint::base_field().elem(&BigUint::from_bytes_le(&decode(t)));
+      let P = RistrettoPoint(RistrettoPoint::map(&t));
+      assert_eq!(P.encode(), decode(exp));
+    }
+  }
+
+  #[test]
+  fn equality_ignores_torsion() {
+    let f = AffinePoint::curve_group();
+    let P = RistrettoPoint::generator() * f.elem(&7u8);
+
+    // adding a 4-torsion point gives a different Edwards point representing the same element
+    let T4 = AffinePoint::new(&SQRT_M1, &AffinePoint::base_field().elem(&0u8));
+    let Q = RistrettoPoint(&P.0 + &T4);
+    assert_ne!(P.0, Q.0);
+    assert_eq!(P, Q);
+    assert_eq!(P.encode(), Q.encode());
+    assert_ne!(P, RistrettoPoint::generator());
+  }
+
+  #[test]
+  fn group_operations() {
+    let f = AffinePoint::curve_group();
+    let G = RistrettoPoint::generator();
+    let a = f.elem(&123456789u32);
+    let b = f.elem(&987654321u32);
+
+    let aG = &G * &a;
+    let bG = &G * &b;
+    assert_eq!(&aG + &bG, &G * (&a + &b));
+    assert_eq!(&aG - &bG, &G * (&a - &b));
+    assert_eq!(&aG + -&aG, RistrettoPoint::identity());
+
+    // l * G = identity
+    let l_minus_1 = f.elem(&0u8) - f.elem(&1u8);
+    assert_eq!(&G * &l_minus_1 + &G, RistrettoPoint::identity());
+
+    let P = RistrettoPoint::decode(&aG.encode()).unwrap();
+    assert_eq!(P, aG);
+  }
+}
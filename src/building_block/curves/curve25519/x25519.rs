@@ -0,0 +1,220 @@
+#![allow(non_snake_case)]
+use crate::building_block::{
+  curves::curve25519::affine_point::{
+    AffinePoint,
+    write_biguint_to_32_byte_buf_as_le_integer,
+  },
+  field::prime_field_elem::PrimeFieldElem,
+  hasher::{
+    hasher::Hasher,
+    sha512::Sha512,
+  },
+};
+use num_bigint::BigUint;
+
+// implementation based on:
+// - https://datatracker.ietf.org/doc/html/rfc7748
+// - https://datatracker.ietf.org/doc/html/rfc8032 (5.1.5) for Ed25519 key conversion
+
+const A24: u32 = 121665;  // (486662 - 2) / 4
+
+pub struct X25519();
+
+impl X25519 {
+  pub fn new() -> Self {
+    X25519()
+  }
+
+  // u-coordinate of the base point
+  pub fn base_point() -> [u8; 32] {
+    let mut u = [0u8; 32];
+    u[0] = 9;
+    u
+  }
+
+  // clear the lowest 3 bits and the highest bit, and set the 2nd highest bit
+  pub fn clamp(k: &[u8; 32]) -> BigUint {
+    let mut k = k.clone();
+    k[0] &= 0b1111_1000;
+    k[31] &= 0b0111_1111;
+    k[31] |= 0b0100_0000;
+    BigUint::from_bytes_le(&k)
+  }
+
+  // the most significant bit is ignored and non-canonical values are reduced mod p
+  fn decode_u(u: &[u8; 32]) -> PrimeFieldElem {
+    let mut u = u.clone();
+    u[31] &= 0b0111_1111;
+    AffinePoint::base_field().elem(&BigUint::from_bytes_le(&u))
+  }
+
+  fn cswap(swap: bool, a: PrimeFieldElem, b: PrimeFieldElem) -> (PrimeFieldElem, PrimeFieldElem) {
+    if swap { (b, a) } else { (a, b) }
+  }
+
+  // Montgomery ladder
+  pub fn scalar_mul(&self, k: &[u8; 32], u: &[u8; 32]) -> [u8; 32] {
+    let f = AffinePoint::base_field();
+    let k = Self::clamp(k);
+    let x1 = Self::decode_u(u);
+
+    let mut x2 = f.elem(&1u8);
+    let mut z2 = f.elem(&0u8);
+    let mut x3 = x1.clone();
+    let mut z3 = f.elem(&1u8);
+    let mut swap = false;
+    let a24 = f.elem(&A24);
+
+    for t in (0..255).rev() {
+      let k_t = k.bit(t);
+      swap ^= k_t;
+      (x2, x3) = Self::cswap(swap, x2, x3);
+      (z2, z3) = Self::cswap(swap, z2, z3);
+      swap = k_t;
+
+      let A = &x2 + &z2;
+      let AA = A.sq();
+      let B = &x2 - &z2;
+      let BB = B.sq();
+      let E = &AA - &BB;
+      let C = &x3 + &z3;
+      let D = &x3 - &z3;
+      let DA = D * &A;
+      let CB = C * &B;
+      x3 = (&DA + &CB).sq();
+      z3 = &x1 * (&DA - &CB).sq();
+      x2 = &AA * &BB;
+      z2 = &E * (AA + &a24 * &E);
+    }
+    (x2, _) = Self::cswap(swap, x2, x3);
+    (z2, _) = Self::cswap(swap, z2, z3);
+
+    // x2 * z2^(p-2). z2 = 0 results in 0
+    let p = f.order_ref();
+    let u = x2 * z2.pow(&(p - 2u8));
+    write_biguint_to_32_byte_buf_as_le_integer(&u.e)
+  }
+
+  pub fn gen_pub_key(&self, prv_key: &[u8; 32]) -> [u8; 32] {
+    self.scalar_mul(prv_key, &Self::base_point())
+  }
+
+  pub fn shared_secret(&self, prv_key: &[u8; 32], pub_key: &[u8; 32]) -> Result<[u8; 32], String> {
+    let secret = self.scalar_mul(prv_key, pub_key);
+
+    // all-zero output means pub_key is a small-order point
+    if secret == [0u8; 32] {
+      return Err("Shared secret is all zero".to_string());
+    }
+    Ok(secret)
+  }
+
+  // converts Ed25519 public key (x, y) to u = (1 + y) / (1 - y)
+  pub fn from_ed25519_pub_key(ed_pub_key: &[u8; 32]) -> Result<[u8; 32], String> {
+    let f = AffinePoint::base_field();
+    let mut buf = ed_pub_key.clone();
+    buf[31] &= 0b0111_1111;  // clear sign bit of x
+    let y = BigUint::from_bytes_le(&buf);
+    if &y >= f.order_ref() {
+      return Err("Non-canonical y-coordinate".to_string());
+    }
+    let y = f.elem(&y);
+    let one = f.elem(&1u8);
+    let denom_inv = (&one - &y).safe_inv()?;
+    let u = (&one + &y) * denom_inv;
+    Ok(write_biguint_to_32_byte_buf_as_le_integer(&u.e))
+  }
+
+  // X25519 private key is the lower 32 bytes of SHA512(Ed25519 private key).
+  // clamping is applied when the key is used
+  pub fn from_ed25519_prv_key(ed_prv_key: &[u8; 32]) -> [u8; 32] {
+    let digest = Sha512().get_digest(ed_prv_key);
+    digest[0..32].try_into().unwrap()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::building_block::curves::curve25519::ed25519_sha512::Ed25519Sha512;
+
+  fn decode(s: &str) -> [u8; 32] {
+    hex::decode(s).unwrap().try_into().unwrap()
+  }
+
+  #[test]
+  fn rfc7748_test_vectors() {
+    let x25519 = X25519::new();
+    let test_cases = [
+      (
+        "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+        "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+        "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+      ),
+      (
+        "4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d",
+        "e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493",
+        "95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957",
+      ),
+    ];
+    for (k, u, exp) in test_cases {
+      assert_eq!(x25519.scalar_mul(&decode(k), &decode(u)), decode(exp));
+    }
+  }
+
+  #[test]
+  fn rfc7748_iterations() {
+    let x25519 = X25519::new();
+    let mut k = X25519::base_point();
+    let mut u = X25519::base_point();
+
+    for i in 0..1000 {
+      let r = x25519.scalar_mul(&k, &u);
+      u = k;
+      k = r;
+      if i == 0 {
+        assert_eq!(k, decode("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079"));
+      }
+    }
+    assert_eq!(k, decode("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51"));
+  }
+
+  #[test]
+  fn rfc7748_diffie_hellman() {
+    let x25519 = X25519::new();
+    let alice_prv = decode("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    let bob_prv = decode("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+
+    let alice_pub = x25519.gen_pub_key(&alice_prv);
+    let bob_pub = x25519.gen_pub_key(&bob_prv);
+    assert_eq!(alice_pub, decode("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"));
+    assert_eq!(bob_pub, decode("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"));
+
+    let exp = decode("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+    assert_eq!(x25519.shared_secret(&alice_prv, &bob_pub).unwrap(), exp);
+    assert_eq!(x25519.shared_secret(&bob_prv, &alice_pub).unwrap(), exp);
+  }
+
+  #[test]
+  fn small_order_pub_key() {
+    let x25519 = X25519::new();
+    let prv = decode("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    // u = 0 and u = 1 are small-order points
+    assert!(x25519.shared_secret(&prv, &[0u8; 32]).is_err());
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    assert!(x25519.shared_secret(&prv, &one).is_err());
+  }
+
+  #[test]
+  fn ed25519_key_conversion() {
+    // RFC 8032 test 1 key pair
+    let ed_prv = decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+    let ed_pub = Ed25519Sha512::new().gen_pub_key(&ed_prv);
+
+    let x_prv = X25519::from_ed25519_prv_key(&ed_prv);
+    let x_pub = X25519::from_ed25519_pub_key(&ed_pub).unwrap();
+    assert_eq!(x_pub, decode("d85e07ec22b0ad881537c2f44d662d1a143cf830c57aca4305d85c7a90f6b62e"));
+    assert_eq!(X25519::new().gen_pub_key(&x_prv), x_pub);
+  }
+}
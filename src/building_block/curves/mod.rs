@@ -1,6 +1,7 @@
 pub mod bls12_381;
 pub mod curve25519;
 mod macros;
+pub mod multi_scalar_mul;
 pub mod rational_point;
 pub mod secp256k1;
 pub mod weierstrass_eq;
\ No newline at end of file
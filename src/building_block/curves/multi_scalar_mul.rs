@@ -0,0 +1,26 @@
+use crate::building_block::zero::Zero;
+use std::ops::Add;
+use num_bigint::BigUint;
+
+// sum of scalars[i] * points[i] w/ interleaved double-and-add. the points share a single
+// doubling chain over the bits of the longest scalar, and each point is added at every bit
+// where its scalar is set
+pub fn multi_scalar_mul<P>(points: &[P], scalars: &[BigUint]) -> P
+where
+  P: Zero<P>,
+  for<'a> &'a P: Add<&'a P, Output = P>,
+{
+  assert_eq!(points.len(), scalars.len());
+  let num_bits = scalars.iter().map(|x| x.bits()).max().unwrap_or(0);
+
+  let mut res = P::zero();
+  for i in (0..num_bits).rev() {
+    res = &res + &res;
+    for (pt, scalar) in points.iter().zip(scalars.iter()) {
+      if scalar.bit(i) {
+        res = &res + pt;
+      }
+    }
+  }
+  res
+}
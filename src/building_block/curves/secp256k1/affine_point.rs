@@ -7,19 +7,23 @@ use crate::{
       prime_field_elem::PrimeFieldElem,
     },
     curves::{
+      multi_scalar_mul,
       rational_point::RationalPoint,
       weierstrass_eq::WeierstrassEq,
     },
+    random_number::RandomNumber,
     zero::Zero,
   },
 };
 use std::{
   fmt,
-  ops::{Add, Mul},
+  ops::{Add, Mul, Neg},
   sync::Arc,
 };
 use num_bigint::BigUint;
+use num_traits::Zero as NumTraitsZero;
 use once_cell::sync::Lazy;
+use rand::{CryptoRng, RngCore};
 
 #[derive(Clone)]
 pub enum AffinePoint {
@@ -67,10 +71,14 @@ impl AffinePoint {
   }
 
   pub fn rand_point(exclude_zero: bool) -> Self {
+    Self::rand_point_with_rng(exclude_zero, &mut RandomNumber::new().gen)
+  }
+
+  pub fn rand_point_with_rng(exclude_zero: bool, rng: &mut (impl RngCore + CryptoRng)) -> Self {
     let g = &AffinePoint::g();
     let f = &AffinePoint::curve_group();
     loop {
-      let multiplier = &f.rand_elem(exclude_zero);
+      let multiplier = &f.rand_elem_with_rng(exclude_zero, rng);
       let p = g * multiplier;
       if !exclude_zero || !p.is_zero() { return p; }
     }
@@ -85,6 +93,88 @@ impl AffinePoint {
       }
     }
   }
+
+  // returns the point w/ the given x-coordinate and even y-coordinate
+  pub fn lift_x(x: &BigUint) -> Result<AffinePoint, String> {
+    let f = Self::base_field();
+    if x >= f.order_ref() {
+      return Err("x-coordinate is not a base field element".to_string());
+    }
+    let x = f.elem(x);
+
+    // y^2 = x^3 + 7
+    let c = x.cube() + 7u8;
+
+    // since p = 3 mod 4, c^((p+1)/4) is a square root of c if c is a quadratic residue
+    let exp = (f.order_ref() + 1u8) / 4u8;
+    let y = c.pow(&exp);
+    if y.sq() != c {
+      return Err("x-coordinate is not on the curve".to_string());
+    }
+    let y = if (&y.e % 2u8).is_zero() { y } else { -y };
+    Ok(AffinePoint::new(&x, &y))
+  }
+
+  pub fn has_even_y(&self) -> bool {
+    match self {
+      AffinePoint::AtInfinity => panic!("Point at infinity has no y-coordinate"),
+      AffinePoint::Rational { x: _, y } => (&y.e % 2u8).is_zero(),
+    }
+  }
+
+  // 32-byte big-endian x-coordinate
+  pub fn to_x_only_bytes(&self) -> [u8; 32] {
+    match self {
+      AffinePoint::AtInfinity => panic!("Point at infinity has no x-coordinate"),
+      AffinePoint::Rational { x, y: _ } => Self::write_biguint_to_32_byte_buf(&x.e),
+    }
+  }
+
+  // SEC1 compressed encoding: 0x02 (even y) or 0x03 (odd y) followed by x-coordinate
+  pub fn to_compressed_bytes(&self) -> [u8; 33] {
+    let mut buf = [0u8; 33];
+    buf[0] = if self.has_even_y() { 0x02 } else { 0x03 };
+    buf[1..].copy_from_slice(&self.to_x_only_bytes());
+    buf
+  }
+
+  // SEC1 uncompressed encoding: 0x04 followed by x and y-coordinates
+  pub fn to_uncompressed_bytes(&self) -> [u8; 65] {
+    match self {
+      AffinePoint::AtInfinity => panic!("Point at infinity cannot be encoded"),
+      AffinePoint::Rational { x, y } => {
+        let mut buf = [0u8; 65];
+        buf[0] = 0x04;
+        buf[1..33].copy_from_slice(&Self::write_biguint_to_32_byte_buf(&x.e));
+        buf[33..].copy_from_slice(&Self::write_biguint_to_32_byte_buf(&y.e));
+        buf
+      },
+    }
+  }
+
+  pub fn from_compressed_bytes(buf: &[u8; 33]) -> Result<AffinePoint, String> {
+    let p = Self::lift_x(&BigUint::from_bytes_be(&buf[1..]))?;
+    match buf[0] {
+      0x02 => Ok(p),
+      0x03 => Ok(-p),
+      _ => Err(format!("Invalid compressed point prefix {:#04x}", buf[0])),
+    }
+  }
+
+  pub fn write_biguint_to_32_byte_buf(n: &BigUint) -> [u8; 32] {
+    let bytes_be = n.to_bytes_be();
+    assert!(bytes_be.len() <= 32);
+
+    // write to 32-byte buffer w/ 0 padding on lower index side
+    let mut buf = [0u8; 32];
+    buf[32 - bytes_be.len()..].copy_from_slice(&bytes_be);
+    buf
+  }
+
+  // sum of scalars[i] * points[i] w/ interleaved double-and-add
+  pub fn multi_scalar_mul(points: &[AffinePoint], scalars: &[BigUint]) -> AffinePoint {
+    multi_scalar_mul::multi_scalar_mul(points, scalars)
+  }
 }
 
 impl RationalPoint for AffinePoint {
@@ -161,6 +251,25 @@ impl PartialEq for AffinePoint {
 
 impl Eq for AffinePoint {}
 
+macro_rules! impl_neg {
+  ($target: ty) => {
+    impl Neg for $target {
+      type Output = AffinePoint;
+
+      fn neg(self) -> Self::Output {
+        match self {
+          AffinePoint::AtInfinity => AffinePoint::AtInfinity,
+          AffinePoint::Rational { x, y } => {
+            AffinePoint::new(&x, &y.negate())
+          }
+        }
+      }
+    }
+  }
+}
+impl_neg!(AffinePoint);
+impl_neg!(&AffinePoint);
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -255,6 +364,65 @@ mod tests {
     }
   }
 
+  #[test]
+  fn neg() {
+    let g = &AffinePoint::g();
+    assert!((g + -g).is_zero());
+    assert_eq!(-(-g), g.clone());
+  }
+
+  #[test]
+  fn lift_x() {
+    let g = &AffinePoint::g();
+    let g_x = BigUint::from_bytes_be(&g.to_x_only_bytes());
+    // y-coordinate of g is even
+    assert_eq!(&AffinePoint::lift_x(&g_x).unwrap(), g);
+
+    // 5 is not an x-coordinate of any point since 5^3 + 7 is not a quadratic residue
+    assert!(AffinePoint::lift_x(&BigUint::from(5u8)).is_err());
+
+    // x-coordinate larger than p
+    let p = AffinePoint::base_field().order();
+    assert!(AffinePoint::lift_x(&p).is_err());
+  }
+
+  #[test]
+  #[allow(non_snake_case)]
+  fn multi_scalar_mul() {
+    let g = &AffinePoint::g();
+    let f = &AffinePoint::curve_group();
+    let P = g * f.elem(&5u8);
+    let Q = g * f.elem(&11u8);
+
+    let act = AffinePoint::multi_scalar_mul(
+      &[g.clone(), P.clone(), Q.clone()],
+      &[BigUint::from(3u8), BigUint::from(0u8), BigUint::from(1234u32)],
+    );
+    let exp = g * f.elem(&3u8) + &Q * f.elem(&1234u32);
+    assert_eq!(act, exp);
+
+    assert_eq!(AffinePoint::multi_scalar_mul(&[], &[]), AffinePoint::zero());
+  }
+
+  #[test]
+  fn compressed_bytes_round_trip() {
+    let g = &AffinePoint::g();
+    let f = AffinePoint::curve_group();
+    for n in 1u8..=10 {
+      let p = g * f.elem(&n);
+      let buf = p.to_compressed_bytes();
+      assert_eq!(buf[0], if p.has_even_y() { 0x02 } else { 0x03 });
+      assert_eq!(AffinePoint::from_compressed_bytes(&buf).unwrap(), p);
+    }
+    let buf = hex::decode("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9").unwrap();
+    let p = AffinePoint::from_compressed_bytes(&buf.try_into().unwrap()).unwrap();
+    assert_eq!(p, g * f.elem(&3u8));
+
+    let mut buf = g.to_compressed_bytes();
+    buf[0] = 0x04;
+    assert!(AffinePoint::from_compressed_bytes(&buf).is_err());
+  }
+
   struct Xy<'a> {
     _n: &'a str,
     x: &'a [u8; 64],
@@ -4,8 +4,10 @@ use crate::building_block::{
     prime_field_elems::PrimeFieldElems,
   },
   curves::secp256k1::affine_point::AffinePoint,
+  random_number::RandomNumber,
   zero::Zero,
 };
+use rand::{CryptoRng, RngCore};
 use std::{
   fmt,
   ops::{Add, Mul, Deref},
@@ -33,11 +35,19 @@ impl AffinePoints {
   pub fn rand_points(
     exclude_zero: bool,
     length: &usize,
+  ) -> Self {
+    Self::rand_points_with_rng(exclude_zero, length, &mut RandomNumber::new().gen)
+  }
+
+  pub fn rand_points_with_rng(
+    exclude_zero: bool,
+    length: &usize,
+    rng: &mut (impl RngCore + CryptoRng),
   ) -> Self {
     let mut points = vec![];
 
     while &points.len() < length {
-      let p = AffinePoint::rand_point(exclude_zero);
+      let p = AffinePoint::rand_point_with_rng(exclude_zero, rng);
       points.push(p);
     }
     AffinePoints::new(&points)
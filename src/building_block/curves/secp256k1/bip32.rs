@@ -0,0 +1,425 @@
+#![allow(non_snake_case)]
+use crate::building_block::{
+  base58::Base58,
+  curves::secp256k1::affine_point::AffinePoint,
+  field::prime_field_elem::PrimeFieldElem,
+  hasher::{
+    hasher::Hasher,
+    hmac::Hmac,
+    ripemd160::Ripemd160,
+    sha256::Sha256,
+    sha512::Sha512,
+  },
+  zero::Zero,
+};
+use num_bigint::BigUint;
+
+// implementation based on: https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
+
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+// mainnet version bytes
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xAD, 0xE4];
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+
+const SERIALIZED_KEY_LEN: usize = 78;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DerivationPath(pub Vec<u32>);
+
+impl DerivationPath {
+  // parses a path such as m/44'/0'/0'/0/1. hardened index can be written w/ ', h or H.
+  // public key path starting w/ M is also accepted
+  pub fn parse(path: &str) -> Result<Self, String> {
+    let mut components = path.split('/');
+    match components.next() {
+      Some("m") | Some("M") => (),
+      _ => return Err(format!("Path needs to start with m: {}", path)),
+    }
+
+    let mut indices = vec![];
+    for c in components {
+      let (num, is_hardened) = match c.strip_suffix(|x| x == '\'' || x == 'h' || x == 'H') {
+        Some(num) => (num, true),
+        None => (c, false),
+      };
+      // only plain decimal digits w/o sign or leading zeros are accepted
+      let is_canonical = !num.is_empty()
+        && num.bytes().all(|b| b.is_ascii_digit())
+        && (num == "0" || !num.starts_with('0'));
+      let index = match num.parse::<u32>() {
+        Ok(x) if is_canonical && x < HARDENED_OFFSET => x,
+        _ => return Err(format!("Invalid path component: {}", c)),
+      };
+      indices.push(if is_hardened { index + HARDENED_OFFSET } else { index });
+    }
+    Ok(DerivationPath(indices))
+  }
+}
+
+fn hash160(data: &[u8]) -> [u8; 20] {
+  Ripemd160().get_digest(&Sha256().get_digest(data))
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> ([u8; 32], [u8; 32]) {
+  let hmac = Hmac::new(Box::new(Sha512()));
+  let I = hmac.get_digest(key, data);
+  (I[0..32].try_into().unwrap(), I[32..64].try_into().unwrap())
+}
+
+// parse256(I_L) that needs to be less than n
+fn parse_IL(IL: &[u8; 32]) -> Result<PrimeFieldElem, String> {
+  let f_n = AffinePoint::curve_group();
+  let n = BigUint::from_bytes_be(IL);
+  if &n >= f_n.order_ref() {
+    return Err("Derived key is invalid. Proceed with the next index".to_string());
+  }
+  Ok(f_n.elem(&n))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendedPrivateKey {
+  pub depth: u8,
+  pub parent_fingerprint: [u8; 4],
+  pub child_number: u32,
+  pub chain_code: [u8; 32],
+  pub priv_key: PrimeFieldElem,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendedPublicKey {
+  pub depth: u8,
+  pub parent_fingerprint: [u8; 4],
+  pub child_number: u32,
+  pub chain_code: [u8; 32],
+  pub pub_key: AffinePoint,
+}
+
+// splits serialized extended key into version, depth, parent fingerprint,
+// child number, chain code and key data
+fn parse_serialized_key(s: &str) -> Result<([u8; 4], u8, [u8; 4], u32, [u8; 32], [u8; 33]), String> {
+  let buf = Base58::check_decode(s)?;
+  if buf.len() != SERIALIZED_KEY_LEN {
+    return Err(format!("Serialized key needs to be {} bytes, but got {}", SERIALIZED_KEY_LEN, buf.len()));
+  }
+  let version: [u8; 4] = buf[0..4].try_into().unwrap();
+  let depth = buf[4];
+  let parent_fingerprint: [u8; 4] = buf[5..9].try_into().unwrap();
+  let child_number = u32::from_be_bytes(buf[9..13].try_into().unwrap());
+  let chain_code: [u8; 32] = buf[13..45].try_into().unwrap();
+  let key_data: [u8; 33] = buf[45..78].try_into().unwrap();
+
+  if depth == 0 && (parent_fingerprint != [0u8; 4] || child_number != 0) {
+    return Err("Master key has non-zero parent fingerprint or child number".to_string());
+  }
+  Ok((version, depth, parent_fingerprint, child_number, chain_code, key_data))
+}
+
+fn serialize_key(
+  version: &[u8; 4],
+  depth: u8,
+  parent_fingerprint: &[u8; 4],
+  child_number: u32,
+  chain_code: &[u8; 32],
+  key_data: &[u8; 33],
+) -> String {
+  let buf = [
+    &version[..],
+    &[depth],
+    parent_fingerprint,
+    &child_number.to_be_bytes(),
+    chain_code,
+    key_data,
+  ].concat();
+  Base58::check_encode(&buf)
+}
+
+impl ExtendedPrivateKey {
+  pub fn from_seed(seed: &[u8]) -> Result<Self, String> {
+    if seed.len() < 16 || seed.len() > 64 {
+      return Err("Seed needs to be between 128 and 512 bits".to_string());
+    }
+    let (IL, IR) = hmac_sha512(b"Bitcoin seed", seed);
+    let priv_key = parse_IL(&IL)?;
+    if priv_key.is_zero() {
+      return Err("Derived master key is zero".to_string());
+    }
+    Ok(ExtendedPrivateKey {
+      depth: 0,
+      parent_fingerprint: [0u8; 4],
+      child_number: 0,
+      chain_code: IR,
+      priv_key,
+    })
+  }
+
+  pub fn to_extended_pub_key(&self) -> ExtendedPublicKey {
+    ExtendedPublicKey {
+      depth: self.depth,
+      parent_fingerprint: self.parent_fingerprint,
+      child_number: self.child_number,
+      chain_code: self.chain_code,
+      pub_key: AffinePoint::g() * &self.priv_key,
+    }
+  }
+
+  pub fn fingerprint(&self) -> [u8; 4] {
+    self.to_extended_pub_key().fingerprint()
+  }
+
+  // CKDpriv
+  pub fn derive_child(&self, index: u32) -> Result<Self, String> {
+    if self.depth == u8::MAX {
+      return Err("Maximum depth has been reached".to_string());
+    }
+    let pub_key = (AffinePoint::g() * &self.priv_key).to_compressed_bytes();
+
+    let data = if index >= HARDENED_OFFSET {
+      // 0x00 || ser256(k_par) || ser32(i)
+      let priv_key = AffinePoint::write_biguint_to_32_byte_buf(&self.priv_key.e);
+      [&[0u8][..], &priv_key, &index.to_be_bytes()].concat()
+    } else {
+      // serP(point(k_par)) || ser32(i)
+      [&pub_key[..], &index.to_be_bytes()].concat()
+    };
+    let (IL, IR) = hmac_sha512(&self.chain_code, &data);
+
+    // k_i = parse256(I_L) + k_par (mod n)
+    let priv_key = parse_IL(&IL)? + &self.priv_key;
+    if priv_key.is_zero() {
+      return Err("Derived key is zero. Proceed with the next index".to_string());
+    }
+
+    Ok(ExtendedPrivateKey {
+      depth: self.depth + 1,
+      parent_fingerprint: hash160(&pub_key)[0..4].try_into().unwrap(),
+      child_number: index,
+      chain_code: IR,
+      priv_key,
+    })
+  }
+
+  pub fn derive_path(&self, path: &str) -> Result<Self, String> {
+    let path = DerivationPath::parse(path)?;
+    let mut key = self.clone();
+    for index in path.0 {
+      key = key.derive_child(index)?;
+    }
+    Ok(key)
+  }
+
+  pub fn serialize(&self) -> String {
+    let priv_key = AffinePoint::write_biguint_to_32_byte_buf(&self.priv_key.e);
+    let key_data: [u8; 33] = [&[0u8][..], &priv_key].concat().try_into().unwrap();
+    serialize_key(&XPRV_VERSION, self.depth, &self.parent_fingerprint, self.child_number, &self.chain_code, &key_data)
+  }
+
+  pub fn deserialize(s: &str) -> Result<Self, String> {
+    let (version, depth, parent_fingerprint, child_number, chain_code, key_data) = parse_serialized_key(s)?;
+    if version != XPRV_VERSION {
+      return Err("Not an extended private key".to_string());
+    }
+    if key_data[0] != 0 {
+      return Err("Private key data needs to be prefixed with 0x00".to_string());
+    }
+    let priv_key = parse_IL(&key_data[1..33].try_into().unwrap())?;
+    if priv_key.is_zero() {
+      return Err("Private key is zero".to_string());
+    }
+    Ok(ExtendedPrivateKey { depth, parent_fingerprint, child_number, chain_code, priv_key })
+  }
+}
+
+impl ExtendedPublicKey {
+  pub fn fingerprint(&self) -> [u8; 4] {
+    hash160(&self.pub_key.to_compressed_bytes())[0..4].try_into().unwrap()
+  }
+
+  // CKDpub. only non-hardened children can be derived
+  pub fn derive_child(&self, index: u32) -> Result<Self, String> {
+    if index >= HARDENED_OFFSET {
+      return Err("Hardened child cannot be derived from public key".to_string());
+    }
+    if self.depth == u8::MAX {
+      return Err("Maximum depth has been reached".to_string());
+    }
+    let pub_key = self.pub_key.to_compressed_bytes();
+    let data = [&pub_key[..], &index.to_be_bytes()].concat();
+    let (IL, IR) = hmac_sha512(&self.chain_code, &data);
+
+    // K_i = point(parse256(I_L)) + K_par
+    let child_pub_key = AffinePoint::g() * parse_IL(&IL)? + &self.pub_key;
+    if child_pub_key.is_zero() {
+      return Err("Derived key is point at infinity. Proceed with the next index".to_string());
+    }
+
+    Ok(ExtendedPublicKey {
+      depth: self.depth + 1,
+      parent_fingerprint: self.fingerprint(),
+      child_number: index,
+      chain_code: IR,
+      pub_key: child_pub_key,
+    })
+  }
+
+  pub fn derive_path(&self, path: &str) -> Result<Self, String> {
+    let path = DerivationPath::parse(path)?;
+    let mut key = self.clone();
+    for index in path.0 {
+      key = key.derive_child(index)?;
+    }
+    Ok(key)
+  }
+
+  pub fn serialize(&self) -> String {
+    let key_data = self.pub_key.to_compressed_bytes();
+    serialize_key(&XPUB_VERSION, self.depth, &self.parent_fingerprint, self.child_number, &self.chain_code, &key_data)
+  }
+
+  pub fn deserialize(s: &str) -> Result<Self, String> {
+    let (version, depth, parent_fingerprint, child_number, chain_code, key_data) = parse_serialized_key(s)?;
+    if version != XPUB_VERSION {
+      return Err("Not an extended public key".to_string());
+    }
+    let pub_key = AffinePoint::from_compressed_bytes(&key_data)?;
+    Ok(ExtendedPublicKey { depth, parent_fingerprint, child_number, chain_code, pub_key })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn run_test_vector(seed: &str, chains: &[(&str, &str, &str)]) {
+    let master = ExtendedPrivateKey::from_seed(&hex::decode(seed).unwrap()).unwrap();
+    for (path, exp_xpub, exp_xprv) in chains {
+      let xprv = master.derive_path(path).unwrap();
+      let xpub = xprv.to_extended_pub_key();
+      assert_eq!(&xprv.serialize(), exp_xprv);
+      assert_eq!(&xpub.serialize(), exp_xpub);
+
+      assert_eq!(&ExtendedPrivateKey::deserialize(exp_xprv).unwrap(), &xprv);
+      assert_eq!(&ExtendedPublicKey::deserialize(exp_xpub).unwrap(), &xpub);
+    }
+  }
+
+  #[test]
+  fn test_vector_1() {
+    run_test_vector("000102030405060708090a0b0c0d0e0f", &[
+      ("m",
+        "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi"),
+      ("m/0H",
+        "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+        "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7"),
+      ("m/0H/1",
+        "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+        "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs"),
+      ("m/0H/1/2H",
+        "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+        "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM"),
+      ("m/0H/1/2H/2",
+        "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+        "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334"),
+      ("m/0H/1/2H/2/1000000000",
+        "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+        "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76"),
+    ]);
+  }
+
+  #[test]
+  fn test_vector_2() {
+    run_test_vector("fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542", &[
+      ("m",
+        "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB",
+        "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U"),
+      ("m/0",
+        "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH",
+        "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt"),
+      ("m/0/2147483647H",
+        "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a",
+        "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9"),
+      ("m/0/2147483647H/1",
+        "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon",
+        "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef"),
+      ("m/0/2147483647H/1/2147483646H",
+        "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL",
+        "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc"),
+      ("m/0/2147483647H/1/2147483646H/2",
+        "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt",
+        "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j"),
+    ]);
+  }
+
+  #[test]
+  fn test_vector_3_leading_zeros() {
+    run_test_vector("4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be", &[
+      ("m",
+        "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13",
+        "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6"),
+      ("m/0H",
+        "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y",
+        "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L"),
+    ]);
+  }
+
+  #[test]
+  fn public_derivation_matches_private_derivation() {
+    let master = ExtendedPrivateKey::from_seed(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap()).unwrap();
+    let xprv = master.derive_path("m/44'/0'/0'").unwrap();
+    let xpub = xprv.to_extended_pub_key();
+
+    let from_prv = xprv.derive_path("m/0/1").unwrap().to_extended_pub_key();
+    let from_pub = xpub.derive_path("M/0/1").unwrap();
+    assert_eq!(from_prv, from_pub);
+
+    // hardened child cannot be derived from public key
+    assert!(xpub.derive_child(HARDENED_OFFSET).is_err());
+    assert!(xpub.derive_path("m/0'").is_err());
+  }
+
+  #[test]
+  fn parse_path() {
+    assert_eq!(DerivationPath::parse("m").unwrap().0, Vec::<u32>::new());
+    assert_eq!(
+      DerivationPath::parse("m/44'/0'/0'/0/1").unwrap().0,
+      vec![44 + HARDENED_OFFSET, HARDENED_OFFSET, HARDENED_OFFSET, 0, 1],
+    );
+    assert_eq!(
+      DerivationPath::parse("m/0h/2147483647H").unwrap().0,
+      vec![HARDENED_OFFSET, u32::MAX],
+    );
+    assert!(DerivationPath::parse("").is_err());
+    assert!(DerivationPath::parse("44'/0'").is_err());
+    assert!(DerivationPath::parse("m/").is_err());
+    assert!(DerivationPath::parse("m/a").is_err());
+    assert!(DerivationPath::parse("m/2147483648").is_err());
+    assert!(DerivationPath::parse("m/-1").is_err());
+    assert!(DerivationPath::parse("m/+1/+0'").is_err());
+    assert!(DerivationPath::parse("m/007").is_err());
+    assert!(DerivationPath::parse("m/'").is_err());
+    assert!(DerivationPath::parse("m/ 1").is_err());
+  }
+
+  #[test]
+  fn deserialize_invalid_keys() {
+    let xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+    let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+    // version mismatch
+    assert!(ExtendedPrivateKey::deserialize(xpub).is_err());
+    assert!(ExtendedPublicKey::deserialize(xprv).is_err());
+
+    // checksum mismatch
+    let mut bad = xprv.to_string();
+    bad.pop();
+    bad.push('j');
+    assert!(ExtendedPrivateKey::deserialize(&bad).is_err());
+  }
+
+  #[test]
+  fn seed_length() {
+    assert!(ExtendedPrivateKey::from_seed(&[0u8; 15]).is_err());
+    assert!(ExtendedPrivateKey::from_seed(&[0u8; 65]).is_err());
+  }
+}
@@ -0,0 +1,120 @@
+#![allow(non_snake_case)]
+use crate::building_block::{
+  curves::secp256k1::affine_point::AffinePoint,
+  field::prime_field_elem::PrimeFieldElem,
+  hasher::{
+    hasher::Hasher,
+    sha256::Sha256,
+  },
+};
+
+// implementation based on: https://www.secg.org/sec1-v2.pdf (3.3.1)
+// hashed shared secret is compatible w/ libsecp256k1's default ECDH hash function
+
+pub struct Ecdh {
+  pub hasher: Sha256,
+}
+
+impl Ecdh {
+  pub fn new(hasher: &Sha256) -> Self {
+    Ecdh {
+      hasher: hasher.clone(),
+    }
+  }
+
+  pub fn gen_pub_key(&self, priv_key: &PrimeFieldElem) -> AffinePoint {
+    AffinePoint::g() * priv_key
+  }
+
+  // P = priv_key * pub_key
+  fn shared_point(&self, priv_key: &PrimeFieldElem, pub_key: &AffinePoint) -> Result<AffinePoint, String> {
+    let f_n = AffinePoint::curve_group();
+    if priv_key.f.order_ref() != f_n.order_ref() {
+      return Err("Private key needs to be an element of curve group".to_string());
+    }
+    if priv_key.is_zero() {
+      return Err("Private key needs to be in [1, n-1]".to_string());
+    }
+    match pub_key {
+      AffinePoint::AtInfinity => {
+        return Err("Public key is point at infinity".to_string());
+      },
+      AffinePoint::Rational { x, y } => {
+        // y^2 = x^3 + 7
+        if y.sq() != x.cube() + 7u8 {
+          return Err("Public key is not on the curve".to_string());
+        }
+      },
+    }
+    Ok(pub_key * priv_key)
+  }
+
+  // x-coordinate of the shared point
+  pub fn shared_x(&self, priv_key: &PrimeFieldElem, pub_key: &AffinePoint) -> Result<[u8; 32], String> {
+    let P = self.shared_point(priv_key, pub_key)?;
+    Ok(P.to_x_only_bytes())
+  }
+
+  // SHA256(compressed shared point)
+  pub fn shared_secret(&self, priv_key: &PrimeFieldElem, pub_key: &AffinePoint) -> Result<[u8; 32], String> {
+    let P = self.shared_point(priv_key, pub_key)?;
+    Ok(self.hasher.get_digest(&P.to_compressed_bytes()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use num_bigint::BigUint;
+
+  fn priv_key(s: &str) -> PrimeFieldElem {
+    AffinePoint::curve_group().elem(&BigUint::parse_bytes(s.as_bytes(), 16).unwrap())
+  }
+
+  #[test]
+  fn shared_secret_agreement() {
+    let ecdh = Ecdh::new(&Sha256());
+    let a = priv_key("3f6c1c5ae0b2b0bba2ef8bb0a1f1b3d8a3a1c7ed2b3e4f1a5c6d7e8f9a0b1c2d");
+    let b = priv_key("7a1b2c3d4e5f60718293a4b5c6d7e8f90112233445566778899aabbccddeeff0");
+
+    let A = ecdh.gen_pub_key(&a);
+    let B = ecdh.gen_pub_key(&b);
+    assert_eq!(A.to_compressed_bytes().to_vec(), hex::decode("03323849d019592acb665ce7ae0b612aad2caeea137c10484e7459f58f6dc51ecb").unwrap());
+    assert_eq!(B.to_compressed_bytes().to_vec(), hex::decode("02e742fd144d6505385488f2a1b576a5d4274d55d060ebf17cde56c3f48963de15").unwrap());
+
+    let x_ab = ecdh.shared_x(&a, &B).unwrap();
+    let x_ba = ecdh.shared_x(&b, &A).unwrap();
+    assert_eq!(x_ab, x_ba);
+    assert_eq!(x_ab.to_vec(), hex::decode("fe4c9c6736eba47e155c581ffa453ea386c8b2f5154cab525fd9db0204d37a5a").unwrap());
+
+    let s_ab = ecdh.shared_secret(&a, &B).unwrap();
+    let s_ba = ecdh.shared_secret(&b, &A).unwrap();
+    assert_eq!(s_ab, s_ba);
+    assert_eq!(s_ab.to_vec(), hex::decode("f849930438d17e25b208f9da4da528077b898dac633f12a73da50d742068e226").unwrap());
+  }
+
+  #[test]
+  fn random_key_agreement() {
+    let ecdh = Ecdh::new(&Sha256());
+    let f_n = AffinePoint::curve_group();
+    let a = f_n.rand_elem(true);
+    let b = f_n.rand_elem(true);
+    let A = ecdh.gen_pub_key(&a);
+    let B = ecdh.gen_pub_key(&b);
+    assert_eq!(ecdh.shared_secret(&a, &B).unwrap(), ecdh.shared_secret(&b, &A).unwrap());
+  }
+
+  #[test]
+  fn invalid_keys() {
+    let ecdh = Ecdh::new(&Sha256());
+    let f_n = AffinePoint::curve_group();
+    let f = AffinePoint::base_field();
+    let a = f_n.elem(&3u8);
+
+    assert!(ecdh.shared_secret(&a, &AffinePoint::AtInfinity).is_err());
+    let off_curve = AffinePoint::new(&f.elem(&1u8), &f.elem(&1u8));
+    assert!(ecdh.shared_secret(&a, &off_curve).is_err());
+    assert!(ecdh.shared_secret(&f_n.elem(&0u8), &AffinePoint::g()).is_err());
+    assert!(ecdh.shared_secret(&f.elem(&3u8), &AffinePoint::g()).is_err());
+  }
+}
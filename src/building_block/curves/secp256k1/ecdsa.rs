@@ -8,10 +8,12 @@ use crate::building_block::{
     hasher::Hasher,
     sha256::Sha256,
   },
+  random_number::RandomNumber,
   zero::Zero,
 };
 use num_bigint::BigUint;
 use num_traits::Zero as NumTraisZero;
+use rand::{CryptoRng, RngCore};
 
 #[derive(Debug, Clone)]
 pub struct Signature {
@@ -35,6 +37,16 @@ impl Ecdsa {
   }
 
   pub fn sign(&self, priv_key: &PrimeFieldElem, message: &[u8]) -> Result<Signature, String> {
+    self.sign_with_rng(priv_key, message, &mut RandomNumber::new().gen)
+  }
+
+  // k is drawn from rng
+  pub fn sign_with_rng(
+    &self,
+    priv_key: &PrimeFieldElem,
+    message: &[u8],
+    rng: &mut (impl RngCore + CryptoRng),
+  ) -> Result<Signature, String> {
     let f_n = &AffinePoint::curve_group();
 
     if priv_key.f.order_ref() != f_n.order_ref() {
@@ -48,7 +60,7 @@ impl Ecdsa {
 
     loop {
       // generate temporary non-zero random number k (mod n)
-      let k = f_n.rand_elem(true);
+      let k = f_n.rand_elem_with_rng(true, rng);
 
       // e = HASH(message)
       // z = e's uppermost Ln bits (Ln = order of n = 256 bits)
@@ -315,4 +327,30 @@ mod tests {
     let is_verified = ecdsa.verify(&sig, &pub_key, &message);
     assert_eq!(is_verified, false);
   }
+
+  #[test]
+  fn sign_with_seeded_rng() {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    let hasher = Sha256();
+    let ecdsa = Ecdsa::new(&hasher);
+    let curve_group = &AffinePoint::curve_group();
+
+    let message = vec![1u8, 2, 3];
+    let priv_key = curve_group.elem(&1234u32);
+    let pub_key = ecdsa.gen_pub_key(&priv_key);
+
+    // same seed results in the same signature
+    let sig1 = ecdsa.sign_with_rng(&priv_key, &message, &mut ChaChaRng::seed_from_u64(1)).unwrap();
+    let sig2 = ecdsa.sign_with_rng(&priv_key, &message, &mut ChaChaRng::seed_from_u64(1)).unwrap();
+    assert_eq!(sig1.r, sig2.r);
+    assert_eq!(sig1.s, sig2.s);
+    assert!(ecdsa.verify(&sig1, &pub_key, &message));
+
+    // different seed results in a different signature
+    let sig3 = ecdsa.sign_with_rng(&priv_key, &message, &mut ChaChaRng::seed_from_u64(2)).unwrap();
+    assert_ne!(sig1.r, sig3.r);
+    assert!(ecdsa.verify(&sig3, &pub_key, &message));
+  }
 }
@@ -0,0 +1,107 @@
+use crate::building_block::{
+  curves::secp256k1::affine_point::AffinePoint,
+  hasher::{
+    hasher::Hasher,
+    keccak::Keccak256,
+  },
+  zero::Zero,
+};
+
+// implementation based on:
+// - https://ethereum.github.io/yellowpaper/paper.pdf (Appendix F)
+// - https://eips.ethereum.org/EIPS/eip-55
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EthAddress(pub [u8; 20]);
+
+impl EthAddress {
+  // last 20 bytes of Keccak-256 of the 64-byte x || y public key
+  pub fn from_pub_key(pub_key: &AffinePoint) -> Result<Self, String> {
+    if pub_key.is_zero() {
+      return Err("Point at infinity is not a valid public key".to_string());
+    }
+    let hash = Keccak256().get_digest(&pub_key.to_uncompressed_bytes()[1..]);
+    Ok(EthAddress(hash[12..].try_into().unwrap()))
+  }
+
+  // EIP-55 mixed-case checksum encoding w/ 0x prefix
+  pub fn to_checksum_string(&self) -> String {
+    let addr = hex::encode(self.0);
+    let hash = Keccak256().get_digest(addr.as_bytes());
+
+    let checksummed: String = addr.chars().enumerate().map(|(i, c)| {
+      let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+      if nibble >= 8 { c.to_ascii_uppercase() } else { c }
+    }).collect();
+    format!("0x{}", checksummed)
+  }
+
+  // accepts an address w/ or w/o 0x prefix. checksum is verified only if
+  // the address contains both upper and lower case letters
+  pub fn parse(s: &str) -> Result<Self, String> {
+    let hex_str = s.strip_prefix("0x").unwrap_or(s);
+    if hex_str.len() != 40 {
+      return Err(format!("Address needs to be 40 hex digits: {}", s));
+    }
+    let buf = hex::decode(hex_str).map_err(|_| format!("Invalid hex address: {}", s))?;
+    let addr = EthAddress(buf.try_into().unwrap());
+
+    let has_lower = hex_str.chars().any(|c| c.is_ascii_lowercase());
+    let has_upper = hex_str.chars().any(|c| c.is_ascii_uppercase());
+    if has_lower && has_upper && addr.to_checksum_string()[2..] != *hex_str {
+      return Err(format!("Invalid EIP-55 checksum: {}", s));
+    }
+    Ok(addr)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use num_bigint::BigUint;
+
+  fn address_of(priv_key: &BigUint) -> String {
+    let f = AffinePoint::curve_group();
+    let pub_key = AffinePoint::g() * f.elem(priv_key);
+    EthAddress::from_pub_key(&pub_key).unwrap().to_checksum_string()
+  }
+
+  #[test]
+  fn from_pub_key() {
+    assert_eq!(address_of(&BigUint::from(1u8)), "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+    assert_eq!(address_of(&BigUint::from(2u8)), "0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF");
+
+    // private key = keccak256("cow")
+    let priv_key = BigUint::from_bytes_be(&Keccak256().get_digest(b"cow"));
+    assert_eq!(address_of(&priv_key), "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826");
+
+    assert!(EthAddress::from_pub_key(&AffinePoint::AtInfinity).is_err());
+  }
+
+  #[test]
+  fn eip55_test_vectors() {
+    let addrs = [
+      "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+      "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+      "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+      "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ];
+    for s in addrs {
+      let addr = EthAddress::parse(&s.to_lowercase()).unwrap();
+      assert_eq!(addr.to_checksum_string(), s);
+      assert_eq!(EthAddress::parse(s).unwrap(), addr);
+    }
+  }
+
+  #[test]
+  fn parse_invalid_address() {
+    // wrong checksum
+    assert!(EthAddress::parse("0x5aaeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+    // single case addresses are accepted w/o checksum
+    assert!(EthAddress::parse("0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED").is_ok());
+    assert!(EthAddress::parse("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_ok());
+    // wrong length and non-hex characters
+    assert!(EthAddress::parse("0x5aaeb6053f3e94c9b9a09f33669435e7ef1bea").is_err());
+    assert!(EthAddress::parse("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beagg").is_err());
+  }
+}
@@ -1,5 +1,10 @@
 pub mod affine_point;
 pub mod affine_points;
+pub mod bip32;
+pub mod ecdh;
 pub mod ecdsa;
+pub mod eth_address;
+pub mod musig2;
+pub mod schnorr;
 // mod jacobian_point;
-// pub mod secp256k1;
\ No newline at end of file
+// pub mod secp256k1;
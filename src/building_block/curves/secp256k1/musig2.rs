@@ -0,0 +1,436 @@
+#![allow(non_snake_case)]
+use crate::building_block::{
+  curves::secp256k1::{
+    affine_point::AffinePoint,
+    schnorr::Schnorr,
+  },
+  field::prime_field_elem::PrimeFieldElem,
+  random_number::RandomNumber,
+  zero::Zero,
+};
+use rand::{CryptoRng, RngCore};
+
+// implementation based on: https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki
+// tweaking of the aggregate public key is not supported
+
+#[derive(Debug, Clone)]
+pub struct KeyAggContext {
+  pub pub_keys: Vec<[u8; 33]>,
+  pub Q: AffinePoint,  // aggregate public key
+  pk_list_hash: [u8; 32],
+  second_key: Option<[u8; 33]>,
+}
+
+impl KeyAggContext {
+  pub fn new(pub_keys: &[[u8; 33]]) -> Result<Self, String> {
+    if pub_keys.is_empty() {
+      return Err("No public key is given".to_string());
+    }
+    let pk_list_hash = Schnorr::tagged_hash("KeyAgg list", &pub_keys.concat());
+
+    // the first key that differs from the first key gets coefficient 1
+    let second_key = pub_keys.iter().find(|pk| *pk != &pub_keys[0]).cloned();
+
+    let mut ctx = KeyAggContext {
+      pub_keys: pub_keys.to_vec(),
+      Q: AffinePoint::zero(),
+      pk_list_hash,
+      second_key,
+    };
+
+    // Q = sum(a_i * P_i)
+    let mut Q = AffinePoint::zero();
+    for pk in pub_keys {
+      let P = AffinePoint::from_compressed_bytes(pk)?;
+      Q = Q + P * ctx.coeff(pk);
+    }
+    if Q.is_zero() {
+      return Err("Aggregate public key is point at infinity".to_string());
+    }
+    ctx.Q = Q;
+    Ok(ctx)
+  }
+
+  // key aggregation coefficient a_i of the given public key
+  pub fn coeff(&self, pub_key: &[u8; 33]) -> PrimeFieldElem {
+    if self.second_key.as_ref() == Some(pub_key) {
+      AffinePoint::curve_group().elem(&1u8)
+    } else {
+      Schnorr::tagged_hash_to_scalar(
+        "KeyAgg coefficient",
+        &[&self.pk_list_hash[..], &pub_key[..]].concat(),
+      )
+    }
+  }
+
+  // x-only aggregate public key that verifies the final BIP-340 signature
+  pub fn agg_pub_key(&self) -> [u8; 32] {
+    self.Q.to_x_only_bytes()
+  }
+}
+
+// secret nonce is intentionally neither Clone nor Copy so that it can be consumed only once
+pub struct SecNonce {
+  k1: PrimeFieldElem,
+  k2: PrimeFieldElem,
+  pub_key: [u8; 33],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PubNonce {
+  pub R1: AffinePoint,
+  pub R2: AffinePoint,
+}
+
+impl PubNonce {
+  pub fn to_bytes(&self) -> [u8; 66] {
+    [self.R1.to_compressed_bytes(), self.R2.to_compressed_bytes()].concat().try_into().unwrap()
+  }
+
+  pub fn from_bytes(buf: &[u8; 66]) -> Result<Self, String> {
+    let R1 = AffinePoint::from_compressed_bytes(&buf[0..33].try_into().unwrap())?;
+    let R2 = AffinePoint::from_compressed_bytes(&buf[33..66].try_into().unwrap())?;
+    Ok(PubNonce { R1, R2 })
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggNonce {
+  pub R1: AffinePoint,
+  pub R2: AffinePoint,
+}
+
+impl AggNonce {
+  // point at infinity is encoded as 33 zero bytes
+  fn point_to_bytes(p: &AffinePoint) -> [u8; 33] {
+    if p.is_zero() { [0u8; 33] } else { p.to_compressed_bytes() }
+  }
+
+  pub fn to_bytes(&self) -> [u8; 66] {
+    [Self::point_to_bytes(&self.R1), Self::point_to_bytes(&self.R2)].concat().try_into().unwrap()
+  }
+}
+
+pub struct Session {
+  pub Q: AffinePoint,
+  pub R: AffinePoint,
+  pub b: PrimeFieldElem,
+  pub e: PrimeFieldElem,
+  key_agg_ctx: KeyAggContext,
+}
+
+impl Session {
+  pub fn new(key_agg_ctx: &KeyAggContext, agg_nonce: &AggNonce, msg: &[u8]) -> Self {
+    let Q = key_agg_ctx.Q.clone();
+    let Q_bytes = Q.to_x_only_bytes();
+
+    // b = hash_noncecoef(aggnonce || xbytes(Q) || m) mod n
+    let b = Schnorr::tagged_hash_to_scalar(
+      "MuSig/noncecoef",
+      &[&agg_nonce.to_bytes()[..], &Q_bytes, msg].concat(),
+    );
+
+    // R = R1 + b * R2, or G if that is point at infinity
+    let R = &agg_nonce.R1 + &agg_nonce.R2 * &b;
+    let R = if R.is_zero() { AffinePoint::g() } else { R };
+
+    // e = hash_challenge(xbytes(R) || xbytes(Q) || m) mod n
+    let e = Schnorr::tagged_hash_to_scalar(
+      "BIP0340/challenge",
+      &[&R.to_x_only_bytes()[..], &Q_bytes, msg].concat(),
+    );
+
+    Session { Q, R, b, e, key_agg_ctx: key_agg_ctx.clone() }
+  }
+
+  // a_i * g where g negates the key if Q has odd y
+  fn signer_coeff(&self, pub_key: &[u8; 33]) -> PrimeFieldElem {
+    let a = self.key_agg_ctx.coeff(pub_key);
+    if self.Q.has_even_y() { a } else { a.negate() }
+  }
+}
+
+pub struct MuSig2();
+
+impl MuSig2 {
+  pub fn key_agg(pub_keys: &[[u8; 33]]) -> Result<KeyAggContext, String> {
+    KeyAggContext::new(pub_keys)
+  }
+
+  fn nonce_hash(
+    rand: &[u8; 32],
+    pub_key: &[u8; 33],
+    agg_pub_key: &[u8; 32],
+    msg: &[u8],
+    i: u8,
+  ) -> PrimeFieldElem {
+    let msg_prefixed = [&[1u8][..], &(msg.len() as u64).to_be_bytes(), msg].concat();
+    let buf = [
+      &rand[..],
+      &[pub_key.len() as u8], pub_key,
+      &[agg_pub_key.len() as u8], agg_pub_key,
+      &msg_prefixed,
+      &0u32.to_be_bytes(),  // empty extra input
+      &[i],
+    ].concat();
+    Schnorr::tagged_hash_to_scalar("MuSig/nonce", &buf)
+  }
+
+  pub fn nonce_gen(
+    priv_key: &PrimeFieldElem,
+    pub_key: &[u8; 33],
+    agg_pub_key: &[u8; 32],
+    msg: &[u8],
+  ) -> Result<(SecNonce, PubNonce), String> {
+    Self::nonce_gen_with_rng(priv_key, pub_key, agg_pub_key, msg, &mut RandomNumber::new().gen)
+  }
+
+  // rng needs to be a CSPRNG. reusing the random bytes for another signing session leaks the private key
+  pub fn nonce_gen_with_rng(
+    priv_key: &PrimeFieldElem,
+    pub_key: &[u8; 33],
+    agg_pub_key: &[u8; 32],
+    msg: &[u8],
+    rng: &mut (impl RngCore + CryptoRng),
+  ) -> Result<(SecNonce, PubNonce), String> {
+    let mut rand = [0u8; 32];
+    rng.fill_bytes(&mut rand);
+
+    // rand = sk XOR hash_aux(rand')
+    let sk_bytes = AffinePoint::write_biguint_to_32_byte_buf(&priv_key.e);
+    let aux_hash = Schnorr::tagged_hash("MuSig/aux", &rand);
+    for i in 0..32 {
+      rand[i] = sk_bytes[i] ^ aux_hash[i];
+    }
+
+    let k1 = Self::nonce_hash(&rand, pub_key, agg_pub_key, msg, 0);
+    let k2 = Self::nonce_hash(&rand, pub_key, agg_pub_key, msg, 1);
+    if k1.is_zero() || k2.is_zero() {
+      return Err("Derived nonce is zero".to_string());
+    }
+
+    let g = &AffinePoint::g();
+    let pub_nonce = PubNonce { R1: g * &k1, R2: g * &k2 };
+    let sec_nonce = SecNonce { k1, k2, pub_key: *pub_key };
+    Ok((sec_nonce, pub_nonce))
+  }
+
+  pub fn nonce_agg(pub_nonces: &[PubNonce]) -> AggNonce {
+    let mut R1 = AffinePoint::zero();
+    let mut R2 = AffinePoint::zero();
+    for pub_nonce in pub_nonces {
+      R1 = R1 + &pub_nonce.R1;
+      R2 = R2 + &pub_nonce.R2;
+    }
+    AggNonce { R1, R2 }
+  }
+
+  // consumes sec_nonce so that the same nonce cannot be used twice
+  pub fn partial_sign(
+    sec_nonce: SecNonce,
+    priv_key: &PrimeFieldElem,
+    session: &Session,
+  ) -> Result<PrimeFieldElem, String> {
+    let pub_key = (AffinePoint::g() * priv_key).to_compressed_bytes();
+    if pub_key != sec_nonce.pub_key {
+      return Err("Secret nonce was generated for a different public key".to_string());
+    }
+    if !session.key_agg_ctx.pub_keys.contains(&pub_key) {
+      return Err("Public key is not a member of the key aggregation".to_string());
+    }
+
+    let (k1, k2) = if session.R.has_even_y() {
+      (sec_nonce.k1, sec_nonce.k2)
+    } else {
+      (sec_nonce.k1.negate(), sec_nonce.k2.negate())
+    };
+
+    // s = k1 + b * k2 + e * a * g * d
+    let d = session.signer_coeff(&pub_key) * priv_key;
+    let s = k1 + &session.b * k2 + &session.e * d;
+    Ok(s)
+  }
+
+  pub fn partial_sig_verify(
+    psig: &PrimeFieldElem,
+    pub_nonce: &PubNonce,
+    pub_key: &[u8; 33],
+    session: &Session,
+  ) -> bool {
+    let P = match AffinePoint::from_compressed_bytes(pub_key) {
+      Ok(p) => p,
+      Err(_) => return false,
+    };
+
+    // R_i = R1_i + b * R2_i, negated if R has odd y
+    let Re = &pub_nonce.R1 + &pub_nonce.R2 * &session.b;
+    let Re = if session.R.has_even_y() { Re } else { -Re };
+
+    // s_i * G == R_i + e * a_i * g * P_i
+    let lhs = AffinePoint::g() * psig;
+    let rhs = Re + P * (&session.e * session.signer_coeff(pub_key));
+    lhs == rhs
+  }
+
+  pub fn partial_sig_agg(psigs: &[PrimeFieldElem], session: &Session) -> [u8; 64] {
+    let f_n = AffinePoint::curve_group();
+    let s = psigs.iter().fold(f_n.elem(&0u8), |acc, x| acc + x);
+    let s_bytes = AffinePoint::write_biguint_to_32_byte_buf(&s.e);
+    [session.R.to_x_only_bytes(), s_bytes].concat().try_into().unwrap()
+  }
+}
+
+// signing party that holds at most one outstanding secret nonce and
+// discards it as soon as it's used for signing
+pub struct Signer {
+  priv_key: PrimeFieldElem,
+  pub_key: [u8; 33],
+  sec_nonce: Option<SecNonce>,
+}
+
+impl Signer {
+  pub fn new(priv_key: &PrimeFieldElem) -> Self {
+    let pub_key = (AffinePoint::g() * priv_key).to_compressed_bytes();
+    Signer {
+      priv_key: priv_key.clone(),
+      pub_key,
+      sec_nonce: None,
+    }
+  }
+
+  pub fn pub_key(&self) -> [u8; 33] {
+    self.pub_key
+  }
+
+  // round 1
+  pub fn gen_nonce(&mut self, key_agg_ctx: &KeyAggContext, msg: &[u8]) -> Result<PubNonce, String> {
+    if self.sec_nonce.is_some() {
+      return Err("Nonce has already been generated for an ongoing session".to_string());
+    }
+    let (sec_nonce, pub_nonce) = MuSig2::nonce_gen(
+      &self.priv_key,
+      &self.pub_key,
+      &key_agg_ctx.agg_pub_key(),
+      msg,
+    )?;
+    self.sec_nonce = Some(sec_nonce);
+    Ok(pub_nonce)
+  }
+
+  // round 2
+  pub fn partial_sign(&mut self, session: &Session) -> Result<PrimeFieldElem, String> {
+    match self.sec_nonce.take() {
+      None => Err("No unused nonce is available".to_string()),
+      Some(sec_nonce) => MuSig2::partial_sign(sec_nonce, &self.priv_key, session),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn decode_pub_key(s: &str) -> [u8; 33] {
+    hex::decode(s).unwrap().try_into().unwrap()
+  }
+
+  #[test]
+  fn key_agg_bip327_test_vectors() {
+    let X1 = decode_pub_key("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9");
+    let X2 = decode_pub_key("03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659");
+    let X3 = decode_pub_key("023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66");
+
+    let test_cases = [
+      (vec![X1, X2, X3], "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C"),
+      (vec![X3, X2, X1], "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B"),
+      (vec![X1, X1, X1], "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935"),
+      (vec![X1, X1, X2, X2], "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E"),
+    ];
+    for (pub_keys, exp) in test_cases {
+      let ctx = MuSig2::key_agg(&pub_keys).unwrap();
+      assert_eq!(ctx.agg_pub_key().to_vec(), hex::decode(exp).unwrap());
+    }
+  }
+
+  #[test]
+  fn key_agg_invalid_pub_key() {
+    let X1 = decode_pub_key("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9");
+    // x-coordinate not on the curve
+    let bad = decode_pub_key("020000000000000000000000000000000000000000000000000000000000000005");
+    assert!(MuSig2::key_agg(&[X1, bad]).is_err());
+    assert!(MuSig2::key_agg(&[]).is_err());
+  }
+
+  #[test]
+  fn sign_and_aggregate() {
+    let f_n = AffinePoint::curve_group();
+    let msg = b"musig2 message";
+
+    let mut signers: Vec<Signer> = [1234u32, 5678, 9012].iter()
+      .map(|x| Signer::new(&f_n.elem(x)))
+      .collect();
+    let pub_keys: Vec<[u8; 33]> = signers.iter().map(|s| s.pub_key()).collect();
+    let ctx = MuSig2::key_agg(&pub_keys).unwrap();
+
+    // round 1
+    let pub_nonces: Vec<PubNonce> = signers.iter_mut()
+      .map(|s| s.gen_nonce(&ctx, msg).unwrap())
+      .collect();
+    let agg_nonce = MuSig2::nonce_agg(&pub_nonces);
+    let session = Session::new(&ctx, &agg_nonce, msg);
+
+    // round 2
+    let psigs: Vec<PrimeFieldElem> = signers.iter_mut()
+      .map(|s| s.partial_sign(&session).unwrap())
+      .collect();
+
+    for i in 0..psigs.len() {
+      assert!(MuSig2::partial_sig_verify(&psigs[i], &pub_nonces[i], &pub_keys[i], &session));
+    }
+    // partial signature doesn't verify against other signer's nonce and public key
+    assert!(!MuSig2::partial_sig_verify(&psigs[0], &pub_nonces[1], &pub_keys[1], &session));
+
+    let sig = MuSig2::partial_sig_agg(&psigs, &session);
+    let schnorr = Schnorr::new();
+    assert!(schnorr.verify(&sig, &ctx.agg_pub_key(), msg));
+    assert!(!schnorr.verify(&sig, &ctx.agg_pub_key(), b"other message"));
+  }
+
+  #[test]
+  fn nonce_reuse_prevention() {
+    let f_n = AffinePoint::curve_group();
+    let msg = b"musig2 message";
+
+    let mut signer1 = Signer::new(&f_n.elem(&11u8));
+    let mut signer2 = Signer::new(&f_n.elem(&22u8));
+    let ctx = MuSig2::key_agg(&[signer1.pub_key(), signer2.pub_key()]).unwrap();
+
+    // cannot sign before generating a nonce
+    let dummy_session = Session::new(&ctx, &AggNonce { R1: AffinePoint::g(), R2: AffinePoint::g() }, msg);
+    assert!(signer1.partial_sign(&dummy_session).is_err());
+
+    let pub_nonce1 = signer1.gen_nonce(&ctx, msg).unwrap();
+    // cannot generate another nonce while one is outstanding
+    assert!(signer1.gen_nonce(&ctx, msg).is_err());
+    let pub_nonce2 = signer2.gen_nonce(&ctx, msg).unwrap();
+
+    let agg_nonce = MuSig2::nonce_agg(&[pub_nonce1, pub_nonce2]);
+    let session = Session::new(&ctx, &agg_nonce, msg);
+
+    assert!(signer1.partial_sign(&session).is_ok());
+    // nonce has been consumed by the first signing
+    assert!(signer1.partial_sign(&session).is_err());
+
+    // a fresh nonce can be generated for the next session
+    assert!(signer1.gen_nonce(&ctx, msg).is_ok());
+  }
+
+  #[test]
+  fn pub_nonce_bytes_round_trip() {
+    let f_n = AffinePoint::curve_group();
+    let mut signer = Signer::new(&f_n.elem(&11u8));
+    let ctx = MuSig2::key_agg(&[signer.pub_key()]).unwrap();
+    let pub_nonce = signer.gen_nonce(&ctx, b"msg").unwrap();
+    assert_eq!(PubNonce::from_bytes(&pub_nonce.to_bytes()).unwrap(), pub_nonce);
+  }
+}
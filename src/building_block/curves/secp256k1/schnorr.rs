@@ -0,0 +1,200 @@
+#![allow(non_snake_case)]
+use crate::building_block::{
+  curves::secp256k1::affine_point::AffinePoint,
+  field::prime_field_elem::PrimeFieldElem,
+  hasher::{
+    hasher::Hasher,
+    sha256::Sha256,
+  },
+};
+use num_bigint::BigUint;
+
+// implementation based on: https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+
+pub struct Schnorr();
+
+impl Schnorr {
+  pub fn new() -> Self {
+    Schnorr()
+  }
+
+  // SHA256(SHA256(tag) || SHA256(tag) || msg)
+  pub fn tagged_hash(tag: &str, msg: &[u8]) -> [u8; 32] {
+    let hasher = Sha256();
+    let tag_hash = hasher.get_digest(tag.as_bytes());
+    hasher.get_digest(&[&tag_hash[..], &tag_hash[..], msg].concat())
+  }
+
+  // interprets the tagged hash as a big-endian integer modulo n
+  pub fn tagged_hash_to_scalar(tag: &str, msg: &[u8]) -> PrimeFieldElem {
+    let digest = Self::tagged_hash(tag, msg);
+    AffinePoint::curve_group().elem(&BigUint::from_bytes_be(&digest))
+  }
+
+  // x-only public key
+  pub fn gen_pub_key(&self, priv_key: &PrimeFieldElem) -> [u8; 32] {
+    (AffinePoint::g() * priv_key).to_x_only_bytes()
+  }
+
+  pub fn sign(
+    &self,
+    priv_key: &PrimeFieldElem,
+    msg: &[u8],
+    aux_rand: &[u8; 32],
+  ) -> Result<[u8; 64], String> {
+    let f_n = &AffinePoint::curve_group();
+    let g = &AffinePoint::g();
+
+    if priv_key.f.order_ref() != f_n.order_ref() {
+      return Err("Private key needs to be an element of curve group".to_string());
+    }
+    if priv_key.is_zero() {
+      return Err("Private key needs to be in [1, n-1]".to_string());
+    }
+
+    // negate the private key if P doesn't have even y so that P matches the x-only public key
+    let P = g * priv_key;
+    let d = if P.has_even_y() { priv_key.clone() } else { priv_key.negate() };
+    let P_bytes = P.to_x_only_bytes();
+
+    // t = bytes(d) XOR hash_aux(a)
+    let d_bytes = AffinePoint::write_biguint_to_32_byte_buf(&d.e);
+    let aux_hash = Self::tagged_hash("BIP0340/aux", aux_rand);
+    let t: Vec<u8> = d_bytes.iter().zip(aux_hash.iter()).map(|(a, b)| a ^ b).collect();
+
+    // k' = hash_nonce(t || bytes(P) || m) mod n
+    let k = Self::tagged_hash_to_scalar("BIP0340/nonce", &[&t[..], &P_bytes, msg].concat());
+    if k.is_zero() {
+      return Err("Derived nonce is zero".to_string());
+    }
+
+    let R = g * &k;
+    let k = if R.has_even_y() { k } else { k.negate() };
+    let R_bytes = R.to_x_only_bytes();
+
+    // e = hash_challenge(bytes(R) || bytes(P) || m) mod n
+    let e = Self::tagged_hash_to_scalar("BIP0340/challenge", &[&R_bytes, &P_bytes, msg].concat());
+
+    let s = k + e * d;
+    let s_bytes = AffinePoint::write_biguint_to_32_byte_buf(&s.e);
+
+    let sig: [u8; 64] = [R_bytes, s_bytes].concat().try_into().unwrap();
+
+    if !self.verify(&sig, &P_bytes, msg) {
+      return Err("Created signature does not pass verification".to_string());
+    }
+    Ok(sig)
+  }
+
+  pub fn verify(&self, sig: &[u8; 64], pub_key: &[u8; 32], msg: &[u8]) -> bool {
+    let f_n = &AffinePoint::curve_group();
+    let g = &AffinePoint::g();
+
+    let P = match AffinePoint::lift_x(&BigUint::from_bytes_be(pub_key)) {
+      Ok(p) => p,
+      Err(_) => return false,
+    };
+
+    let r = BigUint::from_bytes_be(&sig[0..32]);
+    if &r >= AffinePoint::base_field().order_ref() {
+      return false;
+    }
+    let s = BigUint::from_bytes_be(&sig[32..64]);
+    if &s >= f_n.order_ref() {
+      return false;
+    }
+
+    // e = hash_challenge(bytes(r) || bytes(P) || m) mod n
+    let e = Self::tagged_hash_to_scalar("BIP0340/challenge", &[&sig[0..32], pub_key, msg].concat());
+
+    // R = sG - eP
+    let R = g * f_n.elem(&s) + -(P * e);
+
+    match &R {
+      AffinePoint::AtInfinity => false,
+      AffinePoint::Rational { x, y: _ } => R.has_even_y() && x.e == r,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn decode<const N: usize>(s: &str) -> [u8; N] {
+    hex::decode(s).unwrap().try_into().unwrap()
+  }
+
+  fn run_bip340_test(priv_key: &str, exp_pub_key: &str, aux_rand: &str, msg: &str, exp_sig: &str) {
+    let schnorr = Schnorr::new();
+    let f_n = AffinePoint::curve_group();
+
+    let priv_key = f_n.elem(&BigUint::parse_bytes(priv_key.as_bytes(), 16).unwrap());
+    let pub_key = schnorr.gen_pub_key(&priv_key);
+    assert_eq!(pub_key, decode::<32>(exp_pub_key));
+
+    let msg = hex::decode(msg).unwrap();
+    let sig = schnorr.sign(&priv_key, &msg, &decode::<32>(aux_rand)).unwrap();
+    assert_eq!(sig, decode::<64>(exp_sig));
+    assert!(schnorr.verify(&sig, &pub_key, &msg));
+  }
+
+  #[test]
+  fn bip340_test_vector_0() {
+    run_bip340_test(
+      "0000000000000000000000000000000000000000000000000000000000000003",
+      "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+      "0000000000000000000000000000000000000000000000000000000000000000",
+      "0000000000000000000000000000000000000000000000000000000000000000",
+      "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+    );
+  }
+
+  #[test]
+  fn bip340_test_vector_1() {
+    run_bip340_test(
+      "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+      "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+      "0000000000000000000000000000000000000000000000000000000000000001",
+      "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+      "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+    );
+  }
+
+  #[test]
+  fn verify_pub_key_not_on_curve() {
+    let schnorr = Schnorr::new();
+    let pub_key = decode::<32>("EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34");
+    let msg = hex::decode("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89").unwrap();
+    let sig = decode::<64>("6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B");
+    assert!(!schnorr.verify(&sig, &pub_key, &msg));
+  }
+
+  #[test]
+  fn verify_tampered_sig() {
+    let schnorr = Schnorr::new();
+    let pub_key = decode::<32>("DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659");
+    let msg = hex::decode("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89").unwrap();
+    let sig = decode::<64>("6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A");
+    assert!(schnorr.verify(&sig, &pub_key, &msg));
+
+    // flip a bit of s
+    let mut bad_sig = sig.clone();
+    bad_sig[63] ^= 1;
+    assert!(!schnorr.verify(&bad_sig, &pub_key, &msg));
+
+    // different message
+    let mut bad_msg = msg.clone();
+    bad_msg[0] ^= 1;
+    assert!(!schnorr.verify(&sig, &pub_key, &bad_msg));
+  }
+
+  #[test]
+  fn verify_s_out_of_range() {
+    let schnorr = Schnorr::new();
+    let pub_key = decode::<32>("DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659");
+    let msg = hex::decode("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89").unwrap();
+    let sig = decode::<64>("6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141");
+    assert!(!schnorr.verify(&sig, &pub_key, &msg));
+  }
+}
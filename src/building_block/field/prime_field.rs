@@ -9,7 +9,7 @@ use crate::building_block::{
 };
 use num_bigint::{BigUint, BigInt, Sign};
 use num_traits::Zero as NumTraitsZero;
-use rand::RngCore;
+use rand::{CryptoRng, RngCore};
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Hash)]
@@ -71,12 +71,15 @@ impl PrimeField {
 
   // returns FieldElem in range [1, field_order-1]
   pub fn rand_elem(&self, exclude_zero: bool) -> PrimeFieldElem {
+    self.rand_elem_with_rng(exclude_zero, &mut RandomNumber::new().gen)
+  }
+
+  pub fn rand_elem_with_rng(&self, exclude_zero: bool, rng: &mut (impl RngCore + CryptoRng)) -> PrimeFieldElem {
     let buf_size = (self.order.bits() as f64 / 8f64).ceil() as usize;
     let mut buf = vec![0u8; buf_size];
     let f = Arc::new(self.clone());
     loop {
-      let mut rand = RandomNumber::new();
-      rand.gen.fill_bytes(&mut buf);
+      rng.fill_bytes(&mut buf);
       let x = PrimeFieldElem::new(&f, &BigUint::from_bytes_be(&buf));
       if !exclude_zero || x.e != BigUint::zero() {
         return x;
@@ -85,7 +88,11 @@ impl PrimeField {
   }
 
   pub fn rand_elems(&self, n: &usize, exclude_zero: bool) -> PrimeFieldElems {
-    let xs = (0..*n).map(|_| self.rand_elem(exclude_zero)).collect::<Vec<PrimeFieldElem>>();
+    self.rand_elems_with_rng(n, exclude_zero, &mut RandomNumber::new().gen)
+  }
+
+  pub fn rand_elems_with_rng(&self, n: &usize, exclude_zero: bool, rng: &mut (impl RngCore + CryptoRng)) -> PrimeFieldElems {
+    let xs = (0..*n).map(|_| self.rand_elem_with_rng(exclude_zero, rng)).collect::<Vec<PrimeFieldElem>>();
     PrimeFieldElems(xs)
   }
 
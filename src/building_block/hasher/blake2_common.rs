@@ -0,0 +1,202 @@
+// implementation based on: https://datatracker.ietf.org/doc/html/rfc7693
+
+// message word schedule. BLAKE2b uses the first 2 rows again in rounds 10 and 11
+pub const SIGMA: [[usize; 16]; 10] = [
+  [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+  [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+  [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+  [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+  [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+  [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+  [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+  [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+  [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+  [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+];
+
+// implements a BLAKE2 variant w/ N-byte digest, its streaming context and Hasher.
+// the variants differ only in the word size, the number of rounds, the rotation
+// amounts of G and the sizes of the parameters
+#[macro_export]
+macro_rules! impl_blake2 {
+  (
+    $hasher: ident, $context: ident, $word: ty, $num_rounds: expr, $block_size: expr,
+    $max_len: expr, $param_len: expr, $iv: expr,
+    $r1: expr, $r2: expr, $r3: expr, $r4: expr
+  ) => {
+    // max_len is the max length of both the digest and the key
+    #[derive(Clone)]
+    pub struct $hasher<const N: usize> {
+      key: Vec<u8>,
+      salt: [u8; $param_len],
+      personal: [u8; $param_len],
+    }
+
+    impl<const N: usize> $hasher<N> {
+      // panics if N is not in [1, max_len]
+      pub fn new() -> Self {
+        Self::with_params(&[], &[], &[]).unwrap()
+      }
+
+      pub fn with_key(key: &[u8]) -> Result<Self, String> {
+        Self::with_params(key, &[], &[])
+      }
+
+      // salt and personal shorter than the parameter size are padded w/ zeros
+      pub fn with_params(key: &[u8], salt: &[u8], personal: &[u8]) -> Result<Self, String> {
+        if N == 0 || N > $max_len {
+          return Err(format!("Digest size needs to be in [1, {}], but got {}", $max_len, N));
+        }
+        if key.len() > $max_len {
+          return Err(format!("Key can be at most {} bytes, but got {}", $max_len, key.len()));
+        }
+        if salt.len() > $param_len || personal.len() > $param_len {
+          return Err(format!("Salt and personalization can be at most {} bytes", $param_len));
+        }
+        let mut hasher = $hasher {
+          key: key.to_vec(),
+          salt: [0u8; $param_len],
+          personal: [0u8; $param_len],
+        };
+        hasher.salt[..salt.len()].copy_from_slice(salt);
+        hasher.personal[..personal.len()].copy_from_slice(personal);
+        Ok(hasher)
+      }
+
+      fn read_word(buf: &[u8], i: usize) -> $word {
+        let word_size = std::mem::size_of::<$word>();
+        <$word>::from_le_bytes(buf[i * word_size..(i + 1) * word_size].try_into().unwrap())
+      }
+
+      // IV xor'ed w/ the parameter block
+      fn get_initial_hash_value(&self) -> [$word; 8] {
+        let mut h = $iv;
+        h[0] ^= 0x01010000 ^ ((self.key.len() as $word) << 8) ^ (N as $word);
+        for i in 0..2 {
+          h[4 + i] ^= Self::read_word(&self.salt, i);
+          h[6 + i] ^= Self::read_word(&self.personal, i);
+        }
+        h
+      }
+
+      fn g(v: &mut [$word; 16], a: usize, b: usize, c: usize, d: usize, x: $word, y: $word) {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right($r1);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right($r2);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right($r3);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right($r4);
+      }
+
+      // t is the number of bytes fed so far including the block
+      fn compress(h: &mut [$word; 8], block: &[u8], t: u128, is_last_block: bool) {
+        let word_bits = 8 * std::mem::size_of::<$word>();
+        let m: Vec<$word> = (0..16).map(|i| Self::read_word(block, i)).collect();
+
+        let mut v = [0 as $word; 16];
+        v[..8].copy_from_slice(h);
+        v[8..].copy_from_slice(&$iv);
+        v[12] ^= t as $word;
+        v[13] ^= (t >> word_bits) as $word;
+        if is_last_block {
+          v[14] = !v[14];
+        }
+
+        for i in 0..$num_rounds {
+          let s = &SIGMA[i % 10];
+          Self::g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+          Self::g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+          Self::g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+          Self::g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+          Self::g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+          Self::g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+          Self::g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+          Self::g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+
+        for i in 0..8 {
+          h[i] ^= v[i] ^ v[i + 8];
+        }
+      }
+    }
+
+    impl<const N: usize> Hasher<N> for $hasher<N> {
+      fn get_digest(&self, msg: &[u8]) -> [u8; N] {
+        let mut ctx = $context::new(self);
+        ctx.update(msg);
+        ctx.finalize()
+      }
+
+      fn get_block_size(&self) -> usize {
+        $block_size
+      }
+
+      fn new_context(&self) -> Box<dyn HashContext<N>> {
+        Box::new($context::new(self))
+      }
+    }
+
+    // the last block needs to be compressed w/ the finalization flag, so a full
+    // block is kept in buf until the next part of the message arrives
+    #[derive(Clone)]
+    pub struct $context<const N: usize> {
+      hasher: $hasher<N>,
+      h: [$word; 8],
+      t: u128,
+      buf: Vec<u8>,
+    }
+
+    impl<const N: usize> $context<N> {
+      pub fn new(hasher: &$hasher<N>) -> Self {
+        let mut ctx = $context {
+          hasher: hasher.clone(),
+          h: [0; 8],
+          t: 0,
+          buf: Vec::with_capacity($block_size),
+        };
+        ctx.reset();
+        ctx
+      }
+
+      // in keyed mode, the key padded to a block is prepended to the message
+      fn reset(&mut self) {
+        self.h = self.hasher.get_initial_hash_value();
+        self.t = 0;
+        self.buf.clear();
+        if !self.hasher.key.is_empty() {
+          self.buf.extend_from_slice(&self.hasher.key);
+          self.buf.resize($block_size, 0);
+        }
+      }
+    }
+
+    impl<const N: usize> HashContext<N> for $context<N> {
+      fn update(&mut self, msg: &[u8]) {
+        let mut msg = msg;
+        while !msg.is_empty() {
+          if self.buf.len() == $block_size {
+            self.t += $block_size as u128;
+            $hasher::<N>::compress(&mut self.h, &self.buf, self.t, false);
+            self.buf.clear();
+          }
+          let n = ($block_size - self.buf.len()).min(msg.len());
+          self.buf.extend_from_slice(&msg[..n]);
+          msg = &msg[n..];
+        }
+      }
+
+      fn finalize(&mut self) -> [u8; N] {
+        self.t += self.buf.len() as u128;
+        self.buf.resize($block_size, 0);
+        $hasher::<N>::compress(&mut self.h, &self.buf, self.t, true);
+
+        let hash_value: Vec<u8> = self.h.iter().flat_map(|x| x.to_le_bytes()).collect();
+        let digest = hash_value[..N].try_into().unwrap();
+        self.reset();
+        digest
+      }
+    }
+  };
+}
@@ -0,0 +1,124 @@
+use crate::impl_blake2;
+use super::blake2_common::SIGMA;
+use super::hasher::{Hasher, HashContext};
+
+// implementation based on: https://datatracker.ietf.org/doc/html/rfc7693
+// BLAKE2b w/ N-byte digest, where N is in [1, 64]
+
+const BLOCK_SIZE: usize = 128;
+
+const IV: [u64; 8] = [
+  0x6a09e667f3bcc908, 0xbb67ae8584caa73b, 0x3c6ef372fe94f82b, 0xa54ff53a5f1d36f1,
+  0x510e527fade682d1, 0x9b05688c2b3e6c1f, 0x1f83d9abfb41bd6b, 0x5be0cd19137e2179,
+];
+
+impl_blake2!(Blake2b, Blake2bContext, u64, 12, BLOCK_SIZE, 64, 16, IV, 32, 24, 16, 63);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::hmac::Hmac;
+  use hex::ToHex;
+
+  // deterministic byte sequence of RFC 7693 Appendix E
+  fn selftest_seq(len: usize, seed: u32) -> Vec<u8> {
+    let mut a = 0xdead4bad_u32.wrapping_mul(seed);
+    let mut b = 1u32;
+    (0..len).map(|_| {
+      let t = a.wrapping_add(b);
+      a = b;
+      b = t;
+      (t >> 24) as u8
+    }).collect()
+  }
+
+  // feeds unkeyed and keyed digests of the test messages to ctx
+  fn feed_selftest_digests<const N: usize>(ctx: &mut Box<dyn HashContext<32>>, in_lens: &[usize]) {
+    for in_len in in_lens {
+      let msg = selftest_seq(*in_len, *in_len as u32);
+      ctx.update(&Blake2b::<N>::new().get_digest(&msg));
+
+      let key = selftest_seq(N, N as u32);
+      ctx.update(&Blake2b::<N>::with_key(&key).unwrap().get_digest(&msg));
+    }
+  }
+
+  #[test]
+  fn rfc7693_selftest() {
+    let in_lens = [0, 3, 128, 129, 255, 1024];
+    let mut ctx = Blake2b::<32>::new().new_context();
+    feed_selftest_digests::<20>(&mut ctx, &in_lens);
+    feed_selftest_digests::<32>(&mut ctx, &in_lens);
+    feed_selftest_digests::<48>(&mut ctx, &in_lens);
+    feed_selftest_digests::<64>(&mut ctx, &in_lens);
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "c23a7800d98123bd10f506c61e29da5603d763b8bbad2e737f5e765a7bccd475");
+  }
+
+  #[test]
+  fn hash_abc() {
+    // RFC 7693 Appendix A
+    let digest = Blake2b::<64>::new().get_digest(b"abc");
+    assert_eq!(digest.encode_hex::<String>(), "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923");
+  }
+
+  #[test]
+  fn hash_empty() {
+    let digest = Blake2b::<64>::new().get_digest(b"");
+    assert_eq!(digest.encode_hex::<String>(), "786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce");
+  }
+
+  #[test]
+  fn keyed() {
+    let key: Vec<u8> = (0..64u8).collect();
+    let msg: Vec<u8> = (0..255u8).collect();
+    let digest = Blake2b::<64>::with_key(&key).unwrap().get_digest(&msg);
+    assert_eq!(digest.encode_hex::<String>(), "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e92484be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461");
+  }
+
+  #[test]
+  fn salt_and_personalization() {
+    let hasher = Blake2b::<32>::with_params(&[], b"0123456789abcdef", b"ZcashPersonaliz.").unwrap();
+    assert_eq!(hasher.get_digest(b"abc").encode_hex::<String>(), "0c8eec9b03bc8e8f62198022a4ab6244a2c6f5a2009995f73d9cd2ea19f344a8");
+
+    // personalization shorter than 16 bytes is padded w/ zeros
+    let hasher = Blake2b::<32>::with_params(&[], &[], b"ZcashRedJubjubH").unwrap();
+    let padded = Blake2b::<32>::with_params(&[], &[], b"ZcashRedJubjubH\0").unwrap();
+    assert_eq!(hasher.get_digest(b""), padded.get_digest(b""));
+  }
+
+  #[test]
+  fn streaming() {
+    let key: Vec<u8> = (0..64u8).collect();
+    let msg: Vec<u8> = (0..255u8).collect();
+    let hasher = Blake2b::<64>::with_key(&key).unwrap();
+    let mut ctx = hasher.new_context();
+
+    // chunks ending exactly at the block boundary
+    for chunk in msg.chunks(BLOCK_SIZE) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e92484be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461");
+
+    for chunk in msg.chunks(7) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "142709d62e28fcccd0af97fad0f8465b971e82201dc51070faa0372aa43e92484be1c1e73ba10906d5d1853db6a4106e0a7bf9800d373d6dee2d46d62ef2a461");
+  }
+
+  #[test]
+  fn hmac() {
+    let hmac = Hmac::new(Box::new(Blake2b::<64>::new()));
+    let digest = hmac.get_digest(b"Jefe", b"what do ya want for nothing?");
+    assert_eq!(digest.encode_hex::<String>(), "6ff884f8ddc2a6586b3c98a4cd6ebdf14ec10204b6710073eb5865ade37a2643b8807c1335d107ecdb9ffeaeb6828c4625ba172c66379efcd222c2de11727ab4");
+  }
+
+  #[test]
+  fn invalid_params() {
+    assert!(Blake2b::<0>::with_key(&[]).is_err());
+    assert!(Blake2b::<65>::with_key(&[]).is_err());
+    assert!(Blake2b::<32>::with_key(&[0u8; 65]).is_err());
+    assert!(Blake2b::<32>::with_params(&[], &[0u8; 17], &[]).is_err());
+    assert!(Blake2b::<32>::with_params(&[], &[], &[0u8; 17]).is_err());
+    assert!(Blake2b::<32>::with_key(&[0u8; 64]).is_ok());
+  }
+}
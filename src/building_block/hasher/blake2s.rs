@@ -0,0 +1,111 @@
+use crate::impl_blake2;
+use super::blake2_common::SIGMA;
+use super::hasher::{Hasher, HashContext};
+
+// implementation based on: https://datatracker.ietf.org/doc/html/rfc7693
+// BLAKE2s w/ N-byte digest, where N is in [1, 32]
+
+const BLOCK_SIZE: usize = 64;
+
+const IV: [u32; 8] = [
+  0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+  0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+impl_blake2!(Blake2s, Blake2sContext, u32, 10, BLOCK_SIZE, 32, 8, IV, 16, 12, 8, 7);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use hex::ToHex;
+
+  // deterministic byte sequence of RFC 7693 Appendix E
+  fn selftest_seq(len: usize, seed: u32) -> Vec<u8> {
+    let mut a = 0xdead4bad_u32.wrapping_mul(seed);
+    let mut b = 1u32;
+    (0..len).map(|_| {
+      let t = a.wrapping_add(b);
+      a = b;
+      b = t;
+      (t >> 24) as u8
+    }).collect()
+  }
+
+  // feeds unkeyed and keyed digests of the test messages to ctx
+  fn feed_selftest_digests<const N: usize>(ctx: &mut Box<dyn HashContext<32>>, in_lens: &[usize]) {
+    for in_len in in_lens {
+      let msg = selftest_seq(*in_len, *in_len as u32);
+      ctx.update(&Blake2s::<N>::new().get_digest(&msg));
+
+      let key = selftest_seq(N, N as u32);
+      ctx.update(&Blake2s::<N>::with_key(&key).unwrap().get_digest(&msg));
+    }
+  }
+
+  #[test]
+  fn rfc7693_selftest() {
+    let in_lens = [0, 3, 64, 65, 255, 1024];
+    let mut ctx = Blake2s::<32>::new().new_context();
+    feed_selftest_digests::<16>(&mut ctx, &in_lens);
+    feed_selftest_digests::<20>(&mut ctx, &in_lens);
+    feed_selftest_digests::<28>(&mut ctx, &in_lens);
+    feed_selftest_digests::<32>(&mut ctx, &in_lens);
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "6a411f08ce25adcdfb02aba641451cec53c598b24f4fc787fbdc88797f4c1dfe");
+  }
+
+  #[test]
+  fn hash_abc() {
+    // RFC 7693 Appendix B
+    let digest = Blake2s::<32>::new().get_digest(b"abc");
+    assert_eq!(digest.encode_hex::<String>(), "508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982");
+  }
+
+  #[test]
+  fn hash_empty() {
+    let digest = Blake2s::<32>::new().get_digest(b"");
+    assert_eq!(digest.encode_hex::<String>(), "69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9");
+  }
+
+  #[test]
+  fn keyed() {
+    let key: Vec<u8> = (0..32u8).collect();
+    let msg: Vec<u8> = (0..255u8).collect();
+    let digest = Blake2s::<32>::with_key(&key).unwrap().get_digest(&msg);
+    assert_eq!(digest.encode_hex::<String>(), "3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd");
+  }
+
+  #[test]
+  fn salt_and_personalization() {
+    let hasher = Blake2s::<32>::with_params(&[], b"saltsalt", b"Zcash_PH").unwrap();
+    assert_eq!(hasher.get_digest(b"abc").encode_hex::<String>(), "441f512a7c59e2d2ba76bb2e0883b85c6da0f9667a2517eeaa8a1bdafbfbb52b");
+  }
+
+  #[test]
+  fn streaming() {
+    let key: Vec<u8> = (0..32u8).collect();
+    let msg: Vec<u8> = (0..255u8).collect();
+    let hasher = Blake2s::<32>::with_key(&key).unwrap();
+    let mut ctx = hasher.new_context();
+
+    // chunks ending exactly at the block boundary
+    for chunk in msg.chunks(BLOCK_SIZE) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd");
+
+    for chunk in msg.chunks(7) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "3fb735061abc519dfe979e54c1ee5bfad0a9d858b3315bad34bde999efd724dd");
+  }
+
+  #[test]
+  fn invalid_params() {
+    assert!(Blake2s::<0>::with_key(&[]).is_err());
+    assert!(Blake2s::<33>::with_key(&[]).is_err());
+    assert!(Blake2s::<32>::with_key(&[0u8; 33]).is_err());
+    assert!(Blake2s::<32>::with_params(&[], &[0u8; 9], &[]).is_err());
+    assert!(Blake2s::<32>::with_params(&[], &[], &[0u8; 9]).is_err());
+    assert!(Blake2s::<32>::with_key(&[0u8; 32]).is_ok());
+  }
+}
@@ -0,0 +1,128 @@
+use crate::building_block::field::{
+  prime_field::PrimeField,
+  prime_field_elem::PrimeFieldElem,
+};
+use num_bigint::BigUint;
+use std::{
+  collections::VecDeque,
+  sync::Arc,
+};
+
+// implementation based on:
+// - https://eprint.iacr.org/2019/458.pdf (Appendix F)
+// - https://extgit.iaik.tugraz.at/krypto/hadeshash/-/blob/master/code/generate_parameters_grain.sage
+
+const STATE_SIZE: usize = 80;
+const NUM_DISCARDED_BITS: usize = 160;
+
+// 80-bit Grain LFSR used in the self-shrinking mode to derive
+// round constants and MDS matrices of arithmetization-oriented hashers
+pub struct GrainLfsr {
+  state: VecDeque<bool>,
+}
+
+impl GrainLfsr {
+  // state is initialized w/ the big-endian bits of the parameters followed by 30 ones
+  pub fn new(
+    field_type: u8,  // 0 for GF(2^n) and 1 for GF(p)
+    sbox_type: u8,  // 0 for x^alpha and 1 for x^-1
+    field_size: usize,
+    t: usize,
+    full_rounds: usize,
+    partial_rounds: usize,
+  ) -> Self {
+    let mut state = VecDeque::with_capacity(STATE_SIZE);
+    let fields = [
+      (field_type as usize, 2),
+      (sbox_type as usize, 4),
+      (field_size, 12),
+      (t, 12),
+      (full_rounds, 10),
+      (partial_rounds, 10),
+    ];
+    for (value, num_bits) in fields {
+      for i in (0..num_bits).rev() {
+        state.push_back((value >> i) & 1 == 1);
+      }
+    }
+    state.extend([true; 30]);
+
+    let mut lfsr = GrainLfsr { state };
+    for _ in 0..NUM_DISCARDED_BITS {
+      lfsr.update();
+    }
+    lfsr
+  }
+
+  // b_{i+80} = b_{i+62} + b_{i+51} + b_{i+38} + b_{i+23} + b_{i+13} + b_i
+  fn update(&mut self) -> bool {
+    let s = &self.state;
+    let new_bit = s[62] ^ s[51] ^ s[38] ^ s[23] ^ s[13] ^ s[0];
+    self.state.pop_front();
+    self.state.push_back(new_bit);
+    new_bit
+  }
+
+  // bits are generated in pairs. if the first bit is 1, the second bit is
+  // output. otherwise the pair is discarded
+  pub fn next_bit(&mut self) -> bool {
+    loop {
+      let b1 = self.update();
+      let b2 = self.update();
+      if b1 {
+        return b2;
+      }
+    }
+  }
+
+  // interprets the next num_bits bits as a big-endian integer
+  pub fn next_biguint(&mut self, num_bits: usize) -> BigUint {
+    let mut n = BigUint::from(0u8);
+    for _ in 0..num_bits {
+      n <<= 1;
+      if self.next_bit() {
+        n += 1u8;
+      }
+    }
+    n
+  }
+
+  // rejects integers not less than the order
+  pub fn next_field_elem(&mut self, f: &Arc<PrimeField>) -> PrimeFieldElem {
+    let num_bits = f.order_ref().bits() as usize;
+    loop {
+      let n = self.next_biguint(num_bits);
+      if &n < f.order_ref() {
+        return PrimeFieldElem::new(f, &n);
+      }
+    }
+  }
+
+  // reduces the integer modulo the order instead of rejecting it
+  pub fn next_field_elem_mod(&mut self, f: &Arc<PrimeField>) -> PrimeFieldElem {
+    let num_bits = f.order_ref().bits() as usize;
+    let n = self.next_biguint(num_bits);
+    PrimeFieldElem::new(f, &n)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::building_block::curves::bls12_381::params::Params as P;
+
+  #[test]
+  fn bls12_381_fr_x5_3() {
+    // first and last round constants of the reference poseidonperm_x5_255_3 instance
+    let f = &P::subgroup();
+    let mut lfsr = GrainLfsr::new(1, 0, 255, 3, 8, 57);
+    let first = lfsr.next_field_elem(f);
+    assert_eq!(first.e, BigUint::parse_bytes(b"6c4ffa723eaf1a7bf74905cc7dae4ca9ff4a2c3bc81d42e09540d1f250910880", 16).unwrap());
+
+    for _ in 1..(8 + 57) * 3 - 1 {
+      lfsr.next_field_elem(f);
+    }
+    let last = lfsr.next_field_elem(f);
+    assert_eq!(last.e, BigUint::parse_bytes(b"57b33094aeff828377897b56e1c432978d07c668ef25a36bc5e2e835aaeff725", 16).unwrap());
+  }
+}
@@ -1,4 +1,14 @@
 pub trait Hasher<const N: usize> {
   fn get_digest(&self, msg: &[u8]) -> [u8; N];
   fn get_block_size(&self) -> usize;
-}
\ No newline at end of file
+
+  // creates a context that hashes a message fed in multiple parts
+  fn new_context(&self) -> Box<dyn HashContext<N>>;
+}
+
+// incremental hashing. finalize returns the digest of all the parts fed so far
+// and resets the context to its initial state
+pub trait HashContext<const N: usize> {
+  fn update(&mut self, msg: &[u8]);
+  fn finalize(&mut self) -> [u8; N];
+}
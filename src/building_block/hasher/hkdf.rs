@@ -0,0 +1,135 @@
+use super::{
+  hasher::{Hasher, HashContext},
+  hmac::Hmac,
+};
+
+// implementation based on: https://datatracker.ietf.org/doc/html/rfc5869
+
+pub struct Hkdf<const N: usize> {
+  hmac: Hmac<N>,
+}
+
+impl<const N: usize> Hkdf<N> {
+  pub fn new(hasher: Box<dyn Hasher<N>>) -> Self {
+    Hkdf { hmac: Hmac::new(hasher) }
+  }
+
+  // PRK = HMAC-Hash(salt, IKM). empty salt is treated as N zero bytes
+  pub fn extract(&self, salt: &[u8], ikm: &[u8]) -> [u8; N] {
+    if salt.is_empty() {
+      self.hmac.get_digest(&[0u8; N], ikm)
+    } else {
+      self.hmac.get_digest(salt, ikm)
+    }
+  }
+
+  // OKM = first len bytes of T(1) || T(2) || ... where T(i) = HMAC-Hash(PRK, T(i-1) || info || i).
+  // len needs to be at most 255 * N
+  pub fn expand(&self, prk: &[u8], info: &[u8], len: usize) -> Result<Vec<u8>, String> {
+    if len > 255 * N {
+      return Err(format!("Output length can be at most {}, but got {}", 255 * N, len));
+    }
+    let mut ctx = self.hmac.new_context(prk);
+    let mut okm = Vec::with_capacity(len + N);
+    let mut t: Vec<u8> = vec![];
+    let mut i = 1u8;
+    while okm.len() < len {
+      ctx.update(&t);
+      ctx.update(info);
+      ctx.update(&[i]);
+      t = ctx.finalize().to_vec();
+      okm.extend_from_slice(&t);
+      i = i.wrapping_add(1);
+    }
+    okm.truncate(len);
+    Ok(okm)
+  }
+
+  // extract followed by expand
+  pub fn derive(&self, salt: &[u8], ikm: &[u8], info: &[u8], len: usize) -> Result<Vec<u8>, String> {
+    let prk = self.extract(salt, ikm);
+    self.expand(&prk, info, len)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::{
+    sha256::Sha256,
+    sha512::Sha512,
+  };
+  use hex::ToHex;
+
+  fn test(salt: &[u8], ikm: &[u8], info: &[u8], len: usize, exp_prk: &str, exp_okm: &str) {
+    let hkdf = Hkdf::new(Box::new(Sha256()));
+    let prk = hkdf.extract(salt, ikm);
+    assert_eq!(prk.encode_hex::<String>(), exp_prk);
+
+    let okm = hkdf.expand(&prk, info, len).unwrap();
+    assert_eq!(okm.encode_hex::<String>(), exp_okm);
+
+    let okm = hkdf.derive(salt, ikm, info, len).unwrap();
+    assert_eq!(okm.encode_hex::<String>(), exp_okm);
+  }
+
+  #[test]
+  fn rfc5869_test_case_1() {
+    let salt: Vec<u8> = (0x00..=0x0c).collect();
+    let info: Vec<u8> = (0xf0..=0xf9).collect();
+    test(
+      &salt,
+      &[0x0b; 22],
+      &info,
+      42,
+      "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+      "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+    );
+  }
+
+  #[test]
+  fn rfc5869_test_case_2() {
+    let ikm: Vec<u8> = (0x00..=0x4f).collect();
+    let salt: Vec<u8> = (0x60..=0xaf).collect();
+    let info: Vec<u8> = (0xb0..=0xff).collect();
+    test(
+      &salt,
+      &ikm,
+      &info,
+      82,
+      "06a6b88c5853361a06104c9ceb35b45cef760014904671014a193f40c15fc244",
+      "b11e398dc80327a1c8e7f78c596a49344f012eda2d4efad8a050cc4c19afa97c59045a99cac7827271cb41c65e590e09da3275600c2f09b8367793a9aca3db71cc30c58179ec3e87c14c01d5c1f3434f1d87",
+    );
+  }
+
+  #[test]
+  fn rfc5869_test_case_3() {
+    // zero-length salt and info
+    test(
+      b"",
+      &[0x0b; 22],
+      b"",
+      42,
+      "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+      "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8",
+    );
+  }
+
+  #[test]
+  fn sha512() {
+    let hkdf = Hkdf::new(Box::new(Sha512()));
+    let salt: Vec<u8> = (0x00..=0x0c).collect();
+    let info: Vec<u8> = (0xf0..=0xf9).collect();
+    let okm = hkdf.derive(&salt, &[0x0b; 22], &info, 42).unwrap();
+    assert_eq!(okm.encode_hex::<String>(), "832390086cda71fb47625bb5ceb168e4c8e26a1a16ed34d9fc7fe92c1481579338da362cb8d9f925d7cb");
+  }
+
+  #[test]
+  fn output_length_limit() {
+    let hkdf = Hkdf::new(Box::new(Sha256()));
+    let prk = hkdf.extract(b"salt", b"ikm");
+    assert_eq!(hkdf.expand(&prk, b"", 255 * 32).unwrap().len(), 255 * 32);
+    assert!(hkdf.expand(&prk, b"", 255 * 32 + 1).is_err());
+    assert!(hkdf.expand(&prk, b"", 0).unwrap().is_empty());
+  }
+}
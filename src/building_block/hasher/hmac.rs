@@ -1,4 +1,4 @@
-use super::hasher::Hasher;
+use super::hasher::{Hasher, HashContext};
 
 // based on: https://datatracker.ietf.org/doc/html/rfc2104
 
@@ -12,8 +12,14 @@ impl <const N: usize> Hmac<N> {
   }
 
   pub fn get_digest(&self, key: &[u8], text: &[u8]) -> [u8; N] {
+    let mut ctx = self.new_context(key);
+    ctx.update(text);
+    ctx.finalize()
+  }
+
+  // context to feed text in multiple parts
+  pub fn new_context(&self, key: &[u8]) -> HmacContext<N> {
     let block_size: usize = self.hasher.get_block_size();  // B
-    let output_size: usize = N;  // hasher output size L
 
     // k is of block_size length w/ key expended w/ 0 paddings at the end
     // first hash key if key is longer than block_size
@@ -26,22 +32,41 @@ impl <const N: usize> Hmac<N> {
       k[0..key.len()].copy_from_slice(&key[..]);
     }
 
-    let mut r = vec![0u8; block_size + text.len()];
-    let mut s = vec![0u8; block_size + output_size];
+    let k_ipad: Vec<u8> = k.iter().map(|x| x ^ 0x36).collect();  // k XOR ipad(0x36)
+    let k_opad: Vec<u8> = k.iter().map(|x| x ^ 0x5c).collect();  // k XOR opad(0x5c)
 
-    for i in 0..block_size {
-      r[i] = k[i] ^ 0x36;  // r = k XOR ipad(0x36)
-      s[i] = k[i] ^ 0x5c;  // s = k XOR opad(0x5c)
+    let mut inner = self.hasher.new_context();
+    inner.update(&k_ipad);
+
+    HmacContext {
+      inner,
+      outer: self.hasher.new_context(),
+      k_ipad,
+      k_opad,
     }
+  }
+}
+
+pub struct HmacContext<const N: usize> {
+  inner: Box<dyn HashContext<N>>,
+  outer: Box<dyn HashContext<N>>,
+  k_ipad: Vec<u8>,
+  k_opad: Vec<u8>,
+}
 
-    // digest = H(k XOR opad || H(k XOR ipad || text))
-    r[block_size..].copy_from_slice(&text[..]);
-    let inner_digest = self.hasher.get_digest(&r);
+impl<const N: usize> HashContext<N> for HmacContext<N> {
+  fn update(&mut self, text: &[u8]) {
+    self.inner.update(text);
+  }
 
-    s[block_size..].copy_from_slice(&inner_digest);
-    let digest = self.hasher.get_digest(&s);
+  // digest = H(k XOR opad || H(k XOR ipad || text))
+  fn finalize(&mut self) -> [u8; N] {
+    let inner_digest = self.inner.finalize();
+    self.inner.update(&self.k_ipad);
 
-    digest
+    self.outer.update(&self.k_opad);
+    self.outer.update(&inner_digest);
+    self.outer.finalize()
   }
 }
 
@@ -83,4 +108,21 @@ mod tests {
     let digest = hmac.get_digest(key, text);
     assert_eq!(digest.encode_hex::<String>(), "7767617394b05a76be1959b0720891a152536ef407315e8eeb9209957d07c38e");
   }
+
+  #[test]
+  fn hmac_streaming() {
+    let hmac = Hmac::new(Box::new(Sha256()));
+
+    let key = b"fx502p";
+    let text = b"The identity of the longest word in the English language depends upon the definition of what constitutes a word in the English language, as well as how length should be compared.";
+    let mut ctx = hmac.new_context(key);
+    for chunk in text.chunks(10) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "7767617394b05a76be1959b0720891a152536ef407315e8eeb9209957d07c38e");
+
+    // context is reset to the keyed initial state after finalize
+    ctx.update(b"some text");
+    assert_eq!(ctx.finalize(), hmac.get_digest(key, b"some text"));
+  }
 }
\ No newline at end of file
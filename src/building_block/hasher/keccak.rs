@@ -0,0 +1,247 @@
+use super::hasher::{Hasher, HashContext};
+
+// implementation based on:
+// - https://keccak.team/keccak_specs_summary.html
+// - https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+
+const NUM_ROUNDS: usize = 24;
+const STATE_SIZE: usize = 200;  // 1600 bits
+
+const ROUND_CONSTANTS: [u64; NUM_ROUNDS] = [
+  0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+  0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+  0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+  0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+  0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+  0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+// rotation offsets of lane (x, y) stored at index x + 5y
+const ROTATION_OFFSETS: [u32; 25] = [
+  0, 1, 62, 28, 27,
+  36, 44, 6, 55, 20,
+  3, 10, 43, 25, 39,
+  41, 45, 15, 21, 8,
+  18, 2, 61, 56, 14,
+];
+
+// Keccak-f[1600] permutation. lane (x, y) is stored at index x + 5y
+pub fn keccak_f(a: &mut [u64; 25]) {
+  for rc in ROUND_CONSTANTS {
+    // theta
+    let mut c = [0u64; 5];
+    for x in 0..5 {
+      c[x] = a[x] ^ a[x + 5] ^ a[x + 10] ^ a[x + 15] ^ a[x + 20];
+    }
+    for x in 0..5 {
+      let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+      for y in 0..5 {
+        a[x + 5 * y] ^= d;
+      }
+    }
+
+    // rho and pi
+    let mut b = [0u64; 25];
+    for x in 0..5 {
+      for y in 0..5 {
+        b[y + 5 * ((2 * x + 3 * y) % 5)] = a[x + 5 * y].rotate_left(ROTATION_OFFSETS[x + 5 * y]);
+      }
+    }
+
+    // chi
+    for x in 0..5 {
+      for y in 0..5 {
+        a[x + 5 * y] = b[x + 5 * y] ^ (!b[(x + 1) % 5 + 5 * y] & b[(x + 2) % 5 + 5 * y]);
+      }
+    }
+
+    // iota
+    a[0] ^= rc;
+  }
+}
+
+// sponge construction on Keccak-f[1600] w/ multi-rate padding. domain_sep holds
+// the suffix bits appended to the message followed by the first bit of pad10*1
+#[derive(Clone)]
+pub struct KeccakSponge {
+  state: [u64; 25],
+  rate: usize,
+  domain_sep: u8,
+  pos: usize,  // position in the current rate-sized block
+  is_squeezing: bool,
+}
+
+impl KeccakSponge {
+  pub fn new(rate: usize, domain_sep: u8) -> Self {
+    assert!(rate > 0 && rate < STATE_SIZE && rate % 8 == 0, "Invalid rate {}", rate);
+    KeccakSponge {
+      state: [0u64; 25],
+      rate,
+      domain_sep,
+      pos: 0,
+      is_squeezing: false,
+    }
+  }
+
+  fn xor_byte(&mut self, i: usize, b: u8) {
+    self.state[i / 8] ^= (b as u64) << (8 * (i % 8));
+  }
+
+  fn get_byte(&self, i: usize) -> u8 {
+    (self.state[i / 8] >> (8 * (i % 8))) as u8
+  }
+
+  pub fn absorb(&mut self, msg: &[u8]) {
+    assert!(!self.is_squeezing, "Cannot absorb after squeezing has started");
+    for b in msg {
+      self.xor_byte(self.pos, *b);
+      self.pos += 1;
+      if self.pos == self.rate {
+        keccak_f(&mut self.state);
+        self.pos = 0;
+      }
+    }
+  }
+
+  fn pad(&mut self) {
+    self.xor_byte(self.pos, self.domain_sep);
+    self.xor_byte(self.rate - 1, 0x80);
+    keccak_f(&mut self.state);
+    self.pos = 0;
+    self.is_squeezing = true;
+  }
+
+  // pads the absorbed message on the first call. subsequent calls continue
+  // reading the output stream
+  pub fn squeeze(&mut self, out: &mut [u8]) {
+    if !self.is_squeezing {
+      self.pad();
+    }
+    for x in out.iter_mut() {
+      if self.pos == self.rate {
+        keccak_f(&mut self.state);
+        self.pos = 0;
+      }
+      *x = self.get_byte(self.pos);
+      self.pos += 1;
+    }
+  }
+
+  pub fn reset(&mut self) {
+    self.state = [0u64; 25];
+    self.pos = 0;
+    self.is_squeezing = false;
+  }
+}
+
+// implements Hasher and the streaming context for a fixed-length output
+// Keccak-based hasher. the block size of the hasher is the rate of the sponge
+#[macro_export]
+macro_rules! impl_keccak_hasher {
+  ($hasher: ident, $context: ident, $rate: expr, $domain_sep: expr, $digest_size: expr) => {
+    impl Hasher<$digest_size> for $hasher {
+      fn get_digest(&self, msg: &[u8]) -> [u8; $digest_size] {
+        let mut ctx = $context::new();
+        ctx.update(msg);
+        ctx.finalize()
+      }
+
+      fn get_block_size(&self) -> usize {
+        $rate
+      }
+
+      fn new_context(&self) -> Box<dyn HashContext<$digest_size>> {
+        Box::new($context::new())
+      }
+    }
+
+    #[derive(Clone)]
+    pub struct $context {
+      sponge: $crate::building_block::hasher::keccak::KeccakSponge,
+    }
+
+    impl $context {
+      pub fn new() -> Self {
+        $context {
+          sponge: $crate::building_block::hasher::keccak::KeccakSponge::new($rate, $domain_sep),
+        }
+      }
+    }
+
+    impl HashContext<$digest_size> for $context {
+      fn update(&mut self, msg: &[u8]) {
+        self.sponge.absorb(msg);
+      }
+
+      fn finalize(&mut self) -> [u8; $digest_size] {
+        let mut digest = [0u8; $digest_size];
+        self.sponge.squeeze(&mut digest);
+        self.sponge.reset();
+        digest
+      }
+    }
+  };
+}
+
+// original Keccak padding used by Ethereum, which predates the SHA-3 domain separation
+#[derive(Clone)]
+pub struct Keccak256();
+
+impl_keccak_hasher!(Keccak256, Keccak256Context, 136, 0x01, 32);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use hex::ToHex;
+
+  fn test(msg: &[u8], exp: &str) {
+    let hasher = Keccak256();
+    let digest = hasher.get_digest(msg);
+    assert_eq!(digest.encode_hex::<String>(), exp);
+  }
+
+  #[test]
+  fn keccak_f_zero_state() {
+    // Keccak-f[1600] applied to the all-zero state
+    let mut a = [0u64; 25];
+    keccak_f(&mut a);
+    assert_eq!(a[0], 0xf1258f7940e1dde7);
+    assert_eq!(a[1], 0x84d5ccf933c0478a);
+    assert_eq!(a[24], 0xeaf1ff7b5ceca249);
+  }
+
+  #[test]
+  fn hash_empty() {
+    test(b"", "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+  }
+
+  #[test]
+  fn hash_abc() {
+    test(b"abc", "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45");
+  }
+
+  #[test]
+  fn hash_multi_block() {
+    let msg: Vec<u8> = (0..768u32).map(|x| x as u8).collect();
+    test(&msg, "00e77ce2c4f77212a0d5df106b08157b77058479357a98a6039b457c469723e4");
+  }
+
+  #[test]
+  fn hash_a_times_1mil() {
+    test(&[b'a'; 1_000_000], "fadae6b49f129bbb812be8407b7b2894f34aecf6dbd1f9b0f0c7e9853098fc96");
+  }
+
+  #[test]
+  fn streaming() {
+    let msg: Vec<u8> = (0..768u32).map(|x| x as u8).collect();
+    let mut ctx = Keccak256().new_context();
+    for chunk in msg.chunks(135) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "00e77ce2c4f77212a0d5df106b08157b77058479357a98a6039b457c469723e4");
+
+    // context is reset after finalize
+    ctx.update(b"abc");
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45");
+  }
+}
@@ -0,0 +1,158 @@
+#![allow(non_snake_case)]
+
+use crate::building_block::{
+  curves::bls12_381::params::Params as P,
+  field::{
+    prime_field::PrimeField,
+    prime_field_elem::PrimeFieldElem,
+  },
+  hasher::{
+    hasher::Hasher,
+    sha256::Sha256,
+  },
+};
+use num_bigint::BigUint;
+use std::sync::Arc;
+
+// implementation based on:
+// - https://eprint.iacr.org/2016/492.pdf (MiMC-2n/n)
+// - https://github.com/iden3/circomlib/blob/master/circuits/mimcsponge.circom
+
+#[derive(Clone)]
+pub struct MiMC {
+  pub f: Arc<PrimeField>,
+  pub exponent: u64,
+  pub round_constants: Vec<PrimeFieldElem>,  // one constant for each round
+}
+
+impl MiMC {
+  // c_0 = 0 and c_i = H^i(seed) mod p for i > 0 where H is SHA-256
+  pub fn new(f: &Arc<PrimeField>, exponent: u64, rounds: usize, seed: &[u8]) -> Result<Self, String> {
+    if rounds == 0 {
+      return Err("At least 1 round is required".to_string());
+    }
+    let mut round_constants = vec![f.elem(&0u8)];
+    let mut h = Sha256().get_digest(seed);
+    for _ in 1..rounds {
+      round_constants.push(f.elem(&BigUint::from_bytes_be(&h)));
+      h = Sha256().get_digest(&h);
+    }
+    MiMC::with_constants(f, exponent, &round_constants)
+  }
+
+  // the Feistel network is a permutation for any round function, so
+  // the exponent doesn't need to be coprime to p - 1
+  pub fn with_constants(f: &Arc<PrimeField>, exponent: u64, round_constants: &[PrimeFieldElem]) -> Result<Self, String> {
+    if exponent < 2 {
+      return Err(format!("Exponent needs to be at least 2, but got {}", exponent));
+    }
+    if round_constants.is_empty() {
+      return Err("At least 1 round is required".to_string());
+    }
+    Ok(MiMC {
+      f: f.clone(),
+      exponent,
+      round_constants: round_constants.to_vec(),
+    })
+  }
+
+  // x^5 w/ 2 * ceil(255 / log2(5)) = 220 rounds over BLS12-381 Fr
+  pub fn bls12_381_fr() -> Self {
+    MiMC::new(&P::subgroup(), 5, 220, b"mimc").unwrap()
+  }
+
+  pub fn rounds(&self) -> usize {
+    self.round_constants.len()
+  }
+
+  // (xL, xR) -> (xR + (xL + k + c_i)^e, xL). the last round doesn't swap the halves
+  pub fn permute(
+    &self,
+    xL: &PrimeFieldElem,
+    xR: &PrimeFieldElem,
+    k: &PrimeFieldElem,
+  ) -> (PrimeFieldElem, PrimeFieldElem) {
+    let (mut xL, mut xR) = (xL.clone(), xR.clone());
+    for (i, c) in self.round_constants.iter().enumerate() {
+      let t = (&xL + k + c).pow(&self.exponent);
+      if i < self.rounds() - 1 {
+        (xL, xR) = (xR + t, xL);
+      } else {
+        xR += t;
+      }
+    }
+    (xL, xR)
+  }
+
+  // sponge absorbing an input into xL before each permutation. xL is the output
+  pub fn hash_with_key(&self, inputs: &[PrimeFieldElem], k: &PrimeFieldElem) -> PrimeFieldElem {
+    let mut xL = self.f.elem(&0u8);
+    let mut xR = self.f.elem(&0u8);
+    for x in inputs {
+      (xL, xR) = self.permute(&(xL + x), &xR, k);
+    }
+    xL
+  }
+
+  pub fn hash(&self, inputs: &[PrimeFieldElem]) -> PrimeFieldElem {
+    self.hash_with_key(inputs, &self.f.elem(&0u8))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn elem(f: &Arc<PrimeField>, hex: &str) -> PrimeFieldElem {
+    PrimeFieldElem::new(f, &BigUint::parse_bytes(hex.as_bytes(), 16).unwrap())
+  }
+
+  #[test]
+  fn round_constants() {
+    let mimc = MiMC::bls12_381_fr();
+    let f = &mimc.f;
+    assert_eq!(mimc.rounds(), 220);
+    assert_eq!(mimc.round_constants[0], f.elem(&0u8));
+    assert_eq!(mimc.round_constants[1], elem(f, "726dafb796c119aba5982d0b84f986dae355d30e7021ea8c0fa8141aa0a007ad"));
+    assert_eq!(mimc.round_constants[219], elem(f, "0f73b432692f1c660e9b81617582883f3120eb572d8dc1fbadff2328ba7a47fc"));
+  }
+
+  #[test]
+  fn permute() {
+    let mimc = MiMC::bls12_381_fr();
+    let f = &mimc.f;
+    let (xL, xR) = mimc.permute(&f.elem(&1u8), &f.elem(&2u8), &f.elem(&3u8));
+    assert_eq!(xL, elem(f, "6ed056981a16a4726beebb04ff97bd5192aae97e101ece39c5422b0d3111b2d2"));
+    assert_eq!(xR, elem(f, "23e2ae4b1dad35322b82e56bae68172486bbb4566607a5d694ddcf1640582349"));
+  }
+
+  #[test]
+  fn hash() {
+    let mimc = MiMC::bls12_381_fr();
+    let f = &mimc.f;
+
+    let test_cases: [(&[u32], &str); 3] = [
+      (&[1], "2ef217ecb3af0656ecffd6d296a32c131282dfa1cdaf68e2e0ca181e91bf7afd"),
+      (&[1, 2], "0895378f7d6d06e42fe7204f9d6a2bc223b7549133bb1017105ca2d75d6504a9"),
+      (&[1, 2, 3], "51072dd763d44fcd7a979e809d4a74ed3bf9b3af8935222d9b26d6ecebc3bdc1"),
+    ];
+    for (inputs, exp) in test_cases {
+      let inputs: Vec<PrimeFieldElem> = inputs.iter().map(|x| f.elem(x)).collect();
+      assert_eq!(mimc.hash(&inputs), elem(f, exp));
+    }
+
+    let inputs = vec![f.elem(&1u8), f.elem(&2u8)];
+    assert_eq!(
+      mimc.hash_with_key(&inputs, &f.elem(&7u8)),
+      elem(f, "1fc39aac415082d43397ac13f231ad1fbe65ec400b87396f292c3ed5ed13a7bb"),
+    );
+  }
+
+  #[test]
+  fn invalid_parameters() {
+    let f = &P::subgroup();
+    assert!(MiMC::new(f, 1, 220, b"mimc").is_err());
+    assert!(MiMC::new(f, 5, 0, b"mimc").is_err());
+    assert!(MiMC::with_constants(f, 5, &[]).is_err());
+  }
+}
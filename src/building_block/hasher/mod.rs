@@ -1,5 +1,22 @@
+pub mod blake2b;
+pub mod blake2s;
+mod blake2_common;
+pub mod grain_lfsr;
 pub mod hasher;
+pub mod hkdf;
 pub mod hmac;
+pub mod keccak;
+pub mod mimc;
+pub mod pbkdf2;
+pub mod poseidon;
+pub mod rescue_prime;
+pub mod ripemd160;
+pub mod sha224;
 pub mod sha256;
+pub mod sha384;
 pub mod sha512;
-mod sha_common;
\ No newline at end of file
+pub mod sha512_224;
+pub mod sha512_256;
+pub mod sha3;
+pub mod shake;
+mod sha_common;
@@ -0,0 +1,100 @@
+use super::{
+  hasher::Hasher,
+  hmac::Hmac,
+};
+
+// implementation based on: https://datatracker.ietf.org/doc/html/rfc8018#section-5.2
+
+pub struct Pbkdf2<const N: usize> {
+  hmac: Hmac<N>,
+}
+
+impl<const N: usize> Pbkdf2<N> {
+  pub fn new(hasher: Box<dyn Hasher<N>>) -> Self {
+    Pbkdf2 { hmac: Hmac::new(hasher) }
+  }
+
+  pub fn derive_key(
+    &self,
+    password: &[u8],
+    salt: &[u8],
+    num_iters: u32,
+    key_len: usize,
+  ) -> Result<Vec<u8>, String> {
+    if num_iters == 0 {
+      return Err("Number of iterations needs to be positive".to_string());
+    }
+    // key_len needs to be at most (2^32 - 1) * hLen
+    let num_blocks = (key_len + N - 1) / N;
+    if num_blocks as u64 > u32::MAX as u64 {
+      return Err("Derived key is too long".to_string());
+    }
+
+    let mut key = Vec::with_capacity(num_blocks * N);
+    for i in 1..=num_blocks as u32 {
+      // U_1 = PRF(P, S || INT(i)), U_c = PRF(P, U_{c-1})
+      // T_i = U_1 XOR U_2 XOR ... XOR U_c
+      let mut u = self.hmac.get_digest(password, &[salt, &i.to_be_bytes()].concat());
+      let mut t = u;
+      for _ in 1..num_iters {
+        u = self.hmac.get_digest(password, &u);
+        for j in 0..N {
+          t[j] ^= u[j];
+        }
+      }
+      key.extend_from_slice(&t);
+    }
+    key.truncate(key_len);
+    Ok(key)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::{
+    sha256::Sha256,
+    sha512::Sha512,
+  };
+  use hex::ToHex;
+
+  #[test]
+  fn pbkdf2_hmac_sha256() {
+    let pbkdf2 = Pbkdf2::new(Box::new(Sha256()));
+    let test_cases = [
+      (1, 32, "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"),
+      (2, 32, "ae4d0c95af6b46d32d0adff928f06dd02a303f8ef3c251dfd6e2d85a95474c43"),
+      (4096, 32, "c5e478d59288c841aa530db6845c4c8d962893a001ce4e11a4963873aa98134a"),
+      (1, 20, "120fb6cffcf8b32c43e7225256c4f837a86548c9"),
+    ];
+    for (num_iters, key_len, exp) in test_cases {
+      let key = pbkdf2.derive_key(b"password", b"salt", num_iters, key_len).unwrap();
+      assert_eq!(key.encode_hex::<String>(), exp);
+    }
+  }
+
+  #[test]
+  fn pbkdf2_hmac_sha256_multi_block() {
+    let pbkdf2 = Pbkdf2::new(Box::new(Sha256()));
+    let key = pbkdf2.derive_key(
+      b"passwordPASSWORDpassword",
+      b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+      4096,
+      40,
+    ).unwrap();
+    assert_eq!(key.encode_hex::<String>(), "348c89dbcbd32b2f32d814b8116e84cf2b17347ebc1800181c4e2a1fb8dd53e1c635518c7dac47e9");
+  }
+
+  #[test]
+  fn pbkdf2_hmac_sha512() {
+    let pbkdf2 = Pbkdf2::new(Box::new(Sha512()));
+    let key = pbkdf2.derive_key(b"password", b"salt", 1, 64).unwrap();
+    assert_eq!(key.encode_hex::<String>(), "867f70cf1ade02cff3752599a3a53dc4af34c7a669815ae5d513554e1c8cf252c02d470a285a0501bad999bfe943c08f050235d7d68b1da55e63f73b60a57fce");
+  }
+
+  #[test]
+  fn pbkdf2_zero_iters() {
+    let pbkdf2 = Pbkdf2::new(Box::new(Sha256()));
+    assert!(pbkdf2.derive_key(b"password", b"salt", 0, 32).is_err());
+  }
+}
@@ -0,0 +1,361 @@
+use crate::building_block::{
+  curves::bls12_381::params::Params as P,
+  field::{
+    prime_field::PrimeField,
+    prime_field_elem::PrimeFieldElem,
+  },
+  hasher::grain_lfsr::GrainLfsr,
+};
+use num_bigint::BigUint;
+use num_traits::Zero;
+use std::{
+  collections::HashSet,
+  sync::Arc,
+};
+
+// implementation based on:
+// - https://eprint.iacr.org/2019/458.pdf
+// - https://extgit.iaik.tugraz.at/krypto/hadeshash
+//
+// the MDS matrix is a Cauchy matrix generated w/ the Grain LFSR. the additional checks
+// for infinitely long invariant subspace trails (Algorithms 1-3 in the reference script)
+// are not performed, so custom parameters should be checked against the reference script
+
+const CAPACITY: usize = 1;
+
+#[derive(Clone)]
+pub struct Poseidon {
+  pub f: Arc<PrimeField>,
+  pub t: usize,  // state width
+  pub alpha: u64,  // S-box exponent
+  pub full_rounds: usize,
+  pub partial_rounds: usize,
+  pub round_constants: Vec<PrimeFieldElem>,  // t constants for each round
+  pub mds: Vec<Vec<PrimeFieldElem>>,
+}
+
+impl Poseidon {
+  // generates round constants and MDS matrix w/ the Grain LFSR
+  pub fn new(
+    f: &Arc<PrimeField>,
+    t: usize,
+    alpha: u64,
+    full_rounds: usize,
+    partial_rounds: usize,
+  ) -> Result<Self, String> {
+    let field_size = f.order_ref().bits() as usize;
+    let mut lfsr = GrainLfsr::new(1, 0, field_size, t, full_rounds, partial_rounds);
+
+    let round_constants: Vec<PrimeFieldElem> = (0..(full_rounds + partial_rounds) * t)
+      .map(|_| lfsr.next_field_elem(f))
+      .collect();
+    let mds = Poseidon::gen_mds(f, t, &mut lfsr);
+
+    Poseidon::with_constants(f, t, alpha, full_rounds, partial_rounds, &round_constants, &mds)
+  }
+
+  pub fn with_constants(
+    f: &Arc<PrimeField>,
+    t: usize,
+    alpha: u64,
+    full_rounds: usize,
+    partial_rounds: usize,
+    round_constants: &[PrimeFieldElem],
+    mds: &[Vec<PrimeFieldElem>],
+  ) -> Result<Self, String> {
+    if t <= CAPACITY {
+      return Err(format!("State width needs to be greater than {}, but got {}", CAPACITY, t));
+    }
+    if full_rounds % 2 != 0 {
+      return Err(format!("Number of full rounds needs to be even, but got {}", full_rounds));
+    }
+    // x^alpha is a permutation only if gcd(alpha, p - 1) = 1
+    let p_minus_1 = f.order_ref() - 1u8;
+    if alpha < 3 || Poseidon::gcd(&BigUint::from(alpha), &p_minus_1) != BigUint::from(1u8) {
+      return Err(format!("x^{} is not a permutation of the field", alpha));
+    }
+    let num_constants = (full_rounds + partial_rounds) * t;
+    if round_constants.len() != num_constants {
+      return Err(format!("Expected {} round constants, but got {}", num_constants, round_constants.len()));
+    }
+    if mds.len() != t || mds.iter().any(|row| row.len() != t) {
+      return Err(format!("MDS matrix needs to be {}x{}", t, t));
+    }
+    Ok(Poseidon {
+      f: f.clone(),
+      t,
+      alpha,
+      full_rounds,
+      partial_rounds,
+      round_constants: round_constants.to_vec(),
+      mds: mds.to_vec(),
+    })
+  }
+
+  // x5_255_3 instance of the reference implementation over BLS12-381 Fr
+  pub fn bls12_381_fr() -> Self {
+    Poseidon::new(&P::subgroup(), 3, 5, 8, 57).unwrap()
+  }
+
+  fn gcd(a: &BigUint, b: &BigUint) -> BigUint {
+    let (mut a, mut b) = (a.clone(), b.clone());
+    while !b.is_zero() {
+      let r = &a % &b;
+      a = b;
+      b = r;
+    }
+    a
+  }
+
+  // M[i][j] = 1 / (x_i + y_j) where x_1, ..., x_t, y_1, ..., y_t are distinct
+  fn gen_mds(f: &Arc<PrimeField>, t: usize, lfsr: &mut GrainLfsr) -> Vec<Vec<PrimeFieldElem>> {
+    loop {
+      let mut xys: Vec<PrimeFieldElem> = (0..2 * t).map(|_| lfsr.next_field_elem_mod(f)).collect();
+      while xys.iter().map(|x| x.e.clone()).collect::<HashSet<BigUint>>().len() != 2 * t {
+        xys = (0..2 * t).map(|_| lfsr.next_field_elem_mod(f)).collect();
+      }
+      let (xs, ys) = xys.split_at(t);
+
+      if xs.iter().any(|x| ys.iter().any(|y| (x + y).is_zero())) {
+        continue;
+      }
+      return xs.iter().map(|x| ys.iter().map(|y| (x + y).inv()).collect()).collect();
+    }
+  }
+
+  pub fn is_full_round(&self, round: usize) -> bool {
+    let half_full_rounds = self.full_rounds / 2;
+    round < half_full_rounds || round >= half_full_rounds + self.partial_rounds
+  }
+
+  pub fn mix(&self, state: &[PrimeFieldElem]) -> Vec<PrimeFieldElem> {
+    self.mds.iter().map(|row| {
+      row.iter().zip(state.iter()).fold(self.f.elem(&0u8), |acc, (m, s)| acc + m * s)
+    }).collect()
+  }
+
+  // full rounds are split in half and placed before and after the partial rounds.
+  // partial rounds apply the S-box to the first element of the state only
+  pub fn permute(&self, state: &mut Vec<PrimeFieldElem>) {
+    assert!(state.len() == self.t, "State needs to have {} elements, but got {}", self.t, state.len());
+
+    for round in 0..self.full_rounds + self.partial_rounds {
+      let rcs = &self.round_constants[round * self.t..(round + 1) * self.t];
+      for (s, rc) in state.iter_mut().zip(rcs.iter()) {
+        *s = &*s + rc;
+      }
+      if self.is_full_round(round) {
+        for s in state.iter_mut() {
+          *s = s.pow(&self.alpha);
+        }
+      } else {
+        state[0] = state[0].pow(&self.alpha);
+      }
+      *state = self.mix(state);
+    }
+  }
+
+  pub fn rate(&self) -> usize {
+    self.t - CAPACITY
+  }
+
+  // capacity element is initialized w/ len * 2^64 + (out_len - 1) for
+  // fixed-length hashing as suggested in section 4.2 of the paper
+  pub fn hash_n(&self, inputs: &[PrimeFieldElem], out_len: usize) -> Result<Vec<PrimeFieldElem>, String> {
+    if out_len == 0 {
+      return Err("Output length needs to be at least 1".to_string());
+    }
+    let capacity_value = (BigUint::from(inputs.len()) << 64) + out_len - 1u8;
+    let mut sponge = PoseidonSponge::new(self, &self.f.elem(&capacity_value));
+    sponge.absorb(inputs);
+    Ok(sponge.squeeze(out_len))
+  }
+
+  pub fn hash(&self, inputs: &[PrimeFieldElem]) -> PrimeFieldElem {
+    // output length of 1 is always valid
+    self.hash_n(inputs, 1).unwrap().remove(0)
+  }
+}
+
+// sponge w/ a single capacity element placed at the beginning of the state.
+// the last partially filled block is implicitly padded w/ zeros
+pub struct PoseidonSponge<'a> {
+  poseidon: &'a Poseidon,
+  state: Vec<PrimeFieldElem>,
+  pos: usize,  // position in the rate part of the state
+  is_squeezing: bool,
+}
+
+impl<'a> PoseidonSponge<'a> {
+  pub fn new(poseidon: &'a Poseidon, capacity_value: &PrimeFieldElem) -> Self {
+    let mut state = vec![poseidon.f.elem(&0u8); poseidon.t];
+    state[0] = capacity_value.clone();
+    PoseidonSponge {
+      poseidon,
+      state,
+      pos: 0,
+      is_squeezing: false,
+    }
+  }
+
+  pub fn absorb(&mut self, inputs: &[PrimeFieldElem]) {
+    assert!(!self.is_squeezing, "Cannot absorb after squeezing has started");
+    for x in inputs {
+      if self.pos == self.poseidon.rate() {
+        self.poseidon.permute(&mut self.state);
+        self.pos = 0;
+      }
+      let i = CAPACITY + self.pos;
+      self.state[i] = &self.state[i] + x;
+      self.pos += 1;
+    }
+  }
+
+  pub fn squeeze(&mut self, out_len: usize) -> Vec<PrimeFieldElem> {
+    if !self.is_squeezing {
+      self.poseidon.permute(&mut self.state);
+      self.pos = 0;
+      self.is_squeezing = true;
+    }
+    let mut out = Vec::with_capacity(out_len);
+    for _ in 0..out_len {
+      if self.pos == self.poseidon.rate() {
+        self.poseidon.permute(&mut self.state);
+        self.pos = 0;
+      }
+      out.push(self.state[CAPACITY + self.pos].clone());
+      self.pos += 1;
+    }
+    out
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn elem(f: &Arc<PrimeField>, hex: &str) -> PrimeFieldElem {
+    PrimeFieldElem::new(f, &BigUint::parse_bytes(hex.as_bytes(), 16).unwrap())
+  }
+
+  fn elems(f: &Arc<PrimeField>, xs: &[u32]) -> Vec<PrimeFieldElem> {
+    xs.iter().map(|x| f.elem(x)).collect()
+  }
+
+  #[test]
+  fn bls12_381_fr_permutation() {
+    // test vector of the reference poseidonperm_x5_255_3 instance
+    let poseidon = Poseidon::bls12_381_fr();
+    let f = &poseidon.f;
+    assert_eq!(poseidon.round_constants[0], elem(f, "6c4ffa723eaf1a7bf74905cc7dae4ca9ff4a2c3bc81d42e09540d1f250910880"));
+    assert_eq!(poseidon.mds[0][0], elem(f, "3d955d6c02fe4d7cb500e12f2b55eff668a7b4386bd27413766713c93f2acfcd"));
+
+    let mut state = elems(f, &[0, 1, 2]);
+    poseidon.permute(&mut state);
+    assert_eq!(state, vec![
+      elem(f, "28ce19420fc246a05553ad1e8c98f5c9d67166be2c18e9e4cb4b4e317dd2a78a"),
+      elem(f, "51f3e312c95343a896cfd8945ea82ba956c1118ce9b9859b6ea56637b4b1ddc4"),
+      elem(f, "3b2b69139b235626a0bfb56c9527ae66a7bf486ad8c11c14d1da0c69bbe0f79a"),
+    ]);
+  }
+
+  #[test]
+  fn bn254_fr_permutation() {
+    // test vector of the reference poseidonperm_x5_254_3 instance
+    let r = BigUint::parse_bytes(b"21888242871839275222246405745257275088548364400416034343698204186575808495617", 10).unwrap();
+    let f = &Arc::new(PrimeField::new(&r));
+    let poseidon = Poseidon::new(f, 3, 5, 8, 57).unwrap();
+
+    let mut state = elems(f, &[0, 1, 2]);
+    poseidon.permute(&mut state);
+    assert_eq!(state, vec![
+      elem(f, "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"),
+      elem(f, "0fca49b798923ab0239de1c9e7a4a9a2210312b6a2f616d18b5a87f9b628ae29"),
+      elem(f, "0e7ae82e40091e63cbd4f16a6d16310b3729d4b6e138fcf54110e2867045a30c"),
+    ]);
+  }
+
+  #[test]
+  fn custom_parameters() {
+    // t = 5 and x^7 over the Goldilocks field
+    let f = &Arc::new(PrimeField::new(&0xffffffff00000001u64));
+    let poseidon = Poseidon::new(f, 5, 7, 8, 22).unwrap();
+    assert_eq!(poseidon.round_constants.len(), 30 * 5);
+
+    let mut state = elems(f, &[0, 1, 2, 3, 4]);
+    poseidon.permute(&mut state);
+    assert_eq!(state, vec![
+      elem(f, "edefcb9147396483"),
+      elem(f, "130136990d359d1b"),
+      elem(f, "d1ef9ec22ea5ad40"),
+      elem(f, "2c1a765a715acc99"),
+      elem(f, "d1785f96f573ae78"),
+    ]);
+  }
+
+  #[test]
+  fn invalid_parameters() {
+    let f = &P::subgroup();
+    // x^3 is not a permutation since 3 divides r - 1
+    assert!(Poseidon::new(f, 3, 3, 8, 57).is_err());
+    assert!(Poseidon::new(f, 1, 5, 8, 57).is_err());
+    assert!(Poseidon::new(f, 3, 5, 7, 57).is_err());
+
+    let poseidon = Poseidon::bls12_381_fr();
+    assert!(Poseidon::with_constants(f, 3, 5, 8, 56, &poseidon.round_constants, &poseidon.mds).is_err());
+    assert!(Poseidon::with_constants(f, 3, 5, 8, 57, &poseidon.round_constants, &poseidon.mds[..2]).is_err());
+    assert!(Poseidon::with_constants(f, 3, 5, 8, 57, &poseidon.round_constants, &poseidon.mds).is_ok());
+  }
+
+  #[test]
+  fn hash() {
+    let poseidon = Poseidon::bls12_381_fr();
+    let f = &poseidon.f;
+
+    let test_cases: [(&[u32], &str); 4] = [
+      (&[], "10a9e48afc92bd4669b3a8c08c8c99d4144632da67c6cb9bb19cc8facaf8ed3e"),
+      (&[1], "4e4ba9d1cfb76a73e5c7e937dc545ffa72889c1c1bb6a5549160891f7cf4a823"),
+      (&[1, 2], "22bfe6ed4f16dd3abe8becff2a4608d1e61f0ea1b408d508f3ed9ffc599d0ca3"),
+      (&[1, 2, 3], "36e5517519413221092a470659527a8e69a9b393005ee3cbe2c8db57e91f790d"),
+    ];
+    for (inputs, exp) in test_cases {
+      assert_eq!(poseidon.hash(&elems(f, inputs)), elem(f, exp));
+    }
+  }
+
+  #[test]
+  fn hash_n() {
+    let poseidon = Poseidon::bls12_381_fr();
+    let f = &poseidon.f;
+    let out = poseidon.hash_n(&elems(f, &[1, 2, 3, 4, 5]), 3).unwrap();
+    assert_eq!(out, vec![
+      elem(f, "088facc489626d2452e34a16804d23f008cf310a6e9e0647d0e9ee69cc80e3cd"),
+      elem(f, "2743b62d94315ea2db72ce3146a67dc97362d91c97d6187a0551f74bf799f3b5"),
+      elem(f, "106aec5d52260799fa7fb640da733fb026ec4bf4e192b962f73cb3e179e86a6b"),
+    ]);
+  }
+
+  #[test]
+  fn hash_n_empty_output() {
+    let poseidon = Poseidon::bls12_381_fr();
+    let f = &poseidon.f;
+    assert!(poseidon.hash_n(&[], 0).is_err());
+    assert!(poseidon.hash_n(&elems(f, &[1, 2, 3]), 0).is_err());
+  }
+
+  #[test]
+  fn sponge_streaming() {
+    let poseidon = Poseidon::bls12_381_fr();
+    let f = &poseidon.f;
+    let xs = elems(f, &[1, 2, 3, 4, 5]);
+
+    let capacity_value = f.elem(&((5u128 << 64) + 2));
+    let mut sponge = PoseidonSponge::new(&poseidon, &capacity_value);
+    sponge.absorb(&xs[..1]);
+    sponge.absorb(&xs[1..4]);
+    sponge.absorb(&xs[4..]);
+    let mut out = sponge.squeeze(1);
+    out.extend(sponge.squeeze(2));
+    assert_eq!(out, poseidon.hash_n(&xs, 3).unwrap());
+  }
+}
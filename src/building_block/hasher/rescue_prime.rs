@@ -0,0 +1,311 @@
+use crate::building_block::{
+  curves::bls12_381::params::Params as P,
+  field::{
+    prime_field::PrimeField,
+    prime_field_elem::PrimeFieldElem,
+  },
+  hasher::shake::Shake256,
+};
+use num_bigint::{BigInt, BigUint, ToBigInt};
+use num_traits::{One, Zero};
+use std::sync::Arc;
+
+// implementation based on:
+// - https://eprint.iacr.org/2020/1143.pdf
+// - https://github.com/KULeuven-COSIC/Marvellous/blob/master/rescue_prime.sage
+
+#[derive(Clone)]
+pub struct RescuePrime {
+  pub f: Arc<PrimeField>,
+  pub m: usize,  // state width
+  pub capacity: usize,
+  pub alpha: u64,
+  pub alpha_inv: BigUint,  // alpha^-1 mod p - 1
+  pub round_constants: Vec<PrimeFieldElem>,  // 2m constants for each round
+  pub mds: Vec<Vec<PrimeFieldElem>>,
+}
+
+impl RescuePrime {
+  // derives round constants w/ SHAKE256 and the MDS matrix from a Vandermonde
+  // matrix of the generator g as in the reference implementation
+  pub fn new(
+    f: &Arc<PrimeField>,
+    m: usize,
+    capacity: usize,
+    alpha: u64,
+    rounds: usize,
+    security_level: usize,
+    g: &PrimeFieldElem,
+  ) -> Result<Self, String> {
+    let round_constants = RescuePrime::gen_round_constants(f, m, capacity, security_level, rounds);
+    let mds = RescuePrime::gen_mds(m, g)?;
+    RescuePrime::with_constants(f, m, capacity, alpha, &round_constants, &mds)
+  }
+
+  pub fn with_constants(
+    f: &Arc<PrimeField>,
+    m: usize,
+    capacity: usize,
+    alpha: u64,
+    round_constants: &[PrimeFieldElem],
+    mds: &[Vec<PrimeFieldElem>],
+  ) -> Result<Self, String> {
+    if m < 2 || capacity == 0 || capacity >= m {
+      return Err(format!("Capacity {} is invalid for state width {}", capacity, m));
+    }
+    let p_minus_1 = f.order_ref() - 1u8;
+    let alpha_inv = match RescuePrime::mod_inv(&BigUint::from(alpha), &p_minus_1) {
+      Some(x) if alpha >= 3 => x,
+      _ => return Err(format!("x^{} is not a permutation of the field", alpha)),
+    };
+    if round_constants.is_empty() || round_constants.len() % (2 * m) != 0 {
+      return Err(format!("Number of round constants needs to be a positive multiple of {}, but got {}", 2 * m, round_constants.len()));
+    }
+    if mds.len() != m || mds.iter().any(|row| row.len() != m) {
+      return Err(format!("MDS matrix needs to be {}x{}", m, m));
+    }
+    Ok(RescuePrime {
+      f: f.clone(),
+      m,
+      capacity,
+      alpha,
+      alpha_inv,
+      round_constants: round_constants.to_vec(),
+      mds: mds.to_vec(),
+    })
+  }
+
+  // m = 3, capacity = 1 and x^5 w/ 128-bit security over BLS12-381 Fr
+  pub fn bls12_381_fr() -> Self {
+    let f = &P::subgroup();
+    let rounds = RescuePrime::num_rounds(3, 1, 128, 5);
+    RescuePrime::new(f, 3, 1, 5, rounds, 128, &f.elem(&7u8)).unwrap()
+  }
+
+  // 1.5 times the number of rounds that resists Groebner basis attacks w/ a minimum of 5
+  pub fn num_rounds(m: usize, capacity: usize, security_level: usize, alpha: u64) -> usize {
+    let rate = m - capacity;
+    let dcon = |n: usize| (alpha as usize - 1) * m * (n - 1) / 2 + 2;
+    let v = |n: usize| m * (n - 1) + rate;
+    let target = BigUint::one() << security_level;
+
+    let mut l1 = 1;
+    while l1 < 24 {
+      let binom = RescuePrime::binomial(v(l1) + dcon(l1), v(l1));
+      if &binom * &binom > target {
+        break;
+      }
+      l1 += 1;
+    }
+    (3 * l1.max(5) + 1) / 2
+  }
+
+  fn binomial(n: usize, k: usize) -> BigUint {
+    let mut x = BigUint::one();
+    for i in 0..k {
+      x = x * (n - i) / (i + 1);
+    }
+    x
+  }
+
+  fn mod_inv(a: &BigUint, n: &BigUint) -> Option<BigUint> {
+    let n_int = n.to_bigint().unwrap();
+    let (mut r0, mut r1) = (n_int.clone(), a.to_bigint().unwrap());
+    let (mut t0, mut t1) = (BigInt::zero(), BigInt::one());
+    while !r1.is_zero() {
+      let q = &r0 / &r1;
+      (r0, r1) = (r1.clone(), &r0 - &q * &r1);
+      (t0, t1) = (t1.clone(), &t0 - &q * &t1);
+    }
+    if !r0.is_one() {
+      return None;
+    }
+    let t = ((t0 % &n_int) + &n_int) % &n_int;
+    t.to_biguint()
+  }
+
+  // little-endian integers of ceil(log2(p) / 8) + 1 bytes taken from
+  // SHAKE256("Rescue-XLIX(p,m,capacity,security_level)")
+  fn gen_round_constants(
+    f: &Arc<PrimeField>,
+    m: usize,
+    capacity: usize,
+    security_level: usize,
+    rounds: usize,
+  ) -> Vec<PrimeFieldElem> {
+    let bytes_per_int = (f.order_ref().bits() as usize + 7) / 8 + 1;
+    let num_constants = 2 * m * rounds;
+    let seed = format!("Rescue-XLIX({},{},{},{})", f.order_ref(), m, capacity, security_level);
+    let buf = Shake256().get_output(seed.as_bytes(), bytes_per_int * num_constants);
+
+    buf.chunks(bytes_per_int)
+      .map(|chunk| f.elem(&BigUint::from_bytes_le(chunk)))
+      .collect()
+  }
+
+  // transpose of the right half of the reduced row echelon form of
+  // the m x 2m Vandermonde matrix V[i][j] = g^(i * j)
+  fn gen_mds(m: usize, g: &PrimeFieldElem) -> Result<Vec<Vec<PrimeFieldElem>>, String> {
+    let mut v: Vec<Vec<PrimeFieldElem>> = (0..m).map(|i| {
+      (0..2 * m).map(|j| g.pow(&(i * j))).collect()
+    }).collect();
+
+    for col in 0..m {
+      let pivot = match (col..m).find(|row| !v[*row][col].is_zero()) {
+        Some(row) => row,
+        None => return Err(format!("Vandermonde matrix of {:?} is singular", g)),
+      };
+      v.swap(col, pivot);
+
+      let inv = v[col][col].inv();
+      v[col] = v[col].iter().map(|x| x * &inv).collect();
+
+      for row in 0..m {
+        if row != col && !v[row][col].is_zero() {
+          let k = v[row][col].clone();
+          v[row] = v[row].iter().zip(v[col].iter()).map(|(x, y)| x - &(&k * y)).collect();
+        }
+      }
+    }
+    Ok((0..m).map(|i| (0..m).map(|j| v[j][m + i].clone()).collect()).collect())
+  }
+
+  pub fn rounds(&self) -> usize {
+    self.round_constants.len() / (2 * self.m)
+  }
+
+  pub fn rate(&self) -> usize {
+    self.m - self.capacity
+  }
+
+  pub fn mix(&self, state: &[PrimeFieldElem]) -> Vec<PrimeFieldElem> {
+    self.mds.iter().map(|row| {
+      row.iter().zip(state.iter()).fold(self.f.elem(&0u8), |acc, (m, s)| acc + m * s)
+    }).collect()
+  }
+
+  // each round consists of x^alpha, MDS and constants followed by x^(1/alpha), MDS and constants
+  pub fn permute(&self, state: &mut Vec<PrimeFieldElem>) {
+    assert!(state.len() == self.m, "State needs to have {} elements, but got {}", self.m, state.len());
+    let m = self.m;
+
+    for round in 0..self.rounds() {
+      for half in 0..2 {
+        let exp = if half == 0 { BigUint::from(self.alpha) } else { self.alpha_inv.clone() };
+        let sboxed: Vec<PrimeFieldElem> = state.iter().map(|x| x.pow(&exp)).collect();
+        let rcs = &self.round_constants[(2 * round + half) * m..(2 * round + half + 1) * m];
+        *state = self.mix(&sboxed).iter().zip(rcs.iter()).map(|(x, rc)| x + rc).collect();
+      }
+    }
+  }
+
+  // inputs are padded w/ 1 followed by zeros to a multiple of the rate.
+  // the rate part of the state is the output
+  pub fn hash(&self, inputs: &[PrimeFieldElem]) -> Vec<PrimeFieldElem> {
+    let rate = self.rate();
+    let mut padded = inputs.to_vec();
+    padded.push(self.f.elem(&1u8));
+    while padded.len() % rate != 0 {
+      padded.push(self.f.elem(&0u8));
+    }
+
+    let mut state = vec![self.f.elem(&0u8); self.m];
+    for block in padded.chunks(rate) {
+      for (s, x) in state.iter_mut().zip(block.iter()) {
+        *s = &*s + x;
+      }
+      self.permute(&mut state);
+    }
+    state[..rate].to_vec()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn elem(f: &Arc<PrimeField>, hex: &str) -> PrimeFieldElem {
+    PrimeFieldElem::new(f, &BigUint::parse_bytes(hex.as_bytes(), 16).unwrap())
+  }
+
+  fn elems(f: &Arc<PrimeField>, xs: &[u32]) -> Vec<PrimeFieldElem> {
+    xs.iter().map(|x| f.elem(x)).collect()
+  }
+
+  #[test]
+  fn num_rounds() {
+    assert_eq!(RescuePrime::num_rounds(3, 1, 128, 5), 14);
+    assert_eq!(RescuePrime::num_rounds(4, 2, 128, 5), 11);
+    // Goldilocks field instance w/ m = 12
+    assert_eq!(RescuePrime::num_rounds(12, 4, 128, 7), 8);
+  }
+
+  #[test]
+  fn parameters() {
+    let rescue = RescuePrime::bls12_381_fr();
+    let f = &rescue.f;
+    assert_eq!(rescue.rounds(), 14);
+    assert_eq!((&rescue.alpha_inv * 5u8) % (f.order_ref() - 1u8), BigUint::one());
+    assert_eq!(rescue.round_constants[0], elem(f, "4e79ebb1e5a43abef900bd773cdde906e4bf3244749cb64424f7db47ba0dda87"));
+    assert_eq!(rescue.round_constants[83], elem(f, "022335910ed632e5c9e2d372efd6e6841fb5fee114f08d90dc081a5a59445404"));
+    assert_eq!(rescue.mds[0], vec![
+      f.elem(&0x157u16),
+      f.elem(&0u8) - f.elem(&399u16),
+      f.elem(&0x39u8),
+    ]);
+  }
+
+  #[test]
+  fn permute() {
+    let rescue = RescuePrime::bls12_381_fr();
+    let f = &rescue.f;
+    let mut state = elems(f, &[0, 1, 2]);
+    rescue.permute(&mut state);
+    assert_eq!(state, vec![
+      elem(f, "2e1183b4ae571061ed9514118392ede2904ae1376d61653de09083cf0b31abce"),
+      elem(f, "38f9e521c67c329a53403dd42999b19c3bfe355e594752c87ada74da35c74b85"),
+      elem(f, "69a193e3c2734c26d85d191a1e521c1bc8024c9047bb5c79835ed5cfc2d8440e"),
+    ]);
+  }
+
+  #[test]
+  fn hash() {
+    let rescue = RescuePrime::bls12_381_fr();
+    let f = &rescue.f;
+
+    let test_cases: [(&[u32], [&str; 2]); 4] = [
+      (&[], [
+        "65af230cf10be9d2de876bf0ac192381cc85827eb6484b6afce452a82e9db9bc",
+        "006a29d32870ceb54747c39bd7c56113d8d2d7f8077827badd35a2b46f84fab3",
+      ]),
+      (&[1], [
+        "5aabf11b23ffba4a6c7b571753ae1522d0d7fa30c05a673846ae67c1b9e2fb98",
+        "3ca90d940cbf3f7d3a6dea84b777a3bc5b24947e427731c50cfaa9b62e836b6b",
+      ]),
+      (&[1, 2], [
+        "5d87015dfb62279a3dd4b271658e028e7d2a971fa2588b12b600d8d2f439aebb",
+        "0c4fd77e3245d00d08c0330314630b4ca7dfbd65c6256093b95276fa5334061b",
+      ]),
+      (&[1, 2, 3], [
+        "59f0312d54fea9900cb787192aba26bae1f6c4a1d9f9ac5e825224d72d4f9443",
+        "737bbc6c0060411fab96a3be852de9e12abf03ece077350ebc261c5ea5f6445d",
+      ]),
+    ];
+    for (inputs, exp) in test_cases {
+      let exp: Vec<PrimeFieldElem> = exp.iter().map(|x| elem(f, x)).collect();
+      assert_eq!(rescue.hash(&elems(f, inputs)), exp);
+    }
+  }
+
+  #[test]
+  fn invalid_parameters() {
+    let f = &P::subgroup();
+    let g = &f.elem(&7u8);
+    // x^3 is not a permutation since 3 divides r - 1
+    assert!(RescuePrime::new(f, 3, 1, 3, 14, 128, g).is_err());
+    assert!(RescuePrime::new(f, 3, 0, 5, 14, 128, g).is_err());
+    assert!(RescuePrime::new(f, 3, 3, 5, 14, 128, g).is_err());
+    assert!(RescuePrime::new(f, 3, 1, 5, 0, 128, g).is_err());
+    assert!(RescuePrime::new(f, 3, 1, 5, 14, 128, &f.elem(&0u8)).is_err());
+  }
+}
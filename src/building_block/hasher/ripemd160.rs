@@ -0,0 +1,204 @@
+#![allow(non_snake_case)]
+use super::hasher::{Hasher, HashContext};
+use super::sha_common::BlockBuffer;
+
+// implementation based on: https://homes.esat.kuleuven.be/~bosselae/ripemd160/pdf/AB-9601/AB-9601.pdf
+
+const BLOCK_SIZE: usize = 64;
+const DIGEST_SIZE: usize = 20;
+
+// message word selection of the left and right lines
+const R_L: [usize; 80] = [
+  0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15,
+  7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8,
+  3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12,
+  1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2,
+  4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+const R_R: [usize; 80] = [
+  5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12,
+  6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2,
+  15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13,
+  8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14,
+  12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+// amounts of left rotation of the left and right lines
+const S_L: [u32; 80] = [
+  11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8,
+  7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12,
+  11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5,
+  11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12,
+  9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+const S_R: [u32; 80] = [
+  8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6,
+  9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11,
+  9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5,
+  15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8,
+  8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+const K_L: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+const K_R: [u32; 5] = [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+const INITIAL_HASH_VALUE: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+#[derive(Clone)]
+pub struct Ripemd160();
+
+impl Ripemd160 {
+  // j-th nonlinear function
+  fn f(j: usize, x: u32, y: u32, z: u32) -> u32 {
+    match j / 16 {
+      0 => x ^ y ^ z,
+      1 => (x & y) | (!x & z),
+      2 => (x | !y) ^ z,
+      3 => (x & z) | (y & !z),
+      _ => x ^ (y | !z),
+    }
+  }
+
+  // same as sha2 padding except that the length is encoded in little-endian.
+  // msg is the trailing part of a message whose total length is msg_len bytes
+  fn pad_msg_tail(msg: &[u8], msg_len: u128) -> Vec<u8> {
+    let mut padded_msg = msg.to_vec();
+    padded_msg.push(0x80);
+    while padded_msg.len() % BLOCK_SIZE != BLOCK_SIZE - 8 {
+      padded_msg.push(0);
+    }
+    padded_msg.extend_from_slice(&((msg_len * 8) as u64).to_le_bytes());
+    padded_msg
+  }
+
+  fn compress(h: &mut [u32; 5], block: &[u8]) {
+    let X: Vec<u32> = (0..16).map(|i| {
+      u32::from_le_bytes(block[i * 4..(i + 1) * 4].try_into().unwrap())
+    }).collect();
+
+    let (mut al, mut bl, mut cl, mut dl, mut el) = (h[0], h[1], h[2], h[3], h[4]);
+    let (mut ar, mut br, mut cr, mut dr, mut er) = (h[0], h[1], h[2], h[3], h[4]);
+
+    for j in 0..80 {
+      let t = al
+        .wrapping_add(Self::f(j, bl, cl, dl))
+        .wrapping_add(X[R_L[j]])
+        .wrapping_add(K_L[j / 16])
+        .rotate_left(S_L[j])
+        .wrapping_add(el);
+      al = el; el = dl; dl = cl.rotate_left(10); cl = bl; bl = t;
+
+      let t = ar
+        .wrapping_add(Self::f(79 - j, br, cr, dr))
+        .wrapping_add(X[R_R[j]])
+        .wrapping_add(K_R[j / 16])
+        .rotate_left(S_R[j])
+        .wrapping_add(er);
+      ar = er; er = dr; dr = cr.rotate_left(10); cr = br; br = t;
+    }
+
+    let t = h[1].wrapping_add(cl).wrapping_add(dr);
+    h[1] = h[2].wrapping_add(dl).wrapping_add(er);
+    h[2] = h[3].wrapping_add(el).wrapping_add(ar);
+    h[3] = h[4].wrapping_add(al).wrapping_add(br);
+    h[4] = h[0].wrapping_add(bl).wrapping_add(cr);
+    h[0] = t;
+  }
+}
+
+impl Hasher<DIGEST_SIZE> for Ripemd160 {
+  fn get_digest(&self, msg: &[u8]) -> [u8; DIGEST_SIZE] {
+    let mut ctx = Ripemd160Context::new();
+    ctx.update(msg);
+    ctx.finalize()
+  }
+
+  fn get_block_size(&self) -> usize {
+    BLOCK_SIZE
+  }
+
+  fn new_context(&self) -> Box<dyn HashContext<DIGEST_SIZE>> {
+    Box::new(Ripemd160Context::new())
+  }
+}
+
+#[derive(Clone)]
+pub struct Ripemd160Context {
+  h: [u32; 5],
+  buf: BlockBuffer,
+}
+
+impl Ripemd160Context {
+  pub fn new() -> Self {
+    Ripemd160Context {
+      h: INITIAL_HASH_VALUE,
+      buf: BlockBuffer::new(BLOCK_SIZE),
+    }
+  }
+}
+
+impl HashContext<DIGEST_SIZE> for Ripemd160Context {
+  fn update(&mut self, msg: &[u8]) {
+    let h = &mut self.h;
+    self.buf.update(msg, |block| Ripemd160::compress(h, block));
+  }
+
+  fn finalize(&mut self) -> [u8; DIGEST_SIZE] {
+    let msg_len = self.buf.msg_len();
+    let padded_tail = Ripemd160::pad_msg_tail(&self.buf.take_tail(), msg_len);
+    for block in padded_tail.chunks(BLOCK_SIZE) {
+      Ripemd160::compress(&mut self.h, block);
+    }
+
+    let mut digest = [0u8; DIGEST_SIZE];
+    for i in 0..5 {
+      digest[i * 4..(i + 1) * 4].copy_from_slice(&self.h[i].to_le_bytes());
+    }
+    self.h = INITIAL_HASH_VALUE;
+    digest
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use hex::ToHex;
+
+  fn test(msg: &[u8], exp: &str) {
+    let hasher = Ripemd160();
+    let digest = hasher.get_digest(msg);
+    assert_eq!(digest.encode_hex::<String>(), exp);
+  }
+
+  #[test]
+  fn hash_empty() {
+    test(b"", "9c1185a5c5e9fc54612808977ee8f548b2258d31");
+  }
+
+  #[test]
+  fn hash_abc() {
+    test(b"a", "0bdc9d2d256b3ee9daae347be6f4dc835a467ffe");
+    test(b"abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc");
+    test(b"message digest", "5d0689ef49d2fae572b881b123a85ffa21595f36");
+  }
+
+  #[test]
+  fn hash_multi_block() {
+    test(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "12a053384a9c0c88e405a06c27dcf49ada62eb2b");
+    test(b"12345678901234567890123456789012345678901234567890123456789012345678901234567890", "9b752e45573d4b39f4dbd3323cab82bf63326bfb");
+  }
+
+  #[test]
+  fn hash_a_times_1mil() {
+    test(&[b'a'; 1_000_000], "52783243c1697bdbe16d37f97f68f08325dc1528");
+  }
+
+  #[test]
+  fn streaming() {
+    let mut ctx = Ripemd160Context::new();
+    for chunk in b"12345678901234567890123456789012345678901234567890123456789012345678901234567890".chunks(7) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "9b752e45573d4b39f4dbd3323cab82bf63326bfb");
+  }
+}
@@ -0,0 +1,139 @@
+#![allow(non_snake_case)]
+use crate::impl_sha2_hasher;
+use super::hasher::{Hasher, HashContext};
+use super::sha_common::{Block, BlockBuffer, HashValue, CoreLogic};
+use super::sha256::K;
+
+// implementation based on: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+// SHA-224 is SHA-256 w/ a different initial hash value and the digest truncated to 28 bytes
+
+const BLOCK_SIZE: usize = 64;
+const DIGEST_SIZE: usize = 28;
+
+#[derive(Clone)]
+pub struct Sha224();
+
+impl<'a> CoreLogic<
+  'a,
+  u32,
+  64,  // message schedule len
+  BLOCK_SIZE,
+  8,  // length part len
+  7, 18, 3,
+  17, 19, 10,
+  2, 13, 22,
+  6, 11, 25,
+> for Sha224 {
+
+  fn get_K() -> [u32; 64] {
+    K
+  }
+
+  fn get_initial_hash_value() -> HashValue<u32> {
+    HashValue { h: [
+      0xc1059ed8,
+      0x367cd507,
+      0x3070dd17,
+      0xf70e5939,
+      0xffc00b31,
+      0x68581511,
+      0x64f98fa7,
+      0xbefa4fa4,
+    ] }
+  }
+}
+
+impl_sha2_hasher!(Sha224, Sha224Context, u32, BLOCK_SIZE, DIGEST_SIZE);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::hmac::Hmac;
+  use hex::ToHex;
+
+  fn test(msg: &[u8], exp: &str) {
+    let hasher = Sha224();
+    let digest = hasher.get_digest(msg);
+    assert_eq!(digest.encode_hex::<String>(), exp);
+
+    let mut ctx = hasher.new_context();
+    for chunk in msg.chunks(100) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), exp);
+  }
+
+  #[test]
+  fn hash_empty() {
+    test(b"", "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f");
+  }
+
+  #[test]
+  fn hash_abc() {
+    test(b"abc", "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7");
+  }
+
+  // entries of NIST CAVP SHA224ShortMsg.rsp
+  #[test]
+  fn cavp_short_msg() {
+    let test_cases = [
+      ("", "d14a028c2a3a2bc9476102bb288234c415a2b01f828ea62ac5b3e42f"),
+      ("84", "3cd36921df5d6963e73739cf4d20211e2d8877c19cff087ade9d0e3a"),
+      ("5c7b", "daff9bce685eb831f97fc1225b03c275a6c112e2d6e76f5faf7a36e6"),
+      ("51ca3d", "2c8959023515476e38388abb43599a29876b4b33d56adc06032de3a2"),
+      ("6084347e", "ae57c0a6d49739ba338adfa53bdae063e5c09122b77604780a8eeaa3"),
+      ("493e14623c", "7f631f295e024e74552083245ca8f988a3fb65680ae97c3040d2e65c"),
+      ("d729d8cd1631", "342e8e6b23c1c6a54910631f098e08e836259c57e49c1b1d023d166d"),
+      ("5f77b3664823c33e", "bdf21ff325f754157ccf417f4855360a72e8fd117d28c8fe7da3ea38"),
+    ];
+    for (msg, exp) in test_cases {
+      test(&hex::decode(msg).unwrap(), exp);
+    }
+
+    // Len = 512 is padded to 2 blocks
+    test(
+      &hex::decode("a3310ba064be2e14ad32276e18cd0310c933a6e650c3c754d0243c6c61207865b4b65248f66a08edf6e0832689a9dc3a2e5d2095eeea50bd862bac88c8bd318d").unwrap(),
+      "b2a5586d9cbf0baa999157b4af06d88ae08d7c9faab4bc1a96829d65",
+    );
+  }
+
+  // messages and digests of NIST SHA-2 additional examples.
+  // the 56-byte message spills the length into a 2nd block
+  #[test]
+  fn nist_additional_examples() {
+    let test_cases = [
+      (vec![0u8; 56], "5c3e25b69d0ea26f260cfae87e23759e1eca9d1ecc9fbf3c62266804"),
+      (vec![b'Q'; 1000], "3706197f66890a41779dc8791670522e136fafa24874685715bd0a8a"),
+      (vec![b'A'; 1000], "a8d0c66b5c6fdfd836eb3c6d04d32dfe66c3b1f168b488bf4c9c66ce"),
+      (vec![0x99u8; 1005], "cb00ecd03788bf6c0908401e0eb053ac61f35e7e20a2cfd7bd96d640"),
+    ];
+    for (msg, exp) in test_cases {
+      test(&msg, exp);
+    }
+  }
+
+  #[test]
+  fn hash_multi_block() {
+    test(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525");
+    test(b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu", "c97ca9a559850ce97a04a96def6d99a9e0e0e2ab14e6b8df265fc0b3");
+  }
+
+  #[test]
+  fn hash_long_msg() {
+    let msg: Vec<u8> = (0..768u32).map(|x| x as u8).collect();
+    test(&msg, "591b51555cc8c83f6951d9ec73d834d6d17865fecdb543605471a562");
+  }
+
+  #[test]
+  fn hash_a_times_1mil() {
+    test(&[b'a'; 1_000_000], "20794655980c91d8bbb4c1ea97618a4bf03f42581948b2ee4ee7ad67");
+  }
+
+  #[test]
+  fn hmac() {
+    // RFC 4231 test case 2 for SHA-224 and SHA-384
+    let hmac = Hmac::new(Box::new(Sha224()));
+    let digest = hmac.get_digest(b"Jefe", b"what do ya want for nothing?");
+    assert_eq!(digest.encode_hex::<String>(), "a30e01098bc6dbbf45690f3a7e9e6d0f8bbea2a39e6148008fd05e44");
+  }
+}
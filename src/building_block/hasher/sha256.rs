@@ -1,6 +1,7 @@
 #![allow(non_snake_case)]
-use super::hasher::Hasher;
-use super::sha_common::{Block, MessageSchedule, HashValue, CoreLogic};
+use crate::impl_sha2_hasher;
+use super::hasher::{Hasher, HashContext};
+use super::sha_common::{Block, BlockBuffer, MessageSchedule, HashValue, CoreLogic};
 
 // implementation based on: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
 
@@ -30,6 +31,17 @@ impl<'a> MessageSchedule<u32> for Block<'a> {
   }
 }
 
+pub const K: [u32; 64] = [
+  0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+  0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+  0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+  0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+  0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+  0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+  0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+  0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
 #[derive(Clone)]
 pub struct Sha256();
 
@@ -46,16 +58,7 @@ impl<'a> CoreLogic<
 > for Sha256 {
 
   fn get_K() -> [u32; 64] {
-    [
-      0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
-      0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
-      0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
-      0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
-      0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
-      0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
-      0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
-      0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
-    ]
+    K
   }
 
   fn get_initial_hash_value() -> HashValue<u32> {
@@ -72,24 +75,41 @@ impl<'a> CoreLogic<
   }
 }
 
-impl Hasher<DIGEST_SIZE> for Sha256 {
-  fn get_digest(&self, msg: &[u8]) -> [u8; DIGEST_SIZE] {
-    let padded_msg = self.pad_msg(msg);
-    let blocks = Block::parse_padded_msg(&padded_msg, BLOCK_SIZE);
-    let hash_value = self.compute_hash(&blocks);
-    hash_value.to_u8_array()
-  }
-
-  fn get_block_size(&self) -> usize {
-    BLOCK_SIZE
-  }
-}
+impl_sha2_hasher!(Sha256, Sha256Context, u32, BLOCK_SIZE, DIGEST_SIZE);
 
 #[cfg(test)]
 mod tests {
   use super::*;
   use hex::ToHex;
 
+  #[test]
+  fn streaming_matches_one_shot() {
+    let hasher = Sha256();
+    let msg: Vec<u8> = (0..300u32).map(|x| x as u8).collect();
+    for chunk_size in [1, 3, 55, 56, 63, 64, 65, 128, 300] {
+      let mut ctx = Sha256Context::new();
+      for chunk in msg.chunks(chunk_size) {
+        ctx.update(chunk);
+      }
+      assert_eq!(ctx.finalize(), hasher.get_digest(&msg));
+    }
+  }
+
+  #[test]
+  fn streaming_a_times_1mil() {
+    let mut ctx = Sha256Context::new();
+    for _ in 0..1000 {
+      ctx.update(&[b'a'; 1000]);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0");
+
+    // context is reset after finalize
+    ctx.update(b"ab");
+    ctx.update(b"c");
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+  }
+
   #[test]
   fn hash_empty() {
     let hasher = Sha256();
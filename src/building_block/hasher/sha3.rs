@@ -0,0 +1,97 @@
+use crate::impl_keccak_hasher;
+use super::hasher::{Hasher, HashContext};
+
+// implementation based on: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+// SHA3-d is Keccak[c = 2d] w/ suffix bits 01 appended to the message
+
+#[derive(Clone)]
+pub struct Sha3_256();
+
+#[derive(Clone)]
+pub struct Sha3_512();
+
+impl_keccak_hasher!(Sha3_256, Sha3_256Context, 136, 0x06, 32);
+impl_keccak_hasher!(Sha3_512, Sha3_512Context, 72, 0x06, 64);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::hmac::Hmac;
+  use hex::ToHex;
+
+  fn test_256(msg: &[u8], exp: &str) {
+    let digest = Sha3_256().get_digest(msg);
+    assert_eq!(digest.encode_hex::<String>(), exp);
+  }
+
+  fn test_512(msg: &[u8], exp: &str) {
+    let digest = Sha3_512().get_digest(msg);
+    assert_eq!(digest.encode_hex::<String>(), exp);
+  }
+
+  #[test]
+  fn sha3_256_empty() {
+    test_256(b"", "a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a");
+  }
+
+  #[test]
+  fn sha3_256_abc() {
+    test_256(b"abc", "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532");
+  }
+
+  #[test]
+  fn sha3_256_multi_block() {
+    test_256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "41c0dba2a9d6240849100376a8235e2c82e1b9998a999e21db32dd97496d3376");
+    let msg: Vec<u8> = (0..768u32).map(|x| x as u8).collect();
+    test_256(&msg, "c043b2b15d405c9f4cd92fdaef420eba6201d328fb34ec0e2c16e4981b9e4b39");
+  }
+
+  #[test]
+  fn sha3_256_a_times_1mil() {
+    test_256(&[b'a'; 1_000_000], "5c8875ae474a3634ba4fd55ec85bffd661f32aca75c6d699d0cdcb6c115891c1");
+  }
+
+  #[test]
+  fn sha3_512_empty() {
+    test_512(b"", "a69f73cca23a9ac5c8b567dc185a756e97c982164fe25859e0d1dcc1475c80a615b2123af1f5f94c11e3e9402c3ac558f500199d95b6d3e301758586281dcd26");
+  }
+
+  #[test]
+  fn sha3_512_abc() {
+    test_512(b"abc", "b751850b1a57168a5693cd924b6b096e08f621827444f70d884f5d0240d2712e10e116e9192af3c91a7ec57647e3934057340b4cf408d5a56592f8274eec53f0");
+  }
+
+  #[test]
+  fn sha3_512_multi_block() {
+    test_512(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "04a371e84ecfb5b8b77cb48610fca8182dd457ce6f326a0fd3d7ec2f1e91636dee691fbe0c985302ba1b0d8dc78c086346b533b49c030d99a27daf1139d6e75e");
+    let msg: Vec<u8> = (0..768u32).map(|x| x as u8).collect();
+    test_512(&msg, "ad3a11a3430f0fac234a6c15bff0cb609b3d6fde0eac5873893e2775d15cf47d6791b4db907361c53719da248a7662f759b3f6c7ffb4fe69492a9449728cdad7");
+  }
+
+  #[test]
+  fn sha3_512_a_times_1mil() {
+    test_512(&[b'a'; 1_000_000], "3c3a876da14034ab60627c077bb98f7e120a2a5370212dffb3385a18d4f38859ed311d0a9d5141ce9cc5c66ee689b266a8aa18ace8282a0e0db596c90b0a7b87");
+  }
+
+  #[test]
+  fn streaming() {
+    let msg: Vec<u8> = (0..768u32).map(|x| x as u8).collect();
+    let mut ctx = Sha3_512().new_context();
+    for chunk in msg.chunks(71) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), "ad3a11a3430f0fac234a6c15bff0cb609b3d6fde0eac5873893e2775d15cf47d6791b4db907361c53719da248a7662f759b3f6c7ffb4fe69492a9449728cdad7");
+  }
+
+  #[test]
+  fn hmac() {
+    let key = b"Jefe";
+    let data = b"what do ya want for nothing?";
+
+    let digest = Hmac::new(Box::new(Sha3_256())).get_digest(key, data);
+    assert_eq!(digest.encode_hex::<String>(), "c7d4072e788877ae3596bbb0da73b887c9171f93095b294ae857fbe2645e1ba5");
+
+    let digest = Hmac::new(Box::new(Sha3_512())).get_digest(key, data);
+    assert_eq!(digest.encode_hex::<String>(), "5a4bfeab6166427c7a3647b747292b8384537cdb89afb3bf5665e4c5e709350b287baec921fd7ca0ee7a0c31d022a95e1fc92ba9d77df883960275beb4e62024");
+  }
+}
@@ -0,0 +1,131 @@
+#![allow(non_snake_case)]
+use crate::impl_sha2_hasher;
+use super::hasher::{Hasher, HashContext};
+use super::sha_common::{Block, BlockBuffer, HashValue, CoreLogic};
+use super::sha512::K;
+
+// implementation based on: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+// SHA-384 is SHA-512 w/ a different initial hash value and the digest truncated to 48 bytes
+
+const BLOCK_SIZE: usize = 128;
+const DIGEST_SIZE: usize = 48;
+
+#[derive(Clone)]
+pub struct Sha384();
+
+impl<'a> CoreLogic<
+  'a,
+  u64,
+  80,  // message schedule len
+  BLOCK_SIZE,
+  16,  // length part len
+  1, 8, 7,
+  19, 61, 6,
+  28, 34, 39,
+  14, 18, 41,
+> for Sha384 {
+
+  fn get_K() -> [u64; 80] {
+    K
+  }
+
+  fn get_initial_hash_value() -> HashValue<u64> {
+    HashValue { h: [
+      0xcbbb9d5dc1059ed8,
+      0x629a292a367cd507,
+      0x9159015a3070dd17,
+      0x152fecd8f70e5939,
+      0x67332667ffc00b31,
+      0x8eb44a8768581511,
+      0xdb0c2e0d64f98fa7,
+      0x47b5481dbefa4fa4,
+    ] }
+  }
+}
+
+impl_sha2_hasher!(Sha384, Sha384Context, u64, BLOCK_SIZE, DIGEST_SIZE);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::hmac::Hmac;
+  use hex::ToHex;
+
+  fn test(msg: &[u8], exp: &str) {
+    let hasher = Sha384();
+    let digest = hasher.get_digest(msg);
+    assert_eq!(digest.encode_hex::<String>(), exp);
+
+    let mut ctx = hasher.new_context();
+    for chunk in msg.chunks(100) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), exp);
+  }
+
+  #[test]
+  fn hash_empty() {
+    test(b"", "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b");
+  }
+
+  #[test]
+  fn hash_abc() {
+    test(b"abc", "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7");
+  }
+
+  // entries of NIST CAVP SHA384ShortMsg.rsp
+  #[test]
+  fn cavp_short_msg() {
+    let test_cases = [
+      ("", "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b"),
+      ("c5", "b52b72da75d0666379e20f9b4a79c33a329a01f06a2fb7865c9062a28c1de860ba432edfd86b4cb1cb8a75b46076e3b1"),
+      ("6ece", "53d4773da50d8be4145d8f3a7098ff3691a554a29ae6f652cc7121eb8bc96fd2210e06ae2fa2a36c4b3b3497341e70f0"),
+    ];
+    for (msg, exp) in test_cases {
+      test(&hex::decode(msg).unwrap(), exp);
+    }
+  }
+
+  // messages and digests of NIST SHA-2 additional examples.
+  // the messages of 112 bytes or more need a 2nd block for the length
+  #[test]
+  fn nist_additional_examples() {
+    let test_cases = [
+      (vec![0u8; 111], "435770712c611be7293a66dd0dc8d1450dc7ff7337bfe115bf058ef2eb9bed09cee85c26963a5bcc0905dc2df7cc6a76"),
+      (vec![0u8; 112], "3e0cbf3aee0e3aa70415beae1bd12dd7db821efa446440f12132edffce76f635e53526a111491e75ee8e27b9700eec20"),
+      (vec![0u8; 113], "6be9af2cf3cd5dd12c8d9399ec2b34e66034fbd699d4e0221d39074172a380656089caafe8f39963f94cc7c0a07e3d21"),
+      (vec![0u8; 122], "12a72ae4972776b0db7d73d160a15ef0d19645ec96c7f816411ab780c794aa496a22909d941fe671ed3f3caee900bdd5"),
+      (vec![0u8; 1000], "aae017d4ae5b6346dd60a19d52130fb55194b6327dd40b89c11efc8222292de81e1a23c9b59f9f58b7f6ad463fa108ca"),
+      (vec![b'A'; 1000], "7df01148677b7f18617eee3a23104f0eed6bb8c90a6046f715c9445ff43c30d69e9e7082de39c3452fd1d3afd9ba0689"),
+      (vec![b'U'; 1005], "1bb8e256da4a0d1e87453528254f223b4cb7e49c4420dbfa766bba4adba44eeca392ff6a9f565bc347158cc970ce44ec"),
+    ];
+    for (msg, exp) in test_cases {
+      test(&msg, exp);
+    }
+  }
+
+  #[test]
+  fn hash_multi_block() {
+    test(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "3391fdddfc8dc7393707a65b1b4709397cf8b1d162af05abfe8f450de5f36bc6b0455a8520bc4e6f5fe95b1fe3c8452b");
+    test(b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu", "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712fcc7c71a557e2db966c3e9fa91746039");
+  }
+
+  #[test]
+  fn hash_long_msg() {
+    let msg: Vec<u8> = (0..768u32).map(|x| x as u8).collect();
+    test(&msg, "6bee04118003cf5630ccb22cdb53f78dc079231c7d09209955dfc34234cbab03d41da198e30bf1e77ef8b289ddd794ec");
+  }
+
+  #[test]
+  fn hash_a_times_1mil() {
+    test(&[b'a'; 1_000_000], "9d0e1809716474cb086e834e310a4a1ced149e9c00f248527972cec5704c2a5b07b8b3dc38ecc4ebae97ddd87f3d8985");
+  }
+
+  #[test]
+  fn hmac() {
+    // RFC 4231 test case 2 for SHA-224 and SHA-384
+    let hmac = Hmac::new(Box::new(Sha384()));
+    let digest = hmac.get_digest(b"Jefe", b"what do ya want for nothing?");
+    assert_eq!(digest.encode_hex::<String>(), "af45d2e376484031617f78d2b58a6b1b9c7ef464f5a01b47e42ec3736322445e8e2240ca5e69e2c78b3239ecfab21649");
+  }
+}
@@ -0,0 +1,129 @@
+#![allow(non_snake_case)]
+use crate::impl_sha2_hasher;
+use super::hasher::{Hasher, HashContext};
+use super::sha_common::{Block, BlockBuffer, HashValue, CoreLogic};
+use super::sha512::K;
+
+// implementation based on: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+// SHA-512/224 is SHA-512 w/ a different initial hash value and the digest truncated to 28 bytes
+
+const BLOCK_SIZE: usize = 128;
+const DIGEST_SIZE: usize = 28;
+
+#[derive(Clone)]
+pub struct Sha512_224();
+
+impl<'a> CoreLogic<
+  'a,
+  u64,
+  80,  // message schedule len
+  BLOCK_SIZE,
+  16,  // length part len
+  1, 8, 7,
+  19, 61, 6,
+  28, 34, 39,
+  14, 18, 41,
+> for Sha512_224 {
+
+  fn get_K() -> [u64; 80] {
+    K
+  }
+
+  fn get_initial_hash_value() -> HashValue<u64> {
+    HashValue { h: [
+      0x8c3d37c819544da2,
+      0x73e1996689dcd4d6,
+      0x1dfab7ae32ff9c82,
+      0x679dd514582f9fcf,
+      0x0f6d2b697bd44da8,
+      0x77e36f7304c48942,
+      0x3f9d85a86a1d36c8,
+      0x1112e6ad91d692a1,
+    ] }
+  }
+}
+
+impl_sha2_hasher!(Sha512_224, Sha512_224Context, u64, BLOCK_SIZE, DIGEST_SIZE);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::hmac::Hmac;
+  use hex::ToHex;
+
+  fn test(msg: &[u8], exp: &str) {
+    let hasher = Sha512_224();
+    let digest = hasher.get_digest(msg);
+    assert_eq!(digest.encode_hex::<String>(), exp);
+
+    let mut ctx = hasher.new_context();
+    for chunk in msg.chunks(100) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), exp);
+  }
+
+  #[test]
+  fn hash_empty() {
+    test(b"", "6ed0dd02806fa89e25de060c19d3ac86cabb87d6a0ddd05c333b84f4");
+  }
+
+  #[test]
+  fn hash_abc() {
+    test(b"abc", "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa");
+  }
+
+  // entries of NIST CAVP SHA512_224ShortMsg.rsp
+  #[test]
+  fn cavp_short_msg() {
+    let test_cases = [
+      ("", "6ed0dd02806fa89e25de060c19d3ac86cabb87d6a0ddd05c333b84f4"),
+      ("cf", "4199239e87d47b6feda016802bf367fb6e8b5655eff6225cb2668f4a"),
+    ];
+    for (msg, exp) in test_cases {
+      test(&hex::decode(msg).unwrap(), exp);
+    }
+  }
+
+  // messages of NIST SHA-512 additional examples w/ digests computed by OpenSSL.
+  // the messages of 112 bytes or more need a 2nd block for the length
+  #[test]
+  fn nist_additional_examples() {
+    let test_cases = [
+      (vec![0u8; 111], "a23413341d5c14ac3dd1d7136796abe8d0e228f3e4ab4d3ed2c95902"),
+      (vec![0u8; 112], "1fea579628bc0eb589647ec098d5eae4c29d158ea8285ef6ae53810d"),
+      (vec![0u8; 113], "d9b583f4ca8fbc5c582566d356a1ac4285bfc60edcbbfc607ea4ef5a"),
+      (vec![0u8; 122], "c80537aeddb88c3eb9fc5d7d287f571806c9ccdeb7d819260ddf9ae8"),
+      (vec![0u8; 1000], "9109bfe74891b1fdc9ef4947024024fbd702c85df1756a016b136df7"),
+      (vec![b'A'; 1000], "3000c31a7ab8e9c760257073c4d3be370fab6d1d28eb027c6d874f29"),
+      (vec![b'U'; 1005], "9d980f5f97041c4e9b84b2b91c10ad8e8de73635ab8b81071a77c6c6"),
+    ];
+    for (msg, exp) in test_cases {
+      test(&msg, exp);
+    }
+  }
+
+  #[test]
+  fn hash_multi_block() {
+    test(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "e5302d6d54bb242275d1e7622d68df6eb02dedd13f564c13dbda2174");
+    test(b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu", "23fec5bb94d60b23308192640b0c453335d664734fe40e7268674af9");
+  }
+
+  #[test]
+  fn hash_long_msg() {
+    let msg: Vec<u8> = (0..768u32).map(|x| x as u8).collect();
+    test(&msg, "0243909334de5e2efd5a42ff0173e0e87dd54953d04f52119f41add7");
+  }
+
+  #[test]
+  fn hash_a_times_1mil() {
+    test(&[b'a'; 1_000_000], "37ab331d76f0d36de422bd0edeb22a28accd487b7a8453ae965dd287");
+  }
+
+  #[test]
+  fn hmac() {
+    let hmac = Hmac::new(Box::new(Sha512_224()));
+    let digest = hmac.get_digest(b"Jefe", b"what do ya want for nothing?");
+    assert_eq!(digest.encode_hex::<String>(), "4a530b31a79ebcce36916546317c45f247d83241dfb818fd37254bde");
+  }
+}
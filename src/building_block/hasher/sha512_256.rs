@@ -0,0 +1,129 @@
+#![allow(non_snake_case)]
+use crate::impl_sha2_hasher;
+use super::hasher::{Hasher, HashContext};
+use super::sha_common::{Block, BlockBuffer, HashValue, CoreLogic};
+use super::sha512::K;
+
+// implementation based on: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+// SHA-512/256 is SHA-512 w/ a different initial hash value and the digest truncated to 32 bytes
+
+const BLOCK_SIZE: usize = 128;
+const DIGEST_SIZE: usize = 32;
+
+#[derive(Clone)]
+pub struct Sha512_256();
+
+impl<'a> CoreLogic<
+  'a,
+  u64,
+  80,  // message schedule len
+  BLOCK_SIZE,
+  16,  // length part len
+  1, 8, 7,
+  19, 61, 6,
+  28, 34, 39,
+  14, 18, 41,
+> for Sha512_256 {
+
+  fn get_K() -> [u64; 80] {
+    K
+  }
+
+  fn get_initial_hash_value() -> HashValue<u64> {
+    HashValue { h: [
+      0x22312194fc2bf72c,
+      0x9f555fa3c84c64c2,
+      0x2393b86b6f53b151,
+      0x963877195940eabd,
+      0x96283ee2a88effe3,
+      0xbe5e1e2553863992,
+      0x2b0199fc2c85b8aa,
+      0x0eb72ddc81c52ca2,
+    ] }
+  }
+}
+
+impl_sha2_hasher!(Sha512_256, Sha512_256Context, u64, BLOCK_SIZE, DIGEST_SIZE);
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use super::super::hmac::Hmac;
+  use hex::ToHex;
+
+  fn test(msg: &[u8], exp: &str) {
+    let hasher = Sha512_256();
+    let digest = hasher.get_digest(msg);
+    assert_eq!(digest.encode_hex::<String>(), exp);
+
+    let mut ctx = hasher.new_context();
+    for chunk in msg.chunks(100) {
+      ctx.update(chunk);
+    }
+    assert_eq!(ctx.finalize().encode_hex::<String>(), exp);
+  }
+
+  #[test]
+  fn hash_empty() {
+    test(b"", "c672b8d1ef56ed28ab87c3622c5114069bdd3ad7b8f9737498d0c01ecef0967a");
+  }
+
+  #[test]
+  fn hash_abc() {
+    test(b"abc", "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23");
+  }
+
+  // entries of NIST CAVP SHA512_256ShortMsg.rsp
+  #[test]
+  fn cavp_short_msg() {
+    let test_cases = [
+      ("", "c672b8d1ef56ed28ab87c3622c5114069bdd3ad7b8f9737498d0c01ecef0967a"),
+      ("fa", "c4ef36923c64e51e875720e550298a5ab8a3f2f875b1e1a4c9b95babf7344fef"),
+    ];
+    for (msg, exp) in test_cases {
+      test(&hex::decode(msg).unwrap(), exp);
+    }
+  }
+
+  // messages of NIST SHA-512 additional examples w/ digests computed by OpenSSL.
+  // the messages of 112 bytes or more need a 2nd block for the length
+  #[test]
+  fn nist_additional_examples() {
+    let test_cases = [
+      (vec![0u8; 111], "5192ee5471d8a02ffc34bce87142df77aaef777dde522cc171af66e95a006a15"),
+      (vec![0u8; 112], "ae534ff4eb3f2c1e11a16c566148e7aece987752797a8a555b75fb64ff58d54a"),
+      (vec![0u8; 113], "20ce9c21bb5edbffae72135f58bab9fbabb2754614514a72888995c120556552"),
+      (vec![0u8; 122], "2491eba0847e4daf54295002b1f18856582cf1e2ab6e9552847f49d1bc1e1d2d"),
+      (vec![0u8; 1000], "4d7f9c6ab0204db4286fc0bf1ac45f01c2fe656c9650cef1892c2d128cf68221"),
+      (vec![b'A'; 1000], "6ad592c8991fa0fc0fc78b6c2e73f3b55db74afeb1027a5aeacb787fb531e64a"),
+      (vec![b'U'; 1005], "bf1fa2390bab18685fb16564339085bed2b980b8b31dedca9fbc8cc846299f96"),
+    ];
+    for (msg, exp) in test_cases {
+      test(&msg, exp);
+    }
+  }
+
+  #[test]
+  fn hash_multi_block() {
+    test(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq", "bde8e1f9f19bb9fd3406c90ec6bc47bd36d8ada9f11880dbc8a22a7078b6a461");
+    test(b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu", "3928e184fb8690f840da3988121d31be65cb9d3ef83ee6146feac861e19b563a");
+  }
+
+  #[test]
+  fn hash_long_msg() {
+    let msg: Vec<u8> = (0..768u32).map(|x| x as u8).collect();
+    test(&msg, "88f84a27d653b407bc4c1a06b66cff888558af2700f32bb39ee65ee2675688a9");
+  }
+
+  #[test]
+  fn hash_a_times_1mil() {
+    test(&[b'a'; 1_000_000], "9a59a052930187a97038cae692f30708aa6491923ef5194394dc68d56c74fb21");
+  }
+
+  #[test]
+  fn hmac() {
+    let hmac = Hmac::new(Box::new(Sha512_256()));
+    let digest = hmac.get_digest(b"Jefe", b"what do ya want for nothing?");
+    assert_eq!(digest.encode_hex::<String>(), "6df7b24630d5ccb2ee335407081a87188c221489768fa2020513b2d593359456");
+  }
+}
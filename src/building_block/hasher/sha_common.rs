@@ -37,6 +37,59 @@ impl<'a> Block<'a> {
   }
 }
 
+// buffers partial blocks of a message fed incrementally
+#[derive(Clone)]
+pub struct BlockBuffer {
+  buf: Vec<u8>,
+  block_size: usize,
+  msg_len: u128,  // total # of bytes fed so far
+}
+
+impl BlockBuffer {
+  pub fn new(block_size: usize) -> Self {
+    BlockBuffer {
+      buf: Vec::with_capacity(block_size),
+      block_size,
+      msg_len: 0,
+    }
+  }
+
+  // calls process_block for every block completed by msg
+  pub fn update(&mut self, msg: &[u8], mut process_block: impl FnMut(&[u8])) {
+    self.msg_len += msg.len() as u128;
+    let mut msg = msg;
+
+    // fill the partial block first
+    if !self.buf.is_empty() {
+      let n = (self.block_size - self.buf.len()).min(msg.len());
+      self.buf.extend_from_slice(&msg[..n]);
+      msg = &msg[n..];
+      if self.buf.len() < self.block_size {
+        return;
+      }
+      process_block(&self.buf);
+      self.buf.clear();
+    }
+
+    // process the complete blocks directly from msg
+    let mut chunks = msg.chunks_exact(self.block_size);
+    for block in &mut chunks {
+      process_block(block);
+    }
+    self.buf.extend_from_slice(chunks.remainder());
+  }
+
+  pub fn msg_len(&self) -> u128 {
+    self.msg_len
+  }
+
+  // returns the bytes that don't fill a block and resets the buffer
+  pub fn take_tail(&mut self) -> Vec<u8> {
+    self.msg_len = 0;
+    std::mem::take(&mut self.buf)
+  }
+}
+
 pub trait MessageSchedule<U: PrimInt> {
   fn message_schedule(&self, idx: usize) -> U;
 }
@@ -103,48 +156,54 @@ pub trait CoreLogic<
     W.try_into().unwrap()
   }
 
+  // processes a single block and returns the updated hash value
   // using wrapping_add to perform addition in modulo 2^[U bit size]
-  fn compute_hash(&self, blocks: &Vec<Block<'a>>) -> HashValue<U> {
+  fn compress(&self, hash_value: &HashValue<U>, block: &Block<'a>) -> HashValue<U> {
     let mut tmp = [U::default(); 8];
-    let mut hash_value = Self::get_initial_hash_value();
     let K = Self::get_K();
+
+    let mut a: U = hash_value.h[0]; 
+    let mut b: U = hash_value.h[1]; 
+    let mut c: U = hash_value.h[2]; 
+    let mut d: U = hash_value.h[3]; 
+    let mut e: U = hash_value.h[4]; 
+    let mut f: U = hash_value.h[5]; 
+    let mut g: U = hash_value.h[6]; 
+    let mut h: U = hash_value.h[7]; 
+
+    let W = self.prepare_message_schedules(block);
+
+    for t in 0..MSG_SCHE_LEN {
+      let t1 = h.wrapping_add(&Self::uc_sigma_1(e))
+        .wrapping_add(&Self::ch(e, f, g))
+        .wrapping_add(&K[t])
+        .wrapping_add(&W[t]);
+      let t2 = Self::uc_sigma_0(a).wrapping_add(&Self::maj(a, b, c));
+      h = g;
+      g = f;
+      f = e;
+      e = d.wrapping_add(&t1);
+      d = c;
+      c = b;
+      b = a;
+      a = t1.wrapping_add(&t2);
+    }
+
+    tmp[0] = a.wrapping_add(&hash_value.h[0]);
+    tmp[1] = b.wrapping_add(&hash_value.h[1]);
+    tmp[2] = c.wrapping_add(&hash_value.h[2]);
+    tmp[3] = d.wrapping_add(&hash_value.h[3]);
+    tmp[4] = e.wrapping_add(&hash_value.h[4]);
+    tmp[5] = f.wrapping_add(&hash_value.h[5]);
+    tmp[6] = g.wrapping_add(&hash_value.h[6]);
+    tmp[7] = h.wrapping_add(&hash_value.h[7]);
+    HashValue { h: tmp }
+  }
+
+  fn compute_hash(&self, blocks: &Vec<Block<'a>>) -> HashValue<U> {
+    let mut hash_value = Self::get_initial_hash_value();
     for block in blocks {
-      let mut a: U = hash_value.h[0]; 
-      let mut b: U = hash_value.h[1]; 
-      let mut c: U = hash_value.h[2]; 
-      let mut d: U = hash_value.h[3]; 
-      let mut e: U = hash_value.h[4]; 
-      let mut f: U = hash_value.h[5]; 
-      let mut g: U = hash_value.h[6]; 
-      let mut h: U = hash_value.h[7]; 
-
-      let W = self.prepare_message_schedules(block);
-
-      for t in 0..MSG_SCHE_LEN {
-        let t1 = h.wrapping_add(&Self::uc_sigma_1(e))
-          .wrapping_add(&Self::ch(e, f, g))
-          .wrapping_add(&K[t])
-          .wrapping_add(&W[t]);
-        let t2 = Self::uc_sigma_0(a).wrapping_add(&Self::maj(a, b, c));
-        h = g;
-        g = f;
-        f = e;
-        e = d.wrapping_add(&t1);
-        d = c;
-        c = b;
-        b = a;
-        a = t1.wrapping_add(&t2);
-      }
-      
-      tmp[0] = a.wrapping_add(&hash_value.h[0]);
-      tmp[1] = b.wrapping_add(&hash_value.h[1]);
-      tmp[2] = c.wrapping_add(&hash_value.h[2]);
-      tmp[3] = d.wrapping_add(&hash_value.h[3]);
-      tmp[4] = e.wrapping_add(&hash_value.h[4]);
-      tmp[5] = f.wrapping_add(&hash_value.h[5]);
-      tmp[6] = g.wrapping_add(&hash_value.h[6]);
-      tmp[7] = h.wrapping_add(&hash_value.h[7]);
-      hash_value = HashValue { h: tmp };
+      hash_value = self.compress(&hash_value, block);
     }
     hash_value
   }
@@ -155,6 +214,11 @@ pub trait CoreLogic<
   // i.e. l + 1 + k = 448 mod 512 for sha256 or l + 1 + k = 871 mod 1024 for sha512
   // resulting msg will have a length that is a multiple of 512 bits
   fn pad_msg(&self, msg: &[u8]) -> Vec<u8> {
+    self.pad_msg_tail(msg, msg.len() as u128)
+  }
+
+  // pads the trailing part of a message whose total length is msg_len bytes
+  fn pad_msg_tail(&self, msg: &[u8], msg_len: u128) -> Vec<u8> {
     let last_block_max_v_len = BLOCK_SIZE - LENGTH_PART_LEN; 
     let mut v = msg.to_vec();
 
@@ -176,14 +240,78 @@ pub trait CoreLogic<
       v.extend(vec![0u8; k]);
     }
     // append length part to the end 
-    let msg_len: usize = (msg.len() * 8).try_into().unwrap();
-    let msg_len_be = msg_len.to_be_bytes();
+    let msg_len_be = (msg_len * 8).to_be_bytes();
 
     // write msg_len to length part
     let mut length_part = [0u8; LENGTH_PART_LEN];
-    length_part[LENGTH_PART_LEN - msg_len_be.len()..].copy_from_slice(&msg_len_be);
+    length_part.copy_from_slice(&msg_len_be[msg_len_be.len() - LENGTH_PART_LEN..]);
 
     v.extend_from_slice(&length_part);
     v
   }
 }
+
+// implements Hasher and the streaming context for a SHA-2 hasher. the digest is
+// the first DIGEST_SIZE bytes of the final hash value, which truncates the output
+// of SHA-224, SHA-384 and SHA-512/t
+#[macro_export]
+macro_rules! impl_sha2_hasher {
+  ($hasher: ident, $context: ident, $word: ty, $block_size: expr, $digest_size: expr) => {
+    impl Hasher<$digest_size> for $hasher {
+      fn get_digest(&self, msg: &[u8]) -> [u8; $digest_size] {
+        let padded_msg = self.pad_msg(msg);
+        let blocks = Block::parse_padded_msg(&padded_msg, $block_size);
+        let hash_value = self.compute_hash(&blocks);
+        hash_value.to_u8_array()[..$digest_size].try_into().unwrap()
+      }
+
+      fn get_block_size(&self) -> usize {
+        $block_size
+      }
+
+      fn new_context(&self) -> Box<dyn HashContext<$digest_size>> {
+        Box::new($context::new())
+      }
+    }
+
+    #[derive(Clone)]
+    pub struct $context {
+      hasher: $hasher,
+      hash_value: HashValue<$word>,
+      buf: BlockBuffer,
+    }
+
+    impl $context {
+      pub fn new() -> Self {
+        $context {
+          hasher: $hasher(),
+          hash_value: $hasher::get_initial_hash_value(),
+          buf: BlockBuffer::new($block_size),
+        }
+      }
+    }
+
+    impl HashContext<$digest_size> for $context {
+      fn update(&mut self, msg: &[u8]) {
+        let hasher = &self.hasher;
+        let hash_value = &mut self.hash_value;
+        self.buf.update(msg, |data| {
+          *hash_value = hasher.compress(hash_value, &Block { data });
+        });
+      }
+
+      fn finalize(&mut self) -> [u8; $digest_size] {
+        let msg_len = self.buf.msg_len();
+        let tail = self.buf.take_tail();
+        let padded_tail = self.hasher.pad_msg_tail(&tail, msg_len);
+
+        let mut hash_value = self.hash_value.clone();
+        for block in Block::parse_padded_msg(&padded_tail, $block_size) {
+          hash_value = self.hasher.compress(&hash_value, &block);
+        }
+        self.hash_value = $hasher::get_initial_hash_value();
+        hash_value.to_u8_array()[..$digest_size].try_into().unwrap()
+      }
+    }
+  };
+}
@@ -0,0 +1,108 @@
+use super::keccak::KeccakSponge;
+
+// implementation based on: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.202.pdf
+// SHAKE128 and SHAKE256 are extendable-output functions, so they don't implement
+// Hasher, which requires a fixed digest size
+
+const SHAKE_DOMAIN_SEP: u8 = 0x1f;
+
+#[derive(Clone)]
+pub struct Shake128();
+
+#[derive(Clone)]
+pub struct Shake256();
+
+impl Shake128 {
+  pub fn get_output(&self, msg: &[u8], out_len: usize) -> Vec<u8> {
+    let mut ctx = self.new_context();
+    ctx.update(msg);
+    ctx.squeeze(out_len)
+  }
+
+  pub fn new_context(&self) -> ShakeContext {
+    ShakeContext { sponge: KeccakSponge::new(168, SHAKE_DOMAIN_SEP) }
+  }
+}
+
+impl Shake256 {
+  pub fn get_output(&self, msg: &[u8], out_len: usize) -> Vec<u8> {
+    let mut ctx = self.new_context();
+    ctx.update(msg);
+    ctx.squeeze(out_len)
+  }
+
+  pub fn new_context(&self) -> ShakeContext {
+    ShakeContext { sponge: KeccakSponge::new(136, SHAKE_DOMAIN_SEP) }
+  }
+}
+
+// message is fed w/ update. once squeeze is called, the output stream can be
+// read in multiple parts, but no more message can be fed until reset is called
+#[derive(Clone)]
+pub struct ShakeContext {
+  sponge: KeccakSponge,
+}
+
+impl ShakeContext {
+  pub fn update(&mut self, msg: &[u8]) {
+    self.sponge.absorb(msg);
+  }
+
+  pub fn squeeze(&mut self, out_len: usize) -> Vec<u8> {
+    let mut out = vec![0u8; out_len];
+    self.sponge.squeeze(&mut out);
+    out
+  }
+
+  pub fn reset(&mut self) {
+    self.sponge.reset();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use hex::ToHex;
+
+  #[test]
+  fn shake128_empty() {
+    let out = Shake128().get_output(b"", 32);
+    assert_eq!(out.encode_hex::<String>(), "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26");
+  }
+
+  #[test]
+  fn shake256_empty() {
+    let out = Shake256().get_output(b"", 64);
+    assert_eq!(out.encode_hex::<String>(), "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762fd75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be");
+  }
+
+  #[test]
+  fn shake128_output_longer_than_rate() {
+    let out = Shake128().get_output(b"abc", 500);
+    assert_eq!(hex::encode(&out[468..]), "aa3d3b78e3f2061adcdead407085901803ec6f17f0ec650a292198275211a56b");
+  }
+
+  #[test]
+  fn shake256_multi_block() {
+    let msg: Vec<u8> = (0..768u32).map(|x| x as u8).collect();
+    let out = Shake256().get_output(&msg, 64);
+    assert_eq!(out.encode_hex::<String>(), "2c08d3827f9ced84c8263c16ac1d877a70eff56c86a63d3a701ea8cea0ef0b3bf042088081df39105650eccabd3fc225c5f4caffb276b4b53523270e0d13981e");
+  }
+
+  #[test]
+  fn squeeze_in_parts() {
+    let exp = Shake128().get_output(b"abc", 500);
+
+    let mut ctx = Shake128().new_context();
+    ctx.update(b"a");
+    ctx.update(b"bc");
+    let mut out = ctx.squeeze(1);
+    out.extend(ctx.squeeze(167));
+    out.extend(ctx.squeeze(332));
+    assert_eq!(out, exp);
+
+    ctx.reset();
+    ctx.update(b"abc");
+    assert_eq!(ctx.squeeze(500), exp);
+  }
+}
@@ -0,0 +1,237 @@
+use crate::building_block::hasher::{
+  hasher::Hasher,
+  hmac::Hmac,
+};
+use rand::{CryptoRng, RngCore, rngs::OsRng};
+
+// implementation based on: https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-90Ar1.pdf (10.1.2)
+// prediction resistance is not supported. reseed needs to be called explicitly
+
+const MIN_ENTROPY_LEN: usize = 32;  // 256-bit security strength
+const MAX_BYTES_PER_REQUEST: usize = 1 << 16;  // 2^19 bits
+const RESEED_INTERVAL: u64 = 1 << 48;
+
+pub struct HmacDrbg<const N: usize> {
+  hmac: Hmac<N>,
+  k: [u8; N],
+  v: [u8; N],
+  reseed_counter: u64,
+}
+
+impl<const N: usize> HmacDrbg<N> {
+  pub fn new(
+    hasher: Box<dyn Hasher<N>>,
+    entropy: &[u8],
+    nonce: &[u8],
+    pers: &[u8],
+  ) -> Result<Self, String> {
+    if entropy.len() < MIN_ENTROPY_LEN {
+      return Err(format!("Entropy input needs to be at least {} bytes, but got {}", MIN_ENTROPY_LEN, entropy.len()));
+    }
+    let mut drbg = HmacDrbg {
+      hmac: Hmac::new(hasher),
+      k: [0x00; N],
+      v: [0x01; N],
+      reseed_counter: 1,
+    };
+    drbg.update(&[entropy, nonce, pers].concat());
+    Ok(drbg)
+  }
+
+  // instantiates w/ entropy input and nonce taken from the OS
+  pub fn from_entropy(hasher: Box<dyn Hasher<N>>, pers: &[u8]) -> Self {
+    let mut entropy = [0u8; MIN_ENTROPY_LEN];
+    let mut nonce = [0u8; MIN_ENTROPY_LEN / 2];
+    OsRng.fill_bytes(&mut entropy);
+    OsRng.fill_bytes(&mut nonce);
+    Self::new(hasher, &entropy, &nonce, pers).unwrap()
+  }
+
+  // HMAC_DRBG_Update
+  fn update(&mut self, provided_data: &[u8]) {
+    self.k = self.hmac.get_digest(&self.k, &[&self.v[..], &[0x00], provided_data].concat());
+    self.v = self.hmac.get_digest(&self.k, &self.v);
+    if provided_data.is_empty() {
+      return;
+    }
+    self.k = self.hmac.get_digest(&self.k, &[&self.v[..], &[0x01], provided_data].concat());
+    self.v = self.hmac.get_digest(&self.k, &self.v);
+  }
+
+  pub fn reseed(&mut self, entropy: &[u8], additional_input: &[u8]) -> Result<(), String> {
+    if entropy.len() < MIN_ENTROPY_LEN {
+      return Err(format!("Entropy input needs to be at least {} bytes, but got {}", MIN_ENTROPY_LEN, entropy.len()));
+    }
+    self.update(&[entropy, additional_input].concat());
+    self.reseed_counter = 1;
+    Ok(())
+  }
+
+  pub fn generate(&mut self, len: usize, additional_input: &[u8]) -> Result<Vec<u8>, String> {
+    if len > MAX_BYTES_PER_REQUEST {
+      return Err(format!("At most {} bytes can be generated per request, but got {}", MAX_BYTES_PER_REQUEST, len));
+    }
+    if self.reseed_counter > RESEED_INTERVAL {
+      return Err("Reseed required".to_string());
+    }
+    if !additional_input.is_empty() {
+      self.update(additional_input);
+    }
+
+    let mut out = Vec::with_capacity(len + N);
+    while out.len() < len {
+      self.v = self.hmac.get_digest(&self.k, &self.v);
+      out.extend_from_slice(&self.v);
+    }
+    out.truncate(len);
+
+    self.update(additional_input);
+    self.reseed_counter += 1;
+    Ok(out)
+  }
+}
+
+impl<const N: usize> RngCore for HmacDrbg<N> {
+  fn next_u32(&mut self) -> u32 {
+    let mut buf = [0u8; 4];
+    self.fill_bytes(&mut buf);
+    u32::from_le_bytes(buf)
+  }
+
+  fn next_u64(&mut self) -> u64 {
+    let mut buf = [0u8; 8];
+    self.fill_bytes(&mut buf);
+    u64::from_le_bytes(buf)
+  }
+
+  // panics once reseed is required
+  fn fill_bytes(&mut self, dest: &mut [u8]) {
+    self.try_fill_bytes(dest).unwrap()
+  }
+
+  fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+    for chunk in dest.chunks_mut(MAX_BYTES_PER_REQUEST) {
+      let out = self.generate(chunk.len(), &[]).map_err(rand::Error::new)?;
+      chunk.copy_from_slice(&out);
+    }
+    Ok(())
+  }
+}
+
+impl<const N: usize> CryptoRng for HmacDrbg<N> {}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::building_block::hasher::{
+    sha256::Sha256,
+    sha512::Sha512,
+  };
+  use hex::ToHex;
+  use num_bigint::{BigUint, RandBigInt};
+
+  // NIST CAVP HMAC_DRBG.rsp [SHA-256] w/o prediction resistance and reseed.
+  // returned bits are the output of the second generate call
+  fn test_cavp(entropy: &str, nonce: &str, add: [&str; 2], exp: &str) {
+    let entropy = hex::decode(entropy).unwrap();
+    let nonce = hex::decode(nonce).unwrap();
+    let mut drbg = HmacDrbg::new(Box::new(Sha256()), &entropy, &nonce, &[]).unwrap();
+
+    let len = exp.len() / 2;
+    drbg.generate(len, &hex::decode(add[0]).unwrap()).unwrap();
+    let out = drbg.generate(len, &hex::decode(add[1]).unwrap()).unwrap();
+    assert_eq!(out.encode_hex::<String>(), exp);
+  }
+
+  #[test]
+  fn cavp_no_additional_input() {
+    test_cavp(
+      "ca851911349384bffe89de1cbdc46e6831e44d34a4fb935ee285dd14b71a7488",
+      "659ba96c601dc69fc902940805ec0ca8",
+      ["", ""],
+      "e528e9abf2dece54d47c7e75e5fe302149f817ea9fb4bee6f4199697d04d5b89d54fbb978a15b5c443c9ec21036d2460b6f73ebad0dc2aba6e624abf07745bc107694bb7547bb0995f70de25d6b29e2d3011bb19d27676c07162c8b5ccde0668961df86803482cb37ed6d5c0bb8d50cf1f50d476aa0458bdaba806f48be9dcb8",
+    );
+    test_cavp(
+      "d7129e4f47008ad60c9b5d081ff4ca8eb821a6e4deb91608bf4e2647835373a5",
+      "a72882773f78c2fc4878295840a53012",
+      ["", ""],
+      "0cbf48585c5de9183b7ff76557f8fc9ebcfdfde07e588a8641156f61b7952725bbee954f87e9b937513b16bba0f2e523d095114658e00f0f3772175acfcb3240a01de631c19c5a834c94cc58d04a6837f0d2782fa53d2f9f65178ee9c837222494c799e64c60406069bd319549b889fa00a0032dd7ba5b1cc9edbf58de82bfcd",
+    );
+  }
+
+  #[test]
+  fn cavp_additional_input() {
+    test_cavp(
+      "8be0ca6adc8b3870c9d69d6021bc1f1d8eb9e649073d35ee6c5aa0b7e56ad8a5",
+      "9d1265f7d51fdb65377f1e6edd6ae0e4",
+      [
+        "da86167ac997c406bb7979f423986a84ec6614d6caa7afc10aff0699a9b2cf7f",
+        "e4baa3c555950b53e2bfdba480cb4c94b59381bac1e33947e0c22e838a9534cf",
+      ],
+      "64384ecc4ea6b458efc227ca697eac5510092265520c0a0d8a0ccf9ed3ca9d58074671188c6a7ad16d0b050cdc072c125d7298d3a31d9f044a9ee40da0089a84fea28cc7f05f1716db952fad29a0e779635cb7a912a959be67be2f0a4170aace2981802e2ff6467e5b46f0ffbff3b42ba5935fd553c82482ac266acf1cd247d7",
+    );
+  }
+
+  #[test]
+  fn personalization_and_reseed() {
+    let entropy = b"entropy".repeat(5);
+    let mut drbg = HmacDrbg::new(Box::new(Sha256()), &entropy, b"nonce", b"pers").unwrap();
+    let out = drbg.generate(32, &[]).unwrap();
+    assert_eq!(out.encode_hex::<String>(), "df5712902cf7827499173dc4bc14ff9103a38416097f9278ee902e7c893243a6");
+
+    drbg.reseed(&b"more entropy".repeat(3), b"reseed add").unwrap();
+    let out = drbg.generate(32, &[]).unwrap();
+    assert_eq!(out.encode_hex::<String>(), "a95220650f02fc619c31190cd16698f71fd07de5a51c7804edac8a3e1964e175");
+
+    let out = drbg.generate(100, b"add").unwrap();
+    assert_eq!(out.encode_hex::<String>(), "c2b3a32587a0925416e6dade13db531f468a43500e8681393eaa9617b634249c70969476c8b8cfb1baff0b774e39350c38e81f2eb8d74eb5fcc9bae50438303a3b935a025c779f52dcaf283221138e663118f7feb23fbe5506ca9883b106f6f8eb893101");
+  }
+
+  #[test]
+  fn sha512() {
+    let entropy = b"entropy".repeat(5);
+    let mut drbg = HmacDrbg::new(Box::new(Sha512()), &entropy, b"nonce", b"pers").unwrap();
+    let out = drbg.generate(64, &[]).unwrap();
+    assert_eq!(out.encode_hex::<String>(), "53b0cf7a36dd3f317daf75ff2dd6fb54fbc2ba96847733ee018a8aa4aab0ec85edb7e652be8d14bc654876cf370baee462cfc51d6994b1d1ff9a655b4726d5df");
+  }
+
+  #[test]
+  fn invalid_requests() {
+    assert!(HmacDrbg::new(Box::new(Sha256()), &[0u8; 31], &[], &[]).is_err());
+
+    let mut drbg = HmacDrbg::new(Box::new(Sha256()), &[0u8; 32], &[], &[]).unwrap();
+    assert!(drbg.generate(MAX_BYTES_PER_REQUEST + 1, &[]).is_err());
+    assert!(drbg.reseed(&[0u8; 31], &[]).is_err());
+
+    drbg.reseed_counter = RESEED_INTERVAL + 1;
+    assert!(drbg.generate(32, &[]).is_err());
+    drbg.reseed(&[0u8; 32], &[]).unwrap();
+    assert!(drbg.generate(32, &[]).is_ok());
+  }
+
+  #[test]
+  fn rng_core() {
+    let entropy = b"entropy".repeat(5);
+    let mut drbg = HmacDrbg::new(Box::new(Sha256()), &entropy, b"nonce", b"pers").unwrap();
+
+    // fill_bytes is equivalent to generate w/o additional input
+    let mut buf = [0u8; 32];
+    drbg.fill_bytes(&mut buf);
+    assert_eq!(buf.encode_hex::<String>(), "df5712902cf7827499173dc4bc14ff9103a38416097f9278ee902e7c893243a6");
+
+    // same seed results in the same random field element
+    let order = BigUint::parse_bytes(b"73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001", 16).unwrap();
+    let mut drbg1 = HmacDrbg::new(Box::new(Sha256()), &entropy, b"nonce", &[]).unwrap();
+    let mut drbg2 = HmacDrbg::new(Box::new(Sha256()), &entropy, b"nonce", &[]).unwrap();
+    let x = drbg1.gen_biguint_below(&order);
+    assert!(x < order);
+    assert_eq!(x, drbg2.gen_biguint_below(&order));
+  }
+
+  #[test]
+  fn from_entropy() {
+    let mut drbg1 = HmacDrbg::from_entropy(Box::new(Sha256()), &[]);
+    let mut drbg2 = HmacDrbg::from_entropy(Box::new(Sha256()), &[]);
+    assert_ne!(drbg1.generate(32, &[]).unwrap(), drbg2.generate(32, &[]).unwrap());
+  }
+}
@@ -1,3 +1,7 @@
+use crate::building_block::{
+  curves::bls12_381::params::Params as P,
+  random_number::RandomNumber,
+};
 use mcl_rust::*;
 use std::{
   cmp::Ordering,
@@ -12,7 +16,9 @@ use std::{
   },
   hash::{Hash, Hasher},
 };
+use num_bigint::RandBigInt;
 use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
 
 #[derive(Clone)]
 pub struct MclFr {
@@ -38,12 +44,17 @@ impl MclFr {
   }
 
   pub fn rand(exclude_zero: bool) -> Self {
-    let mut v = Fr::zero();
-    while {
-      Fr::set_by_csprng(&mut v);
-      v.is_zero() && exclude_zero
-    } {}
-    MclFr::from(&v)
+    MclFr::rand_with_rng(exclude_zero, &mut RandomNumber::new().gen)
+  }
+
+  pub fn rand_with_rng(exclude_zero: bool, rng: &mut (impl RngCore + CryptoRng)) -> Self {
+    let r = P::subgroup().order();
+    loop {
+      let n = rng.gen_biguint_below(&r);
+      if !exclude_zero || !n.is_zero() {
+        return MclFr::from(n.to_string().as_str());
+      }
+    }
   }
 
   pub fn inc(&mut self) {
@@ -2,7 +2,11 @@ use mcl_rust::*;
 use std::ops::{Add, Mul, Neg, AddAssign};
 use num_traits::Zero;
 use once_cell::sync::Lazy;
-use crate::building_block::mcl::mcl_fr::MclFr;
+use rand::{CryptoRng, RngCore};
+use crate::building_block::{
+  mcl::mcl_fr::MclFr,
+  random_number::RandomNumber,
+};
 
 #[derive(Clone, Debug)]
 pub struct MclG1 {
@@ -33,9 +37,11 @@ impl MclG1 {
   }
 
   pub fn get_random_point() -> MclG1 {
-    let mut v = Fr::zero();
-    v.set_by_csprng();
-    MclG1::g() * MclFr::from(&v)
+    MclG1::get_random_point_with_rng(&mut RandomNumber::new().gen)
+  }
+
+  pub fn get_random_point_with_rng(rng: &mut (impl RngCore + CryptoRng)) -> MclG1 {
+    MclG1::g() * MclFr::rand_with_rng(false, rng)
   }
 }
 
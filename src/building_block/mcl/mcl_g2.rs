@@ -2,7 +2,11 @@ use mcl_rust::*;
 use std::ops::{Add, Mul, Neg, AddAssign};
 use num_traits::Zero;
 use once_cell::sync::Lazy;
-use crate::building_block::mcl::mcl_fr::MclFr;
+use rand::{CryptoRng, RngCore};
+use crate::building_block::{
+  mcl::mcl_fr::MclFr,
+  random_number::RandomNumber,
+};
 
 #[derive(Clone, Debug)]
 pub struct MclG2 {
@@ -33,9 +37,11 @@ impl MclG2 {
   }
 
   pub fn get_random_point() -> MclG2 {
-    let mut v = Fr::zero();
-    v.set_by_csprng();
-    MclG2::g() * MclFr::from(&v)
+    MclG2::get_random_point_with_rng(&mut RandomNumber::new().gen)
+  }
+
+  pub fn get_random_point_with_rng(rng: &mut (impl RngCore + CryptoRng)) -> MclG2 {
+    MclG2::g() * MclFr::rand_with_rng(false, rng)
   }
 
   pub fn hash_and_map(buf: &Vec<u8>) -> MclG2 {
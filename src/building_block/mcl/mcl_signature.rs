@@ -0,0 +1,212 @@
+use crate::building_block::{
+  curves::bls12_381::signature::Scheme,
+  mcl::{
+    mcl_fr::MclFr,
+    mcl_g1::MclG1,
+    mcl_g2::MclG2,
+    pairing::Pairing,
+  },
+};
+use num_traits::Zero;
+use std::collections::HashSet;
+
+// mcl-based counterpart of bls12_381::signature::Signer
+// public keys are in G1 and signatures are in G2
+
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+pub struct MclSigner {
+  g1: MclG1,
+  pairing: Pairing,
+  scheme: Scheme,
+}
+
+impl MclSigner {
+  pub fn new() -> Self {
+    MclSigner::with_scheme(Scheme::Basic)
+  }
+
+  pub fn with_scheme(scheme: Scheme) -> Self {
+    MclSigner {
+      g1: MclG1::g(),
+      pairing: Pairing,
+      scheme,
+    }
+  }
+
+  fn hash_to_point(dst: &[u8], m: &[u8]) -> MclG2 {
+    MclG2::hash_and_map(&[dst, m].concat())
+  }
+
+  // message actually signed under the scheme
+  fn augment(&self, m: &[u8], pk: &MclG1) -> Vec<u8> {
+    match self.scheme {
+      Scheme::MessageAugmentation => [&pk.v.serialize()[..], m].concat(),
+      _ => m.to_vec(),
+    }
+  }
+
+  pub fn gen_public_key(&self, sk: &MclFr) -> MclG1 {
+    &self.g1 * sk
+  }
+
+  pub fn sign(&self, m: &Vec<u8>, sk: &MclFr) -> MclG2 {
+    // public key is derived only when it needs to be prepended to the message
+    let m = match self.scheme {
+      Scheme::MessageAugmentation => self.augment(m, &self.gen_public_key(sk)),
+      _ => m.clone(),
+    };
+    let hash_m = Self::hash_to_point(self.scheme.dst(), &m);
+    hash_m * sk
+  }
+
+  pub fn verify(&self, m: &Vec<u8>, sig: &MclG2, pk: &MclG1) -> bool {
+    if pk.is_zero() || sig.is_zero() {
+      return false;
+    }
+    let hash_m = &Self::hash_to_point(self.scheme.dst(), &self.augment(m, pk));
+    let lhs = self.pairing.e(&self.g1, sig);
+    let rhs = self.pairing.e(pk, hash_m);
+    lhs == rhs
+  }
+
+  pub fn aggregate(&self, sigs: &[MclG2]) -> Result<MclG2, String> {
+    if sigs.is_empty() {
+      return Err("No signatures to aggregate".to_string());
+    }
+    let mut agg_sig = MclG2::zero();
+    for sig in sigs {
+      agg_sig += sig.clone();
+    }
+    Ok(agg_sig)
+  }
+
+  // e(g1, sig) = e(pk_1, H(m_1)) * ... * e(pk_n, H(m_n))
+  pub fn aggregate_verify(&self, pks: &[MclG1], ms: &[Vec<u8>], agg_sig: &MclG2) -> bool {
+    if pks.is_empty() || pks.len() != ms.len() || agg_sig.is_zero() {
+      return false;
+    }
+    if pks.iter().any(|pk| pk.is_zero()) {
+      return false;
+    }
+    if self.scheme == Scheme::Basic {
+      let distinct_ms: HashSet<&Vec<u8>> = ms.iter().collect();
+      if distinct_ms.len() != ms.len() {
+        return false;
+      }
+    }
+
+    let lhs = self.pairing.e(&self.g1, agg_sig);
+    let mut rhs = None;
+    for (pk, m) in pks.iter().zip(ms.iter()) {
+      let hash_m = &Self::hash_to_point(self.scheme.dst(), &self.augment(m, pk));
+      let e = self.pairing.e(pk, hash_m);
+      rhs = Some(match rhs {
+        None => e,
+        Some(acc) => acc * e,
+      });
+    }
+    rhs.map(|rhs| lhs == rhs).unwrap_or(false)
+  }
+
+  // signature on the serialized public key w/ a dedicated DST
+  pub fn pop_prove(&self, sk: &MclFr) -> Result<MclG2, String> {
+    if self.scheme != Scheme::ProofOfPossession {
+      return Err("Proof of possession requires ProofOfPossession scheme".to_string());
+    }
+    let pk = self.gen_public_key(sk);
+    let hash_pk = Self::hash_to_point(POP_DST, &pk.v.serialize());
+    Ok(hash_pk * sk)
+  }
+
+  pub fn pop_verify(&self, pk: &MclG1, proof: &MclG2) -> bool {
+    if self.scheme != Scheme::ProofOfPossession || pk.is_zero() || proof.is_zero() {
+      return false;
+    }
+    let hash_pk = &Self::hash_to_point(POP_DST, &pk.v.serialize());
+    let lhs = self.pairing.e(&self.g1, proof);
+    let rhs = self.pairing.e(pk, hash_pk);
+    lhs == rhs
+  }
+
+  // the public keys need to have been checked w/ pop_verify beforehand
+  pub fn fast_aggregate_verify(&self, pks: &[MclG1], m: &Vec<u8>, agg_sig: &MclG2) -> bool {
+    if self.scheme != Scheme::ProofOfPossession || pks.is_empty() {
+      return false;
+    }
+    let mut agg_pk = MclG1::zero();
+    for pk in pks {
+      agg_pk += pk.clone();
+    }
+    self.verify(m, agg_sig, &agg_pk)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::building_block::mcl::mcl_initializer::MclInitializer;
+
+  #[test]
+  fn sign_and_verify() {
+    MclInitializer::init();
+    let signer = MclSigner::new();
+    let sk = MclFr::rand(true);
+    let pk = signer.gen_public_key(&sk);
+    let m = b"hamburg steak".to_vec();
+
+    let sig = signer.sign(&m, &sk);
+    assert!(signer.verify(&m, &sig, &pk));
+    assert!(!signer.verify(&b"meatball".to_vec(), &sig, &pk));
+  }
+
+  #[test]
+  fn aggregate_verify_distinct_messages() {
+    MclInitializer::init();
+    for scheme in [Scheme::Basic, Scheme::MessageAugmentation] {
+      let signer = MclSigner::with_scheme(scheme);
+      let sks = [MclFr::rand(true), MclFr::rand(true), MclFr::rand(true)];
+      let pks: Vec<MclG1> = sks.iter().map(|sk| signer.gen_public_key(sk)).collect();
+      let ms = vec![b"hamburg steak".to_vec(), b"meatball".to_vec(), b"omurice".to_vec()];
+
+      let sigs: Vec<MclG2> = sks.iter().zip(ms.iter()).map(|(sk, m)| signer.sign(m, sk)).collect();
+      let agg_sig = signer.aggregate(&sigs).unwrap();
+      assert!(signer.aggregate_verify(&pks, &ms, &agg_sig));
+      assert!(!signer.aggregate_verify(&pks[0..2], &ms[0..2], &agg_sig));
+    }
+  }
+
+  #[test]
+  fn basic_scheme_rejects_duplicate_messages() {
+    MclInitializer::init();
+    let signer = MclSigner::new();
+    let sks = [MclFr::rand(true), MclFr::rand(true)];
+    let pks: Vec<MclG1> = sks.iter().map(|sk| signer.gen_public_key(sk)).collect();
+    let ms = vec![b"hamburg steak".to_vec(), b"hamburg steak".to_vec()];
+
+    let sigs: Vec<MclG2> = sks.iter().zip(ms.iter()).map(|(sk, m)| signer.sign(m, sk)).collect();
+    let agg_sig = signer.aggregate(&sigs).unwrap();
+    assert!(!signer.aggregate_verify(&pks, &ms, &agg_sig));
+  }
+
+  #[test]
+  fn proof_of_possession_and_fast_aggregate_verify() {
+    MclInitializer::init();
+    let signer = MclSigner::with_scheme(Scheme::ProofOfPossession);
+    let sks = [MclFr::rand(true), MclFr::rand(true), MclFr::rand(true)];
+    let pks: Vec<MclG1> = sks.iter().map(|sk| signer.gen_public_key(sk)).collect();
+
+    for (sk, pk) in sks.iter().zip(pks.iter()) {
+      let proof = signer.pop_prove(sk).unwrap();
+      assert!(signer.pop_verify(pk, &proof));
+      assert!(!signer.verify(&pk.v.serialize(), &proof, pk));
+    }
+
+    let m = b"hamburg steak".to_vec();
+    let sigs: Vec<MclG2> = sks.iter().map(|sk| signer.sign(&m, sk)).collect();
+    let agg_sig = signer.aggregate(&sigs).unwrap();
+    assert!(signer.fast_aggregate_verify(&pks, &m, &agg_sig));
+    assert!(!signer.fast_aggregate_verify(&pks[1..], &m, &agg_sig));
+    assert!(!MclSigner::new().fast_aggregate_verify(&pks, &m, &agg_sig));
+  }
+}
@@ -3,6 +3,7 @@ pub mod mcl_g2;
 pub mod mcl_gt;
 pub mod mcl_fr;
 pub mod mcl_initializer;
+pub mod mcl_signature;
 pub mod mcl_sparse_matrix;
 pub mod mcl_sparse_vec;
 pub mod pairing;
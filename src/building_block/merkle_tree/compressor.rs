@@ -0,0 +1,73 @@
+use crate::building_block::{
+  field::prime_field_elem::PrimeFieldElem,
+  hasher::{
+    hasher::Hasher,
+    poseidon::Poseidon,
+  },
+};
+
+// two-to-one compression function used to compute the parent of two nodes.
+// leaves are hashed differently from the nodes so that an internal node
+// cannot be passed off as a leaf
+pub trait Compressor<T> {
+  fn hash_leaf(&self, leaf: &T) -> T;
+
+  fn compress(&self, left: &T, right: &T) -> T;
+
+  // node used as is to pad incomplete trees and to fill empty slots of
+  // sparse trees. it is not the hash of any leaf
+  fn empty_leaf(&self) -> T;
+}
+
+// H(0x00 || leaf) for leaves and H(0x01 || left || right) for nodes
+// w/ an all-zero empty leaf
+pub struct HasherCompressor<const N: usize> {
+  hasher: Box<dyn Hasher<N>>,
+}
+
+impl<const N: usize> HasherCompressor<N> {
+  pub fn new(hasher: Box<dyn Hasher<N>>) -> Self {
+    HasherCompressor { hasher }
+  }
+}
+
+impl<const N: usize> Compressor<[u8; N]> for HasherCompressor<N> {
+  fn hash_leaf(&self, leaf: &[u8; N]) -> [u8; N] {
+    self.hasher.get_digest(&[&[0u8][..], &leaf[..]].concat())
+  }
+
+  fn compress(&self, left: &[u8; N], right: &[u8; N]) -> [u8; N] {
+    self.hasher.get_digest(&[&[1u8][..], &left[..], &right[..]].concat())
+  }
+
+  fn empty_leaf(&self) -> [u8; N] {
+    [0u8; N]
+  }
+}
+
+// Poseidon hash of [leaf] for leaves and [left, right] for nodes w/ zero as
+// the empty leaf. the input length is encoded in the capacity element, so
+// the two never collide
+pub struct PoseidonCompressor {
+  poseidon: Poseidon,
+}
+
+impl PoseidonCompressor {
+  pub fn new(poseidon: &Poseidon) -> Self {
+    PoseidonCompressor { poseidon: poseidon.clone() }
+  }
+}
+
+impl Compressor<PrimeFieldElem> for PoseidonCompressor {
+  fn hash_leaf(&self, leaf: &PrimeFieldElem) -> PrimeFieldElem {
+    self.poseidon.hash(std::slice::from_ref(leaf))
+  }
+
+  fn compress(&self, left: &PrimeFieldElem, right: &PrimeFieldElem) -> PrimeFieldElem {
+    self.poseidon.hash(&[left.clone(), right.clone()])
+  }
+
+  fn empty_leaf(&self) -> PrimeFieldElem {
+    self.poseidon.f.elem(&0u8)
+  }
+}
@@ -0,0 +1,314 @@
+use crate::building_block::merkle_tree::compressor::Compressor;
+
+// binary Merkle tree whose hashed leaves are padded w/ the empty leaf to a
+// power of 2. verifiers take the number of leaves from the tree rather than
+// from the proof, so that neither internal nodes nor padding can be proven
+
+pub struct MerkleTree<T, C: Compressor<T>> {
+  compressor: C,
+  levels: Vec<Vec<T>>,  // levels[0] holds the padded leaves and the last level holds the root
+  num_leaves: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct InclusionProof<T> {
+  pub index: usize,
+  pub siblings: Vec<T>,  // from the leaf level to the level below the root
+}
+
+// siblings of all the leaves that cannot be computed from the proven leaves
+#[derive(Clone, Debug)]
+pub struct BatchProof<T> {
+  pub indices: Vec<usize>,  // sorted w/o duplicates
+  pub nodes: Vec<T>,  // ordered by level and then by index
+}
+
+fn depth_of(num_leaves: usize) -> usize {
+  num_leaves.next_power_of_two().trailing_zeros() as usize
+}
+
+impl<T: Clone + PartialEq, C: Compressor<T>> MerkleTree<T, C> {
+  pub fn new(compressor: C, leaves: &[T]) -> Result<Self, String> {
+    if leaves.is_empty() {
+      return Err("At least 1 leaf is required".to_string());
+    }
+    let mut level: Vec<T> = leaves.iter().map(|x| compressor.hash_leaf(x)).collect();
+    level.resize(leaves.len().next_power_of_two(), compressor.empty_leaf());
+
+    let mut levels = vec![level];
+    while levels.last().unwrap().len() > 1 {
+      let next = levels.last().unwrap()
+        .chunks(2)
+        .map(|pair| compressor.compress(&pair[0], &pair[1]))
+        .collect();
+      levels.push(next);
+    }
+    Ok(MerkleTree { compressor, levels, num_leaves: leaves.len() })
+  }
+
+  pub fn root(&self) -> &T {
+    &self.levels.last().unwrap()[0]
+  }
+
+  pub fn depth(&self) -> usize {
+    self.levels.len() - 1
+  }
+
+  pub fn num_leaves(&self) -> usize {
+    self.num_leaves
+  }
+
+  pub fn compressor(&self) -> &C {
+    &self.compressor
+  }
+
+  pub fn prove(&self, index: usize) -> Result<InclusionProof<T>, String> {
+    if index >= self.num_leaves {
+      return Err(format!("Leaf index needs to be less than {}, but got {}", self.num_leaves, index));
+    }
+    let siblings = (0..self.depth())
+      .map(|level| self.levels[level][(index >> level) ^ 1].clone())
+      .collect();
+    Ok(InclusionProof { index, siblings })
+  }
+
+  pub fn prove_batch(&self, indices: &[usize]) -> Result<BatchProof<T>, String> {
+    if indices.is_empty() {
+      return Err("At least 1 leaf index is required".to_string());
+    }
+    let mut indices = indices.to_vec();
+    indices.sort();
+    indices.dedup();
+    if let Some(index) = indices.iter().find(|i| **i >= self.num_leaves) {
+      return Err(format!("Leaf index needs to be less than {}, but got {}", self.num_leaves, index));
+    }
+
+    let mut nodes = vec![];
+    let mut known = indices.clone();
+    for level in 0..self.depth() {
+      let mut next = vec![];
+      let mut i = 0;
+      while i < known.len() {
+        let index = known[i];
+        if i + 1 < known.len() && known[i + 1] == index ^ 1 {
+          i += 2;
+        } else {
+          nodes.push(self.levels[level][index ^ 1].clone());
+          i += 1;
+        }
+        next.push(index >> 1);
+      }
+      known = next;
+    }
+    Ok(BatchProof { indices, nodes })
+  }
+}
+
+impl<T: Clone + PartialEq> InclusionProof<T> {
+  pub fn verify(&self, compressor: &impl Compressor<T>, num_leaves: usize, root: &T, leaf: &T) -> bool {
+    if self.index >= num_leaves || self.siblings.len() != depth_of(num_leaves) {
+      return false;
+    }
+    let mut node = compressor.hash_leaf(leaf);
+    for (level, sibling) in self.siblings.iter().enumerate() {
+      node = if (self.index >> level) & 1 == 0 {
+        compressor.compress(&node, sibling)
+      } else {
+        compressor.compress(sibling, &node)
+      };
+    }
+    &node == root
+  }
+}
+
+impl<T: Clone + PartialEq> BatchProof<T> {
+  // leaves need to be in the order of indices
+  pub fn verify(&self, compressor: &impl Compressor<T>, num_leaves: usize, root: &T, leaves: &[T]) -> bool {
+    if leaves.is_empty() || leaves.len() != self.indices.len() {
+      return false;
+    }
+    if self.indices.windows(2).any(|w| w[0] >= w[1])
+      || self.indices.iter().any(|i| *i >= num_leaves) {
+      return false;
+    }
+
+    let mut nodes = self.nodes.iter();
+    let mut known: Vec<(usize, T)> = self.indices.iter().cloned()
+      .zip(leaves.iter().map(|x| compressor.hash_leaf(x)))
+      .collect();
+    for _ in 0..depth_of(num_leaves) {
+      let mut next = vec![];
+      let mut i = 0;
+      while i < known.len() {
+        let (index, node) = &known[i];
+        let parent = if i + 1 < known.len() && known[i + 1].0 == index ^ 1 {
+          i += 2;
+          compressor.compress(node, &known[i - 1].1)
+        } else {
+          let sibling = match nodes.next() {
+            Some(x) => x,
+            None => return false,
+          };
+          i += 1;
+          if index & 1 == 0 {
+            compressor.compress(node, sibling)
+          } else {
+            compressor.compress(sibling, node)
+          }
+        };
+        next.push((index >> 1, parent));
+      }
+      known = next;
+    }
+    nodes.next().is_none() && &known[0].1 == root
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::building_block::{
+    hasher::{
+      hasher::Hasher,
+      poseidon::Poseidon,
+      sha256::Sha256,
+    },
+    merkle_tree::compressor::{
+      HasherCompressor,
+      PoseidonCompressor,
+    },
+  };
+  use hex::ToHex;
+
+  fn sha256_tree(n: u8) -> (MerkleTree<[u8; 32], HasherCompressor<32>>, Vec<[u8; 32]>) {
+    let leaves: Vec<[u8; 32]> = (0..n).map(|i| Sha256().get_digest(&[i])).collect();
+    let tree = MerkleTree::new(HasherCompressor::new(Box::new(Sha256())), &leaves).unwrap();
+    (tree, leaves)
+  }
+
+  #[test]
+  fn root() {
+    let (tree, _) = sha256_tree(5);
+    assert_eq!(tree.depth(), 3);
+    assert_eq!(tree.num_leaves(), 5);
+    assert_eq!(tree.root().encode_hex::<String>(), "ce0f6244b12890d90d78b340cbac0f08a84dd692296412b0ccba28c7a7ff91f2");
+
+    // root of a single leaf tree is the hashed leaf
+    let (tree, leaves) = sha256_tree(1);
+    assert_eq!(tree.depth(), 0);
+    assert_eq!(tree.root(), &tree.compressor().hash_leaf(&leaves[0]));
+
+    let c = HasherCompressor::new(Box::new(Sha256()));
+    assert!(MerkleTree::new(c, &[]).is_err());
+  }
+
+  #[test]
+  fn inclusion_proof() {
+    let (tree, leaves) = sha256_tree(5);
+    let c = tree.compressor();
+    for (i, leaf) in leaves.iter().enumerate() {
+      let proof = tree.prove(i).unwrap();
+      assert_eq!(proof.siblings.len(), 3);
+      assert!(proof.verify(c, 5, tree.root(), leaf));
+
+      // wrong leaf
+      assert!(!proof.verify(c, 5, tree.root(), &leaves[(i + 1) % 5]));
+
+      // wrong index
+      let mut bad_proof = proof.clone();
+      bad_proof.index ^= 1;
+      assert!(!bad_proof.verify(c, 5, tree.root(), leaf));
+      bad_proof.index = 8;
+      assert!(!bad_proof.verify(c, 5, tree.root(), leaf));
+
+      // wrong number of leaves
+      assert!(!proof.verify(c, 9, tree.root(), leaf));
+    }
+    // padding leaves cannot be proven
+    assert!(tree.prove(5).is_err());
+  }
+
+  #[test]
+  fn forged_inclusion_proof() {
+    let (tree, _) = sha256_tree(5);
+    let c = tree.compressor();
+
+    // internal node at level 1 passed off as a leaf w/ a truncated proof
+    let proof = tree.prove(2).unwrap();
+    let node = tree.levels[1][1];
+    let forged = InclusionProof { index: 1, siblings: proof.siblings[1..].to_vec() };
+    assert!(!forged.verify(c, 5, tree.root(), &node));
+    assert!(!forged.verify(c, 4, tree.root(), &node));
+
+    // padding slot proven w/ the empty leaf
+    let siblings = (0..3).map(|level| tree.levels[level][(5 >> level) ^ 1]).collect();
+    let forged = InclusionProof { index: 5, siblings };
+    assert!(!forged.verify(c, 5, tree.root(), &[0u8; 32]));
+    assert!(!forged.verify(c, 8, tree.root(), &[0u8; 32]));
+
+    // batch proof of an internal node
+    let proof = tree.prove_batch(&[2, 3]).unwrap();
+    let forged = BatchProof { indices: vec![1], nodes: proof.nodes.clone() };
+    assert!(!forged.verify(c, 5, tree.root(), &[node]));
+    assert!(!forged.verify(c, 4, tree.root(), &[node]));
+  }
+
+  #[test]
+  fn batch_proof() {
+    let (tree, leaves) = sha256_tree(7);
+    let c = tree.compressor();
+
+    let index_sets: [&[usize]; 5] = [&[0], &[0, 1], &[4, 0, 3], &[6, 5, 1, 2], &[0, 1, 2, 3, 4, 5, 6]];
+    for indices in index_sets {
+      let proof = tree.prove_batch(indices).unwrap();
+      let proven_leaves: Vec<[u8; 32]> = proof.indices.iter().map(|i| leaves[*i]).collect();
+      assert!(proof.verify(c, 7, tree.root(), &proven_leaves));
+
+      // wrong leaf
+      let mut bad_leaves = proven_leaves.clone();
+      bad_leaves[0] = [0u8; 32];
+      assert!(!proof.verify(c, 7, tree.root(), &bad_leaves));
+
+      // missing leaf
+      assert!(!proof.verify(c, 7, tree.root(), &proven_leaves[1..]));
+    }
+
+    // siblings shared by the proven leaves are not included
+    let proof = tree.prove_batch(&[0, 1, 2, 3]).unwrap();
+    assert_eq!(proof.nodes.len(), 1);
+
+    // duplicates are removed
+    let proof = tree.prove_batch(&[3, 3, 1]).unwrap();
+    assert_eq!(proof.indices, vec![1, 3]);
+    assert!(proof.verify(c, 7, tree.root(), &[leaves[1], leaves[3]]));
+
+    // extra node
+    let mut bad_proof = proof.clone();
+    bad_proof.nodes.push([0u8; 32]);
+    assert!(!bad_proof.verify(c, 7, tree.root(), &[leaves[1], leaves[3]]));
+
+    // index of a padding leaf
+    let mut bad_proof = proof.clone();
+    bad_proof.indices = vec![1, 7];
+    assert!(!bad_proof.verify(c, 7, tree.root(), &[leaves[1], [0u8; 32]]));
+
+    assert!(tree.prove_batch(&[]).is_err());
+    assert!(tree.prove_batch(&[1, 7]).is_err());
+  }
+
+  #[test]
+  fn poseidon_compressor() {
+    let poseidon = Poseidon::bls12_381_fr();
+    let f = &poseidon.f;
+    let leaves: Vec<_> = (1..=3u8).map(|i| f.elem(&i)).collect();
+    let tree = MerkleTree::new(PoseidonCompressor::new(&poseidon), &leaves).unwrap();
+
+    let hashed: Vec<_> = leaves.iter().map(|x| poseidon.hash(std::slice::from_ref(x))).collect();
+    let left = poseidon.hash(&[hashed[0].clone(), hashed[1].clone()]);
+    let right = poseidon.hash(&[hashed[2].clone(), f.elem(&0u8)]);
+    assert_eq!(tree.root(), &poseidon.hash(&[left, right]));
+
+    let proof = tree.prove(2).unwrap();
+    assert!(proof.verify(tree.compressor(), 3, tree.root(), &leaves[2]));
+  }
+}
@@ -0,0 +1,3 @@
+pub mod compressor;
+pub mod merkle_tree;
+pub mod sparse_merkle_tree;
@@ -0,0 +1,281 @@
+use crate::building_block::merkle_tree::compressor::Compressor;
+use num_bigint::BigUint;
+use std::collections::HashMap;
+
+// Merkle tree of a fixed depth w/ 2^depth leaves that are initially empty.
+// inserted values are hashed while empty slots hold the empty leaf as is.
+// only the nodes that differ from the root of an empty subtree of the same
+// height are stored
+
+pub struct SparseMerkleTree<T, C: Compressor<T>> {
+  compressor: C,
+  depth: usize,
+  defaults: Vec<T>,  // defaults[h] is the root of an empty subtree of height h
+  nodes: HashMap<(usize, BigUint), T>,  // non-default nodes keyed by (height, index)
+}
+
+#[derive(Clone, Debug)]
+pub struct SparseMerkleProof<T> {
+  pub key: BigUint,
+  pub siblings: Vec<T>,  // from the leaf level to the level below the root
+}
+
+impl<T: Clone + PartialEq, C: Compressor<T>> SparseMerkleTree<T, C> {
+  pub fn new(compressor: C, depth: usize) -> Result<Self, String> {
+    if depth == 0 {
+      return Err("Depth needs to be at least 1".to_string());
+    }
+    let mut defaults = vec![compressor.empty_leaf()];
+    for h in 0..depth {
+      defaults.push(compressor.compress(&defaults[h], &defaults[h]));
+    }
+    Ok(SparseMerkleTree {
+      compressor,
+      depth,
+      defaults,
+      nodes: HashMap::new(),
+    })
+  }
+
+  pub fn depth(&self) -> usize {
+    self.depth
+  }
+
+  pub fn compressor(&self) -> &C {
+    &self.compressor
+  }
+
+  fn validate_key(&self, key: &BigUint) -> Result<(), String> {
+    if key.bits() as usize > self.depth {
+      return Err(format!("Key needs to be less than 2^{}, but got {}", self.depth, key));
+    }
+    Ok(())
+  }
+
+  fn node(&self, height: usize, index: &BigUint) -> T {
+    match self.nodes.get(&(height, index.clone())) {
+      Some(x) => x.clone(),
+      None => self.defaults[height].clone(),
+    }
+  }
+
+  fn set_node(&mut self, height: usize, index: &BigUint, node: T) {
+    if node == self.defaults[height] {
+      self.nodes.remove(&(height, index.clone()));
+    } else {
+      self.nodes.insert((height, index.clone()), node);
+    }
+  }
+
+  fn sibling_index(index: &BigUint) -> BigUint {
+    if index.bit(0) { index - 1u8 } else { index + 1u8 }
+  }
+
+  pub fn root(&self) -> T {
+    self.node(self.depth, &BigUint::from(0u8))
+  }
+
+  // returns the hashed value or the empty leaf if nothing has been inserted
+  // at the key
+  pub fn get(&self, key: &BigUint) -> Result<T, String> {
+    self.validate_key(key)?;
+    Ok(self.node(0, key))
+  }
+
+  pub fn insert(&mut self, key: &BigUint, value: &T) -> Result<(), String> {
+    let leaf = self.compressor.hash_leaf(value);
+    self.set_leaf(key, leaf)
+  }
+
+  fn set_leaf(&mut self, key: &BigUint, leaf: T) -> Result<(), String> {
+    self.validate_key(key)?;
+    let mut index = key.clone();
+    let mut node = leaf;
+    self.set_node(0, &index, node.clone());
+
+    for h in 0..self.depth {
+      let sibling = self.node(h, &SparseMerkleTree::<T, C>::sibling_index(&index));
+      node = if index.bit(0) {
+        self.compressor.compress(&sibling, &node)
+      } else {
+        self.compressor.compress(&node, &sibling)
+      };
+      index >>= 1;
+      self.set_node(h + 1, &index, node.clone());
+    }
+    Ok(())
+  }
+
+  pub fn remove(&mut self, key: &BigUint) -> Result<(), String> {
+    let empty_leaf = self.compressor.empty_leaf();
+    self.set_leaf(key, empty_leaf)
+  }
+
+  // proves the leaf at the key, which is empty for keys not inserted
+  pub fn prove(&self, key: &BigUint) -> Result<SparseMerkleProof<T>, String> {
+    self.validate_key(key)?;
+    let mut index = key.clone();
+    let mut siblings = Vec::with_capacity(self.depth);
+    for h in 0..self.depth {
+      siblings.push(self.node(h, &SparseMerkleTree::<T, C>::sibling_index(&index)));
+      index >>= 1;
+    }
+    Ok(SparseMerkleProof { key: key.clone(), siblings })
+  }
+}
+
+impl<T: Clone + PartialEq> SparseMerkleProof<T> {
+  // depth is that of the tree rather than the length of the proof
+  pub fn verify(&self, compressor: &impl Compressor<T>, depth: usize, root: &T, value: &T) -> bool {
+    self.verify_leaf(compressor, depth, root, &compressor.hash_leaf(value))
+  }
+
+  // the key is not in the tree if the leaf at the key is empty
+  pub fn verify_non_membership(&self, compressor: &impl Compressor<T>, depth: usize, root: &T) -> bool {
+    self.verify_leaf(compressor, depth, root, &compressor.empty_leaf())
+  }
+
+  fn verify_leaf(&self, compressor: &impl Compressor<T>, depth: usize, root: &T, leaf: &T) -> bool {
+    if self.siblings.len() != depth || self.key.bits() as usize > depth {
+      return false;
+    }
+    let mut node = leaf.clone();
+    for (h, sibling) in self.siblings.iter().enumerate() {
+      node = if self.key.bit(h as u64) {
+        compressor.compress(sibling, &node)
+      } else {
+        compressor.compress(&node, sibling)
+      };
+    }
+    &node == root
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::building_block::{
+    field::prime_field_elem::PrimeFieldElem,
+    hasher::{
+      hasher::Hasher,
+      poseidon::Poseidon,
+      sha256::Sha256,
+    },
+    merkle_tree::compressor::{
+      HasherCompressor,
+      PoseidonCompressor,
+    },
+  };
+
+  fn sha256_compressor() -> HasherCompressor<32> {
+    HasherCompressor::new(Box::new(Sha256()))
+  }
+
+  // root computed from all the nodes at the leaf level
+  fn full_root(c: &HasherCompressor<32>, leaves: &[[u8; 32]]) -> [u8; 32] {
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+      level = level.chunks(2).map(|pair| c.compress(&pair[0], &pair[1])).collect();
+    }
+    level[0]
+  }
+
+  #[test]
+  fn matches_full_tree() {
+    let c = &sha256_compressor();
+    let mut smt = SparseMerkleTree::new(sha256_compressor(), 8).unwrap();
+    let mut leaves = vec![[0u8; 32]; 256];
+
+    // empty tree
+    assert_eq!(smt.root(), full_root(c, &leaves));
+
+    for key in [3u8, 100, 255, 0] {
+      let value = Sha256().get_digest(&[key]);
+      smt.insert(&BigUint::from(key), &value).unwrap();
+      leaves[key as usize] = c.hash_leaf(&value);
+      assert_eq!(smt.root(), full_root(c, &leaves));
+    }
+
+    // removal restores the previous root
+    let root = smt.root();
+    smt.insert(&BigUint::from(7u8), &[1u8; 32]).unwrap();
+    assert_ne!(smt.root(), root);
+    smt.remove(&BigUint::from(7u8)).unwrap();
+    assert_eq!(smt.root(), root);
+  }
+
+  #[test]
+  fn membership_and_non_membership() {
+    let mut smt = SparseMerkleTree::new(sha256_compressor(), 256).unwrap();
+    let c = &sha256_compressor();
+
+    let keys: Vec<BigUint> = [b"alice", b"bob  "].iter()
+      .map(|x| BigUint::from_bytes_be(&Sha256().get_digest(*x)))
+      .collect();
+    let values: Vec<[u8; 32]> = [b"10", b"20"].iter().map(|x| Sha256().get_digest(*x)).collect();
+    for (key, value) in keys.iter().zip(values.iter()) {
+      smt.insert(key, value).unwrap();
+    }
+    let root = &smt.root();
+
+    for (key, value) in keys.iter().zip(values.iter()) {
+      assert_eq!(smt.get(key).unwrap(), c.hash_leaf(value));
+      let proof = smt.prove(key).unwrap();
+      assert!(proof.verify(c, 256, root, value));
+      assert!(!proof.verify(c, 256, root, &[0u8; 32]));
+      assert!(!proof.verify_non_membership(c, 256, root));
+    }
+
+    let absent = BigUint::from_bytes_be(&Sha256().get_digest(b"carol"));
+    assert_eq!(smt.get(&absent).unwrap(), [0u8; 32]);
+    let proof = smt.prove(&absent).unwrap();
+    assert!(proof.verify_non_membership(c, 256, root));
+    assert!(!proof.verify(c, 256, root, &values[0]));
+    assert!(!proof.verify(c, 256, root, &[0u8; 32]));
+
+    // only the nodes on the paths of the inserted keys are stored
+    assert!(smt.nodes.len() <= 2 * 257);
+
+    let too_large = BigUint::from(1u8) << 256;
+    assert!(smt.insert(&too_large, &values[0]).is_err());
+    assert!(smt.prove(&too_large).is_err());
+    assert!(SparseMerkleTree::new(sha256_compressor(), 0).is_err());
+  }
+
+  #[test]
+  fn poseidon_compressor() {
+    let poseidon = Poseidon::bls12_381_fr();
+    let f = &poseidon.f;
+    let c = &PoseidonCompressor::new(&poseidon);
+    let mut smt = SparseMerkleTree::new(PoseidonCompressor::new(&poseidon), 16).unwrap();
+
+    let key = BigUint::from(12345u16);
+    let value: PrimeFieldElem = f.elem(&42u8);
+    smt.insert(&key, &value).unwrap();
+
+    let root = &smt.root();
+    assert!(smt.prove(&key).unwrap().verify(c, 16, root, &value));
+    assert!(smt.prove(&BigUint::from(12344u16)).unwrap().verify_non_membership(c, 16, root));
+  }
+
+  #[test]
+  fn forged_proof() {
+    let c = &sha256_compressor();
+    let mut smt = SparseMerkleTree::new(sha256_compressor(), 8).unwrap();
+    let value = Sha256().get_digest(b"value");
+    smt.insert(&BigUint::from(6u8), &value).unwrap();
+    let root = &smt.root();
+
+    // internal node at height 1 passed off as a leaf w/ a truncated proof
+    let proof = smt.prove(&BigUint::from(6u8)).unwrap();
+    let node = smt.node(1, &BigUint::from(3u8));
+    let forged = SparseMerkleProof { key: BigUint::from(3u8), siblings: proof.siblings[1..].to_vec() };
+    assert!(!forged.verify(c, 8, root, &node));
+    assert!(!forged.verify(c, 7, root, &node));
+
+    // longer proof
+    let mut forged = proof.clone();
+    forged.siblings.push([0u8; 32]);
+    assert!(!forged.verify(c, 8, root, &value));
+  }
+}
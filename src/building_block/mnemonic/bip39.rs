@@ -0,0 +1,212 @@
+use crate::building_block::hasher::{
+  hasher::Hasher,
+  pbkdf2::Pbkdf2,
+  sha256::Sha256,
+  sha512::Sha512,
+};
+use once_cell::sync::Lazy;
+
+// implementation based on: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+// passphrase is expected to be NFKD-normalized by the caller
+
+static WORDLIST: Lazy<Vec<&'static str>> = Lazy::new(|| {
+  include_str!("english.txt").lines().collect()
+});
+
+const BITS_PER_WORD: usize = 11;
+const PBKDF2_ROUNDS: u32 = 2048;
+const SEED_LEN: usize = 64;
+
+pub struct Bip39();
+
+impl Bip39 {
+  fn to_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes.iter().flat_map(|b| (0..8).rev().map(move |i| (b >> i) & 1 == 1)).collect()
+  }
+
+  fn checksum_bits(entropy: &[u8]) -> Vec<bool> {
+    let checksum_len = entropy.len() * 8 / 32;
+    let digest = Sha256().get_digest(entropy);
+    Self::to_bits(&digest)[0..checksum_len].to_vec()
+  }
+
+  pub fn entropy_to_mnemonic(entropy: &[u8]) -> Result<String, String> {
+    // entropy needs to be 128-256 bits and a multiple of 32 bits
+    if entropy.len() < 16 || entropy.len() > 32 || entropy.len() % 4 != 0 {
+      return Err(format!("Invalid entropy length: {} bytes", entropy.len()));
+    }
+
+    // entropy || first ENT/32 bits of sha256(entropy)
+    let mut bits = Self::to_bits(entropy);
+    bits.extend(Self::checksum_bits(entropy));
+
+    let words: Vec<&str> = bits.chunks(BITS_PER_WORD).map(|chunk| {
+      let index = chunk.iter().fold(0usize, |acc, bit| (acc << 1) | *bit as usize);
+      WORDLIST[index]
+    }).collect();
+
+    Ok(words.join(" "))
+  }
+
+  // recovers the entropy after validating the word count, words and checksum
+  pub fn mnemonic_to_entropy(mnemonic: &str) -> Result<Vec<u8>, String> {
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    if words.len() < 12 || words.len() > 24 || words.len() % 3 != 0 {
+      return Err(format!("Invalid number of words: {}", words.len()));
+    }
+
+    let mut bits: Vec<bool> = vec![];
+    for word in &words {
+      // wordlist is sorted
+      let index = match WORDLIST.binary_search(word) {
+        Ok(x) => x,
+        Err(_) => return Err(format!("Unknown word: {}", word)),
+      };
+      bits.extend((0..BITS_PER_WORD).rev().map(|i| (index >> i) & 1 == 1));
+    }
+
+    let checksum_len = bits.len() / 33;
+    let entropy_len = bits.len() - checksum_len;
+    let entropy: Vec<u8> = bits[0..entropy_len].chunks(8).map(|chunk| {
+      chunk.iter().fold(0u8, |acc, bit| (acc << 1) | *bit as u8)
+    }).collect();
+
+    if Self::checksum_bits(&entropy) != bits[entropy_len..] {
+      return Err("Checksum mismatch".to_string());
+    }
+    Ok(entropy)
+  }
+
+  pub fn validate(mnemonic: &str) -> bool {
+    Self::mnemonic_to_entropy(mnemonic).is_ok()
+  }
+
+  pub fn mnemonic_to_seed(mnemonic: &str, passphrase: &str) -> Result<[u8; SEED_LEN], String> {
+    Self::mnemonic_to_entropy(mnemonic)?;
+
+    // PBKDF2-HMAC-SHA512 w/ mnemonic as password and "mnemonic" || passphrase as salt
+    let mnemonic = mnemonic.split_whitespace().collect::<Vec<&str>>().join(" ");
+    let salt = format!("mnemonic{}", passphrase);
+    let pbkdf2 = Pbkdf2::new(Box::new(Sha512()));
+    let seed = pbkdf2.derive_key(mnemonic.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, SEED_LEN)?;
+    Ok(seed.try_into().unwrap())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::building_block::curves::secp256k1::bip32::ExtendedPrivateKey;
+
+  // https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+  const TREZOR_VECTORS: [(&str, &str, &str, &str); 10] = [
+    (
+      "00000000000000000000000000000000",
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+      "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+      "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF",
+    ),
+    (
+      "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+      "legal winner thank year wave sausage worth useful legal winner thank yellow",
+      "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+      "xprv9s21ZrQH143K2gA81bYFHqU68xz1cX2APaSq5tt6MFSLeXnCKV1RVUJt9FWNTbrrryem4ZckN8k4Ls1H6nwdvDTvnV7zEXs2HgPezuVccsq",
+    ),
+    (
+      "ffffffffffffffffffffffffffffffff",
+      "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+      "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069",
+      "xprv9s21ZrQH143K2V4oox4M8Zmhi2Fjx5XK4Lf7GKRvPSgydU3mjZuKGCTg7UPiBUD7ydVPvSLtg9hjp7MQTYsW67rZHAXeccqYqrsx8LcXnyd",
+    ),
+    (
+      "000000000000000000000000000000000000000000000000",
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon agent",
+      "035895f2f481b1b0f01fcf8c289c794660b289981a78f8106447707fdd9666ca06da5a9a565181599b79f53b844d8a71dd9f439c52a3d7b3e8a79c906ac845fa",
+      "xprv9s21ZrQH143K3mEDrypcZ2usWqFgzKB6jBBx9B6GfC7fu26X6hPRzVjzkqkPvDqp6g5eypdk6cyhGnBngbjeHTe4LsuLG1cCmKJka5SMkmU",
+    ),
+    (
+      "808080808080808080808080808080808080808080808080",
+      "letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter always",
+      "107d7c02a5aa6f38c58083ff74f04c607c2d2c0ecc55501dadd72d025b751bc27fe913ffb796f841c49b1d33b610cf0e91d3aa239027f5e99fe4ce9e5088cd65",
+      "xprv9s21ZrQH143K3VPCbxbUtpkh9pRG371UCLDz3BjceqP1jz7XZsQ5EnNkYAEkfeZp62cDNj13ZTEVG1TEro9sZ9grfRmcYWLBhCocViKEJae",
+    ),
+    (
+      "0000000000000000000000000000000000000000000000000000000000000000",
+      "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+      "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8",
+      "xprv9s21ZrQH143K32qBagUJAMU2LsHg3ka7jqMcV98Y7gVeVyNStwYS3U7yVVoDZ4btbRNf4h6ibWpY22iRmXq35qgLs79f312g2kj5539ebPM",
+    ),
+    (
+      "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+      "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+      "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+      "xprv9s21ZrQH143K2WFF16X85T2QCpndrGwx6GueB72Zf3AHwHJaknRXNF37ZmDrtHrrLSHvbuRejXcnYxoZKvRquTPyp2JiNG3XcjQyzSEgqCB",
+    ),
+    (
+      "9e885d952ad362caeb4efe34a8e91bd2",
+      "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic",
+      "274ddc525802f7c828d8ef7ddbcdc5304e87ac3535913611fbbfa986d0c9e5476c91689f9c8a54fd55bd38606aa6a8595ad213d4c9c9f9aca3fb217069a41028",
+      "xprv9s21ZrQH143K2oZ9stBYpoaZ2ktHj7jLz7iMqpgg1En8kKFTXJHsjxry1JbKH19YrDTicVwKPehFKTbmaxgVEc5TpHdS1aYhB2s9aFJBeJH",
+    ),
+    (
+      "6610b25967cdcca9d59875f5cb50b0ea75433311869e930b",
+      "gravity machine north sort system female filter attitude volume fold club stay feature office ecology stable narrow fog",
+      "628c3827a8823298ee685db84f55caa34b5cc195a778e52d45f59bcf75aba68e4d7590e101dc414bc1bbd5737666fbbef35d1f1903953b66624f910feef245ac",
+      "xprv9s21ZrQH143K3uT8eQowUjsxrmsA9YUuQQK1RLqFufzybxD6DH6gPY7NjJ5G3EPHjsWDrs9iivSbmvjc9DQJbJGatfa9pv4MZ3wjr8qWPAK",
+    ),
+    (
+      "68a79eaca2324873eacc50cb9c6eca8cc68ea5d936f98787c60c7ebc74e6ce7c",
+      "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length",
+      "64c87cde7e12ecf6704ab95bb1408bef047c22db4cc7491c4271d170a1b213d20b385bc1588d9c7b38f1b39d415665b8a9030c9ec653d75e65f847d8fc1fc440",
+      "xprv9s21ZrQH143K2XTAhys3pMNcGn261Fi5Ta2Pw8PwaVPhg3D8DWkzWQwjTJfskj8ofb81i9NP2cUNKxwjueJHHMQAnxtivTA75uUFqPFeWzk",
+    ),
+  ];
+
+  #[test]
+  fn wordlist() {
+    assert_eq!(WORDLIST.len(), 2048);
+    assert_eq!(WORDLIST[0], "abandon");
+    assert_eq!(WORDLIST[2047], "zoo");
+  }
+
+  #[test]
+  fn entropy_to_mnemonic() {
+    for (entropy, mnemonic, _, _) in TREZOR_VECTORS {
+      let entropy = hex::decode(entropy).unwrap();
+      assert_eq!(Bip39::entropy_to_mnemonic(&entropy).unwrap(), mnemonic);
+      assert_eq!(Bip39::mnemonic_to_entropy(mnemonic).unwrap(), entropy);
+    }
+  }
+
+  #[test]
+  fn mnemonic_to_seed() {
+    for (_, mnemonic, seed, xprv) in TREZOR_VECTORS {
+      let seed_bytes = Bip39::mnemonic_to_seed(mnemonic, "TREZOR").unwrap();
+      assert_eq!(hex::encode(seed_bytes), seed);
+
+      let master = ExtendedPrivateKey::from_seed(&seed_bytes).unwrap();
+      assert_eq!(master.serialize(), xprv);
+    }
+  }
+
+  #[test]
+  fn invalid_entropy_len() {
+    assert!(Bip39::entropy_to_mnemonic(&[0u8; 12]).is_err());
+    assert!(Bip39::entropy_to_mnemonic(&[0u8; 17]).is_err());
+    assert!(Bip39::entropy_to_mnemonic(&[0u8; 36]).is_err());
+  }
+
+  #[test]
+  fn validate() {
+    assert!(Bip39::validate("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"));
+    // bad checksum
+    assert!(!Bip39::validate("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"));
+    // unknown word
+    assert!(!Bip39::validate("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abou"));
+    // invalid number of words
+    assert!(!Bip39::validate("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"));
+    assert!(!Bip39::validate(""));
+
+    assert!(Bip39::mnemonic_to_seed("zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo", "").is_err());
+  }
+}
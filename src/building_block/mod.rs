@@ -1,9 +1,14 @@
+pub mod base58;
 // pub mod elliptic_curve;
 pub mod field;
 pub mod hasher;
+pub mod hmac_drbg;
 pub mod mcl;
+pub mod merkle_tree;
+pub mod mnemonic;
 pub mod random_number;
 pub mod curves;
 pub mod to_bigint;
+pub mod transcript;
 pub mod to_biguint;
 pub mod zero;
@@ -0,0 +1,203 @@
+use crate::building_block::{
+  curves::{
+    bls12_381::g1_point::G1Point,
+    curve25519::ristretto255::RistrettoPoint,
+    secp256k1::affine_point::AffinePoint,
+  },
+  field::{
+    prime_field::PrimeField,
+    prime_field_elem::PrimeFieldElem,
+  },
+  hasher::{
+    hasher::Hasher,
+    sha256::Sha256,
+  },
+};
+use num_bigint::BigUint;
+use std::sync::Arc;
+
+// Fiat-Shamir transcript that turns a public-coin interactive protocol into
+// a non-interactive one. every message is framed by its label and length so
+// that different sequences of messages never result in the same transcript.
+// squeezing a challenge replaces the transcript w/ its digest, so that each
+// challenge depends on all the preceding messages and challenges
+
+// canonical encoding of a point appended to a transcript
+pub trait TranscriptPoint {
+  fn to_transcript_bytes(&self) -> Vec<u8>;
+}
+
+pub struct Transcript<const N: usize> {
+  hasher: Box<dyn Hasher<N>>,
+  state: Vec<u8>,
+}
+
+impl Transcript<32> {
+  pub fn new(label: &[u8]) -> Self {
+    Transcript::with_hasher(Box::new(Sha256()), label)
+  }
+}
+
+impl<const N: usize> Transcript<N> {
+  // label separates the domain of the protocol using the transcript
+  pub fn with_hasher(hasher: Box<dyn Hasher<N>>, label: &[u8]) -> Self {
+    let mut transcript = Transcript { hasher, state: vec![] };
+    transcript.append_message(b"dom-sep", label);
+    transcript
+  }
+
+  pub fn append_message(&mut self, label: &[u8], msg: &[u8]) {
+    self.state.extend_from_slice(&(label.len() as u64).to_be_bytes());
+    self.state.extend_from_slice(label);
+    self.state.extend_from_slice(&(msg.len() as u64).to_be_bytes());
+    self.state.extend_from_slice(msg);
+  }
+
+  pub fn append_u64(&mut self, label: &[u8], x: u64) {
+    self.append_message(label, &x.to_be_bytes());
+  }
+
+  // big-endian w/ the byte length of the field order
+  pub fn append_field_elem(&mut self, label: &[u8], x: &PrimeFieldElem) {
+    let len = Self::byte_len(&x.f);
+    let bytes = x.e.to_bytes_be();
+    let mut buf = vec![0u8; len - bytes.len()];
+    buf.extend_from_slice(&bytes);
+    self.append_message(label, &buf);
+  }
+
+  pub fn append_point(&mut self, label: &[u8], p: &impl TranscriptPoint) {
+    self.append_message(label, &p.to_transcript_bytes());
+  }
+
+  fn byte_len(f: &PrimeField) -> usize {
+    (f.order_ref().bits() as usize).div_ceil(8)
+  }
+
+  pub fn challenge_bytes(&mut self, label: &[u8], len: usize) -> Vec<u8> {
+    self.append_u64(label, len as u64);
+    let seed = self.hasher.get_digest(&self.state);
+
+    // expand the seed in counter mode
+    let mut buf = Vec::with_capacity(len + N);
+    let mut counter = 0u64;
+    while buf.len() < len {
+      buf.extend_from_slice(&self.hasher.get_digest(&[&seed[..], &counter.to_be_bytes()].concat()));
+      counter += 1;
+    }
+    buf.truncate(len);
+
+    self.state.clear();
+    self.append_message(b"ratchet", &seed);
+    buf
+  }
+
+  // 16 extra bytes make the bias of the modular reduction negligible.
+  // zero is rejected so that challenges can always be inverted
+  pub fn challenge_field_elem(&mut self, label: &[u8], f: &Arc<PrimeField>) -> PrimeFieldElem {
+    loop {
+      let buf = self.challenge_bytes(label, Self::byte_len(f) + 16);
+      let x = f.elem(&BigUint::from_bytes_be(&buf));
+      if !x.is_zero() {
+        return x;
+      }
+    }
+  }
+}
+
+// SEC1 compressed encoding. point at infinity is encoded as zeros
+impl TranscriptPoint for AffinePoint {
+  fn to_transcript_bytes(&self) -> Vec<u8> {
+    match self {
+      AffinePoint::AtInfinity => vec![0u8; 33],
+      AffinePoint::Rational { x: _, y: _ } => self.to_compressed_bytes().to_vec(),
+    }
+  }
+}
+
+impl TranscriptPoint for G1Point {
+  fn to_transcript_bytes(&self) -> Vec<u8> {
+    self.to_compressed_bytes().to_vec()
+  }
+}
+
+impl TranscriptPoint for RistrettoPoint {
+  fn to_transcript_bytes(&self) -> Vec<u8> {
+    self.encode().to_vec()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::building_block::hasher::keccak::Keccak256;
+  use hex::ToHex;
+
+  #[test]
+  fn challenge_bytes() {
+    let mut t = Transcript::new(b"test");
+    t.append_message(b"msg", b"hello");
+    let c1 = t.challenge_bytes(b"c", 32);
+    assert_eq!(c1.encode_hex::<String>(), "78cbd4a90b9b29a68936d8829c35d50f6c4ebc6be7e16a26945d5ecfd98ff349");
+
+    // later challenges depend on earlier ones
+    let c2 = t.challenge_bytes(b"c", 32);
+    assert_ne!(c1, c2);
+
+    // outputs longer than the digest
+    let mut t = Transcript::new(b"test");
+    t.append_message(b"msg", b"hello");
+    let c = t.challenge_bytes(b"c", 100);
+    assert_eq!(c.len(), 100);
+    assert_ne!(c[..32], c1[..]);
+  }
+
+  #[test]
+  fn framing() {
+    let challenge = |label: &[u8], msgs: &[(&[u8], &[u8])]| {
+      let mut t = Transcript::new(label);
+      for (label, msg) in msgs {
+        t.append_message(label, msg);
+      }
+      t.challenge_bytes(b"c", 32)
+    };
+    let base = challenge(b"test", &[(b"a", b"bc")]);
+    assert_eq!(base, challenge(b"test", &[(b"a", b"bc")]));
+    assert_ne!(base, challenge(b"test2", &[(b"a", b"bc")]));
+    assert_ne!(base, challenge(b"test", &[(b"ab", b"c")]));
+    assert_ne!(base, challenge(b"test", &[(b"a", b"b"), (b"", b"c")]));
+    assert_ne!(base, challenge(b"test", &[(b"a", b"bd")]));
+  }
+
+  #[test]
+  fn challenge_field_elem() {
+    let f = &AffinePoint::curve_group();
+    let mut t = Transcript::new(b"test");
+    t.append_field_elem(b"x", &f.elem(&5u8));
+    t.append_point(b"g", &AffinePoint::g());
+    t.append_point(b"o", &AffinePoint::AtInfinity);
+    let x = t.challenge_field_elem(b"y", f);
+    assert_eq!(
+      x.e,
+      BigUint::parse_bytes(b"e4e10bce2e68ce232140ea9949dd23cb2b98c283d36c38a9582d09dc5c479852", 16).unwrap(),
+    );
+
+    // small field
+    let f = &Arc::new(PrimeField::new(&7u8));
+    let mut t = Transcript::new(b"test");
+    for _ in 0..20 {
+      let x = t.challenge_field_elem(b"c", f);
+      assert!(!x.is_zero() && x.e < BigUint::from(7u8));
+    }
+  }
+
+  #[test]
+  fn other_hasher() {
+    let mut t1 = Transcript::with_hasher(Box::new(Keccak256()), b"test");
+    let mut t2 = Transcript::new(b"test");
+    let c1 = t1.challenge_bytes(b"c", 32);
+    let c2 = t2.challenge_bytes(b"c", 32);
+    assert_ne!(c1, c2);
+    assert_eq!(c1.encode_hex::<String>(), "46fc24bff30c4d4ec1130ddcd33a165442d4caf0b599839079d583cf35bd9c15");
+  }
+}
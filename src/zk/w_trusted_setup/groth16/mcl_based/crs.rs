@@ -1,14 +1,18 @@
 use crate::{
-  building_block::mcl::{
-    mcl_fr::MclFr,
-    mcl_g1::MclG1,
-    mcl_g2::MclG2,
-    mcl_gt::MclGT,
-    pairing::Pairing,
-    qap::qap::QAP,
+  building_block::{
+    mcl::{
+      mcl_fr::MclFr,
+      mcl_g1::MclG1,
+      mcl_g2::MclG2,
+      mcl_gt::MclGT,
+      pairing::Pairing,
+      qap::qap::QAP,
+    },
+    random_number::RandomNumber,
   },
   zk::w_trusted_setup::groth16::mcl_based::prover::Prover,
 };
+use rand::{CryptoRng, RngCore};
 
 pub struct G1 {
   pub alpha: MclG1,
@@ -45,17 +49,26 @@ impl CRS {
   pub fn new(
     prover: &Prover,
     pairing: &Pairing,
+  ) -> Self {
+    Self::new_with_rng(prover, pairing, &mut RandomNumber::new().gen)
+  }
+
+  // toxic waste is drawn from rng
+  pub fn new_with_rng(
+    prover: &Prover,
+    pairing: &Pairing,
+    rng: &mut (impl RngCore + CryptoRng),
   ) -> Self {
     println!("--> Building sigma...");
     let g = &MclG1::g();
     let h = &MclG2::g();
 
     // sample random non-zero field element
-    let alpha = &MclFr::rand(true);
-    let beta = &MclFr::rand(true);
-    let gamma = &MclFr::rand(true);
-    let delta = &MclFr::rand(true);
-    let x = &MclFr::rand(true);
+    let alpha = &MclFr::rand_with_rng(true, rng);
+    let beta = &MclFr::rand_with_rng(true, rng);
+    let gamma = &MclFr::rand_with_rng(true, rng);
+    let delta = &MclFr::rand_with_rng(true, rng);
+    let x = &MclFr::rand_with_rng(true, rng);
 
     macro_rules! calc_uvw_div {
       ($from: expr, $to: expr, $div_factor: expr) => {
@@ -1,20 +1,23 @@
 use crate::{
-  building_block::mcl::{
-    mcl_fr::MclFr,
-    mcl_g1::MclG1,
-    mcl_g2::MclG2,
-    polynomial::{
-      DivResult,
-      Polynomial,
-    },
-    qap::{
-      equation_parser::EquationParser,
-      gate::Gate,
-      qap::QAP,
-      r1cs::R1CS,
-      r1cs_tmpl::R1CSTmpl,
-      term::Term,
+  building_block::{
+    mcl::{
+      mcl_fr::MclFr,
+      mcl_g1::MclG1,
+      mcl_g2::MclG2,
+      polynomial::{
+        DivResult,
+        Polynomial,
+      },
+      qap::{
+        equation_parser::EquationParser,
+        gate::Gate,
+        qap::QAP,
+        r1cs::R1CS,
+        r1cs_tmpl::R1CSTmpl,
+        term::Term,
+      },
     },
+    random_number::RandomNumber,
   },
   zk::w_trusted_setup::groth16::mcl_based::{
     crs::CRS,
@@ -23,6 +26,7 @@ use crate::{
   },
 };
 use num_traits::Zero;
+use rand::{CryptoRng, RngCore};
 use std::collections::HashMap;
 
 pub struct Prover {
@@ -81,9 +85,15 @@ impl Prover {
 
   #[allow(non_snake_case)]
   pub fn prove(&self, crs: &CRS) -> Proof {
+    self.prove_with_rng(crs, &mut RandomNumber::new().gen)
+  }
+
+  // blinding factors r and s are drawn from rng
+  #[allow(non_snake_case)]
+  pub fn prove_with_rng(&self, crs: &CRS, rng: &mut (impl RngCore + CryptoRng)) -> Proof {
     println!("--> Generating proof...");
-    let r = &MclFr::rand(true);
-    let s = &MclFr::rand(true);
+    let r = &MclFr::rand_with_rng(true, rng);
+    let s = &MclFr::rand_with_rng(true, rng);
 
     let (A, B, B_g1) = {
       let mut sum_term_A = MclG1::zero();
@@ -7,12 +7,14 @@ use crate::{
       pairing::Pairing,
     },
     field::prime_field::PrimeField,
+    random_number::RandomNumber,
   },
   zk::w_trusted_setup::{
     groth16::zktoolkit_based::prover::Prover,
     qap::qap::QAP,
   },
 };
+use rand::{CryptoRng, RngCore};
 
 pub struct G1 {
   pub alpha: G1Point,
@@ -50,17 +52,27 @@ impl CRS {
     f: &PrimeField,
     prover: &Prover,
     pairing: &Pairing,
+  ) -> Self {
+    Self::new_with_rng(f, prover, pairing, &mut RandomNumber::new().gen)
+  }
+
+  // toxic waste is drawn from rng
+  pub fn new_with_rng(
+    f: &PrimeField,
+    prover: &Prover,
+    pairing: &Pairing,
+    rng: &mut (impl RngCore + CryptoRng),
   ) -> Self {
     println!("--> Building sigma...");
     let g = &G1Point::g();
     let h = &G2Point::g();
 
     // sample random non-zero field element
-    let alpha = &f.rand_elem(true);
-    let beta = &f.rand_elem(true);
-    let gamma = &f.rand_elem(true);
-    let delta = &f.rand_elem(true);
-    let x = &f.rand_elem(true);
+    let alpha = &f.rand_elem_with_rng(true, rng);
+    let beta = &f.rand_elem_with_rng(true, rng);
+    let gamma = &f.rand_elem_with_rng(true, rng);
+    let delta = &f.rand_elem_with_rng(true, rng);
+    let x = &f.rand_elem_with_rng(true, rng);
 
     macro_rules! calc_uvw_div {
       ($from: expr, $to: expr, $div_factor: expr) => {
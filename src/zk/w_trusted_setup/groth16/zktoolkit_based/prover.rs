@@ -12,6 +12,7 @@ use crate::{
       prime_field::PrimeField,
       prime_field_elem::PrimeFieldElem,
     },
+    random_number::RandomNumber,
     zero::Zero,
   },
   zk::w_trusted_setup::{
@@ -30,6 +31,7 @@ use crate::{
     },
   },
 };
+use rand::{CryptoRng, RngCore};
 use std::collections::HashMap;
 
 pub struct Prover {
@@ -94,11 +96,17 @@ impl Prover {
 
   #[allow(non_snake_case)]
   pub fn prove(&self, crs: &CRS) -> Proof {
+    self.prove_with_rng(crs, &mut RandomNumber::new().gen)
+  }
+
+  // blinding factors r and s are drawn from rng
+  #[allow(non_snake_case)]
+  pub fn prove_with_rng(&self, crs: &CRS, rng: &mut (impl RngCore + CryptoRng)) -> Proof {
     println!("--> Generating proof...");
     let f = &self.f;
 
-    let r = &f.rand_elem(true);
-    let s = &f.rand_elem(true);
+    let r = &f.rand_elem_with_rng(true, rng);
+    let s = &f.rand_elem_with_rng(true, rng);
 
     let (A, B, B_g1) = {
       let mut sum_term_A = G1Point::zero();
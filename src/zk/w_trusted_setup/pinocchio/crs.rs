@@ -5,9 +5,11 @@ use crate::{
       g2_point::G2Point,
     },
     field::prime_field::PrimeField,
+    random_number::RandomNumber,
   },
   zk::w_trusted_setup::pinocchio::prover::Prover,
 };
+use rand::{CryptoRng, RngCore};
 
 pub struct EvaluationKeys {
   pub vk_mid: Vec<G1Point>,
@@ -44,23 +46,32 @@ pub struct CRS {
 }
 
 impl CRS {
-  #[allow(non_snake_case)]
   pub fn new(
     f: &PrimeField,
     p: &Prover,
+  ) -> Self {
+    Self::new_with_rng(f, p, &mut RandomNumber::new().gen)
+  }
+
+  // secret values are drawn from rng
+  #[allow(non_snake_case)]
+  pub fn new_with_rng(
+    f: &PrimeField,
+    p: &Prover,
+    rng: &mut (impl RngCore + CryptoRng),
   ) -> Self {
     println!("--> Building CRS...");
     let g1 = &G1Point::g();
     let g2 = &G2Point::g();
 
     // generate random values 
-    let r_v = &f.rand_elem(true);
-    let r_w = &f.rand_elem(true);
-    let alpha_v = &f.rand_elem(true);
-    let alpha_w = &f.rand_elem(true);
-    let alpha_y = &f.rand_elem(true);
-    let beta = &f.rand_elem(true);
-    let gamma = &f.rand_elem(true);
+    let r_v = &f.rand_elem_with_rng(true, rng);
+    let r_w = &f.rand_elem_with_rng(true, rng);
+    let alpha_v = &f.rand_elem_with_rng(true, rng);
+    let alpha_w = &f.rand_elem_with_rng(true, rng);
+    let alpha_y = &f.rand_elem_with_rng(true, rng);
+    let beta = &f.rand_elem_with_rng(true, rng);
+    let gamma = &f.rand_elem_with_rng(true, rng);
 
     // derive values from random values
     let r_y = &(r_v * r_w);
@@ -79,7 +90,7 @@ impl CRS {
       let io = (0..mid_beg).collect::<Vec<usize>>();
       (mid, io)
     };
-    let s = &f.rand_elem(true);
+    let s = &f.rand_elem_with_rng(true, rng);
 
     // compute evaluation keys
     println!("----> Computing evaluation keys...");
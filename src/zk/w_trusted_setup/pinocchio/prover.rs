@@ -14,6 +14,7 @@ use crate::{
       prime_field::PrimeField,
       prime_field_elem::PrimeFieldElem,
     },
+    random_number::RandomNumber,
     zero::Zero,
   },
   zk::w_trusted_setup::{
@@ -32,6 +33,7 @@ use crate::{
     },
   },
 };
+use rand::{CryptoRng, RngCore};
 use std::collections::HashMap;
 
 pub struct Prover {
@@ -94,12 +96,17 @@ impl Prover {
   }
 
   pub fn prove(&self, crs: &CRS) -> Proof {
+    self.prove_with_rng(crs, &mut RandomNumber::new().gen)
+  }
+
+  // delta_v and delta_y are drawn from rng
+  pub fn prove_with_rng(&self, crs: &CRS, rng: &mut (impl RngCore + CryptoRng)) -> Proof {
     println!("--> Generating proof...");
     let witness_mid = &self.witness.mid();
 
     let (ek, vk) = (&crs.ek, &crs.vk);
-    let delta_v = &self.f.rand_elem(true);
-    let delta_y = &self.f.rand_elem(true);
+    let delta_v = &self.f.rand_elem_with_rng(true, rng);
+    let delta_y = &self.f.rand_elem_with_rng(true, rng);
 
     // adjust v(s) and y(s) for zero-knowledge, w(s) is left untouched
     // due to current constraints in the pairing function implementation.
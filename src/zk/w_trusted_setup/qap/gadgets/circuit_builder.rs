@@ -0,0 +1,216 @@
+use crate::building_block::field::{
+  prime_field::PrimeField,
+  prime_field_elem::PrimeFieldElem,
+};
+use crate::zk::w_trusted_setup::qap::{
+  config::SignalId,
+  gate::Gate,
+  term::Term,
+};
+use num_bigint::BigUint;
+use std::{
+  collections::HashMap,
+  sync::Arc,
+};
+
+// term in the circuit paired w/ its value in the witness
+#[derive(Clone, Debug)]
+pub struct Signal {
+  pub term: Term,
+  pub value: PrimeFieldElem,
+}
+
+impl Signal {
+  pub fn is_constant(&self) -> bool {
+    matches!(self.term, Term::Num(_))
+  }
+}
+
+// builds gates together w/ the witness values so that gadgets can be
+// evaluated and constrained in a single pass. since R1CSTmpl assigns
+// coefficient 1 to every term in a Sum, multiplications by constants
+// are expressed as separate gates
+pub struct CircuitBuilder {
+  pub f: Arc<PrimeField>,
+  pub gates: Vec<Gate>,
+  pub witness: HashMap<Term, PrimeFieldElem>,
+  next_signal_id: SignalId,
+}
+
+impl CircuitBuilder {
+  pub fn new(f: &Arc<PrimeField>) -> Self {
+    let witness = HashMap::from([(Term::One, f.elem(&1u8))]);
+    CircuitBuilder {
+      f: f.clone(),
+      gates: vec![],
+      witness,
+      next_signal_id: 1,
+    }
+  }
+
+  pub fn input(&mut self, name: &str, value: &PrimeFieldElem) -> Signal {
+    let term = Term::var(name);
+    self.witness.insert(term.clone(), value.clone());
+    Signal { term, value: value.clone() }
+  }
+
+  // connects the signal to Term::Out
+  pub fn output(&mut self, signal: &Signal) {
+    self.gates.push(Gate { a: signal.term.clone(), b: Term::One, c: Term::Out });
+    self.witness.insert(Term::Out, signal.value.clone());
+  }
+
+  pub fn constant(&self, value: &PrimeFieldElem) -> Signal {
+    Signal { term: Term::Num(value.clone()), value: value.clone() }
+  }
+
+  fn new_signal(&mut self, value: &PrimeFieldElem) -> Signal {
+    let term = Term::TmpVar(self.next_signal_id);
+    self.next_signal_id += 1;
+    self.witness.insert(term.clone(), value.clone());
+    Signal { term, value: value.clone() }
+  }
+
+  // a * b = c
+  pub fn mul(&mut self, a: &Signal, b: &Signal) -> Signal {
+    let value = &a.value * &b.value;
+    if a.is_constant() && b.is_constant() {
+      return self.constant(&value);
+    }
+    let c = self.new_signal(&value);
+    self.gates.push(Gate { a: a.term.clone(), b: b.term.clone(), c: c.term.clone() });
+    c
+  }
+
+  // (k + x_1 + x_2 + ...) * 1 = c where k is the sum of the constant signals.
+  // since a term appearing more than once in a Sum still gets coefficient 1,
+  // a signal repeated n times is replaced w/ n * x
+  pub fn sum(&mut self, signals: &[Signal]) -> Signal {
+    let k = signals.iter()
+      .filter(|x| x.is_constant())
+      .fold(self.f.elem(&0u8), |acc, x| acc + &x.value);
+
+    let mut counts: Vec<(&Signal, u64)> = vec![];
+    for x in signals.iter().filter(|x| !x.is_constant()) {
+      match counts.iter_mut().find(|(y, _)| y.term == x.term) {
+        Some((_, count)) => *count += 1,
+        None => counts.push((x, 1)),
+      }
+    }
+    let mut vars: Vec<Signal> = vec![];
+    for (x, count) in counts {
+      if count == 1 {
+        vars.push(x.clone());
+      } else {
+        let n = self.constant(&self.f.elem(&count));
+        vars.push(self.mul(&n, x));
+      }
+    }
+
+    if vars.is_empty() {
+      return self.constant(&k);
+    }
+    if vars.len() == 1 && k.is_zero() {
+      return vars[0].clone();
+    }
+
+    let mut term = vars[0].term.clone();
+    let mut value = vars[0].value.clone();
+    for x in &vars[1..] {
+      term = Term::Sum(Box::new(term), Box::new(x.term.clone()));
+      value = value + &x.value;
+    }
+    if !k.is_zero() {
+      term = Term::Sum(Box::new(Term::Num(k.clone())), Box::new(term));
+      value = value + &k;
+    }
+    let c = self.new_signal(&value);
+    self.gates.push(Gate { a: term, b: Term::One, c: c.term.clone() });
+    c
+  }
+
+  // sum_j m_j * x_j + k
+  pub fn linear_combination(&mut self, coeffs: &[PrimeFieldElem], xs: &[Signal], k: &PrimeFieldElem) -> Signal {
+    let mut terms: Vec<Signal> = coeffs.iter().zip(xs.iter()).map(|(m, x)| {
+      let m = self.constant(m);
+      self.mul(&m, x)
+    }).collect();
+    terms.push(self.constant(k));
+    self.sum(&terms)
+  }
+
+  // x^e w/ the left-to-right binary method
+  pub fn pow(&mut self, x: &Signal, e: u64) -> Signal {
+    assert!(e > 0, "Exponent needs to be positive");
+    let mut res = x.clone();
+    for i in (0..63 - e.leading_zeros()).rev() {
+      res = self.mul(&res, &res.clone());
+      if (e >> i) & 1 == 1 {
+        res = self.mul(&res, x);
+      }
+    }
+    res
+  }
+
+  // y = x^e_inv constrained by y^e = x where e * e_inv = 1 mod (p - 1)
+  pub fn root(&mut self, x: &Signal, e: u64, e_inv: &BigUint) -> Signal {
+    let value = x.value.pow(e_inv);
+    if x.is_constant() {
+      return self.constant(&value);
+    }
+    let y = self.new_signal(&value);
+    let y_e_minus_1 = if e == 1 { self.constant(&self.f.elem(&1u8)) } else { self.pow(&y, e - 1) };
+    self.gates.push(Gate { a: y_e_minus_1.term.clone(), b: y.term.clone(), c: x.term.clone() });
+    y
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::zk::w_trusted_setup::qap::{
+    r1cs::R1CS,
+    r1cs_tmpl::R1CSTmpl,
+  };
+
+  #[test]
+  fn build_and_validate() {
+    let f = &Arc::new(PrimeField::new(&3911u16));
+    let mut cb = CircuitBuilder::new(f);
+
+    // out = (2x + 3y + 5)^5 + (x^3)^(1/3)
+    let x = cb.input("x", &f.elem(&7u8));
+    let y = cb.input("y", &f.elem(&11u8));
+    let lc = cb.linear_combination(&[f.elem(&2u8), f.elem(&3u8)], &[x.clone(), y], &f.elem(&5u8));
+    let p = cb.pow(&lc, 5);
+    let x3 = cb.pow(&x, 3);
+    let e_inv = BigUint::from(2607u16);  // 3 * 2607 = 1 mod 3910
+    let r = cb.root(&x3, 3, &e_inv);
+    assert_eq!(r.value, x.value);
+    let out = cb.sum(&[p, r]);
+    cb.output(&out);
+    assert_eq!(out.value, f.elem(&52u8).pow(&5u8) + f.elem(&7u8));
+
+    let tmpl = R1CSTmpl::new(f, &cb.gates);
+    let r1cs = R1CS::from_tmpl(f, &tmpl, &cb.witness).unwrap();
+    assert!(r1cs.validate().is_ok());
+  }
+
+  #[test]
+  fn sum_of_repeated_signals() {
+    let f = &Arc::new(PrimeField::new(&3911u16));
+    let mut cb = CircuitBuilder::new(f);
+
+    // out = x + y + x + 3 + x
+    let x = cb.input("x", &f.elem(&7u8));
+    let y = cb.input("y", &f.elem(&11u8));
+    let three = cb.constant(&f.elem(&3u8));
+    let out = cb.sum(&[x.clone(), y, x.clone(), three, x]);
+    cb.output(&out);
+    assert_eq!(out.value, f.elem(&35u8));
+
+    let tmpl = R1CSTmpl::new(f, &cb.gates);
+    let r1cs = R1CS::from_tmpl(f, &tmpl, &cb.witness).unwrap();
+    assert!(r1cs.validate().is_ok());
+  }
+}
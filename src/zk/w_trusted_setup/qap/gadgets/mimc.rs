@@ -0,0 +1,121 @@
+#![allow(non_snake_case)]
+
+use crate::building_block::{
+  field::prime_field_elem::PrimeFieldElem,
+  hasher::mimc::MiMC,
+};
+use crate::zk::w_trusted_setup::qap::gadgets::circuit_builder::{
+  CircuitBuilder,
+  Signal,
+};
+
+// constrains the MiMC-Feistel sponge hash computed by MiMC::hash_with_key
+pub struct MiMCGadget<'a> {
+  mimc: &'a MiMC,
+}
+
+impl<'a> MiMCGadget<'a> {
+  pub fn new(mimc: &'a MiMC) -> Self {
+    MiMCGadget { mimc }
+  }
+
+  // builds a circuit whose output is the hash of inputs named in0, in1, ...
+  // under the key named k
+  pub fn build(&self, inputs: &[PrimeFieldElem], k: &PrimeFieldElem) -> CircuitBuilder {
+    let mut cb = CircuitBuilder::new(&self.mimc.f);
+    let inputs: Vec<Signal> = inputs.iter().enumerate()
+      .map(|(i, x)| cb.input(&format!("in{}", i), x))
+      .collect();
+    let k = cb.input("k", k);
+    let digest = self.hash(&mut cb, &inputs, &k);
+    cb.output(&digest);
+    cb
+  }
+
+  pub fn permute(&self, cb: &mut CircuitBuilder, xL: &Signal, xR: &Signal, k: &Signal) -> (Signal, Signal) {
+    let (mut xL, mut xR) = (xL.clone(), xR.clone());
+    let rounds = self.mimc.rounds();
+
+    for (i, c) in self.mimc.round_constants.iter().enumerate() {
+      let c = cb.constant(c);
+      let t = cb.sum(&[xL.clone(), k.clone(), c]);
+      let t = cb.pow(&t, self.mimc.exponent);
+      let new_xR = cb.sum(&[xR.clone(), t]);
+      if i < rounds - 1 {
+        (xL, xR) = (new_xR, xL);
+      } else {
+        xR = new_xR;
+      }
+    }
+    (xL, xR)
+  }
+
+  pub fn hash(&self, cb: &mut CircuitBuilder, inputs: &[Signal], k: &Signal) -> Signal {
+    let mut xL = cb.constant(&cb.f.elem(&0u8));
+    let mut xR = cb.constant(&cb.f.elem(&0u8));
+    for x in inputs {
+      let xL_plus_x = cb.sum(&[xL, x.clone()]);
+      (xL, xR) = self.permute(cb, &xL_plus_x, &xR, k);
+    }
+    xL
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::zk::w_trusted_setup::qap::{
+    r1cs::R1CS,
+    r1cs_tmpl::R1CSTmpl,
+    term::Term,
+  };
+
+  #[test]
+  fn matches_native_hash() {
+    let mimc = MiMC::bls12_381_fr();
+    let f = &mimc.f;
+
+    for (n, k) in [(1u32, 0u8), (2, 7), (3, 0)] {
+      let inputs: Vec<PrimeFieldElem> = (1..=n).map(|x| f.elem(&x)).collect();
+      let k = &f.elem(&k);
+      let cb = MiMCGadget::new(&mimc).build(&inputs, k);
+      assert_eq!(cb.witness.get(&Term::Out).unwrap(), &mimc.hash_with_key(&inputs, k));
+
+      let tmpl = R1CSTmpl::new(f, &cb.gates);
+      let r1cs = R1CS::from_tmpl(f, &tmpl, &cb.witness).unwrap();
+      assert!(r1cs.validate().is_ok());
+    }
+  }
+
+  #[test]
+  fn key_reused_as_input() {
+    let mimc = MiMC::bls12_381_fr();
+    let f = &mimc.f;
+    let mut cb = CircuitBuilder::new(f);
+
+    // x + k + c_0 w/ x = k in the first round
+    let x = cb.input("x", &f.elem(&5u8));
+    let digest = MiMCGadget::new(&mimc).hash(&mut cb, &[x.clone()], &x);
+    cb.output(&digest);
+    assert_eq!(digest.value, mimc.hash_with_key(&[x.value.clone()], &x.value));
+
+    let tmpl = R1CSTmpl::new(f, &cb.gates);
+    let r1cs = R1CS::from_tmpl(f, &tmpl, &cb.witness).unwrap();
+    assert!(r1cs.validate().is_ok());
+  }
+
+  #[test]
+  fn bad_witness() {
+    let mimc = MiMC::bls12_381_fr();
+    let f = &mimc.f;
+    let inputs = vec![f.elem(&1u8), f.elem(&2u8)];
+    let mut cb = MiMCGadget::new(&mimc).build(&inputs, &f.elem(&0u8));
+
+    // use a different key w/o updating the rest of the witness
+    cb.witness.insert(Term::var("k"), f.elem(&1u8));
+
+    let tmpl = R1CSTmpl::new(f, &cb.gates);
+    let r1cs = R1CS::from_tmpl(f, &tmpl, &cb.witness).unwrap();
+    assert!(r1cs.validate().is_err());
+  }
+}
@@ -0,0 +1,4 @@
+pub mod circuit_builder;
+pub mod mimc;
+pub mod poseidon;
+pub mod rescue_prime;
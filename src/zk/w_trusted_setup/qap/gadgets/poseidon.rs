@@ -0,0 +1,116 @@
+use crate::building_block::{
+  field::prime_field_elem::PrimeFieldElem,
+  hasher::poseidon::Poseidon,
+};
+use crate::zk::w_trusted_setup::qap::gadgets::circuit_builder::{
+  CircuitBuilder,
+  Signal,
+};
+use num_bigint::BigUint;
+
+// constrains the Poseidon sponge hash computed by Poseidon::hash
+pub struct PoseidonGadget<'a> {
+  poseidon: &'a Poseidon,
+}
+
+impl<'a> PoseidonGadget<'a> {
+  pub fn new(poseidon: &'a Poseidon) -> Self {
+    PoseidonGadget { poseidon }
+  }
+
+  // builds a circuit whose output is the hash of inputs named in0, in1, ...
+  pub fn build(&self, inputs: &[PrimeFieldElem]) -> CircuitBuilder {
+    let mut cb = CircuitBuilder::new(&self.poseidon.f);
+    let inputs: Vec<Signal> = inputs.iter().enumerate()
+      .map(|(i, x)| cb.input(&format!("in{}", i), x))
+      .collect();
+    let digest = self.hash(&mut cb, &inputs);
+    cb.output(&digest);
+    cb
+  }
+
+  fn permute(&self, cb: &mut CircuitBuilder, state: &mut Vec<Signal>) {
+    let t = self.poseidon.t;
+    let zero = &cb.f.elem(&0u8);
+
+    for round in 0..self.poseidon.full_rounds + self.poseidon.partial_rounds {
+      for i in 0..t {
+        let rc = cb.constant(&self.poseidon.round_constants[round * t + i]);
+        state[i] = cb.sum(&[state[i].clone(), rc]);
+      }
+      if self.poseidon.is_full_round(round) {
+        for i in 0..t {
+          state[i] = cb.pow(&state[i], self.poseidon.alpha);
+        }
+      } else {
+        state[0] = cb.pow(&state[0], self.poseidon.alpha);
+      }
+      *state = self.poseidon.mds.iter()
+        .map(|row| cb.linear_combination(row, state, zero))
+        .collect();
+    }
+  }
+
+  // same sponge construction as Poseidon::hash
+  pub fn hash(&self, cb: &mut CircuitBuilder, inputs: &[Signal]) -> Signal {
+    let rate = self.poseidon.rate();
+    let capacity_value = cb.f.elem(&(BigUint::from(inputs.len()) << 64));
+
+    let mut state = vec![cb.constant(&cb.f.elem(&0u8)); self.poseidon.t];
+    state[0] = cb.constant(&capacity_value);
+
+    let mut pos = 0;
+    for x in inputs {
+      if pos == rate {
+        self.permute(cb, &mut state);
+        pos = 0;
+      }
+      state[1 + pos] = cb.sum(&[state[1 + pos].clone(), x.clone()]);
+      pos += 1;
+    }
+    self.permute(cb, &mut state);
+    state[1].clone()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::zk::w_trusted_setup::qap::{
+    r1cs::R1CS,
+    r1cs_tmpl::R1CSTmpl,
+    term::Term,
+  };
+
+  #[test]
+  fn matches_native_hash() {
+    let poseidon = Poseidon::bls12_381_fr();
+    let f = &poseidon.f;
+
+    for n in [1u32, 2, 3] {
+      let inputs: Vec<PrimeFieldElem> = (1..=n).map(|x| f.elem(&x)).collect();
+      let cb = PoseidonGadget::new(&poseidon).build(&inputs);
+      assert_eq!(cb.witness.get(&Term::Out).unwrap(), &poseidon.hash(&inputs));
+
+      let tmpl = R1CSTmpl::new(f, &cb.gates);
+      let r1cs = R1CS::from_tmpl(f, &tmpl, &cb.witness).unwrap();
+      assert!(r1cs.validate().is_ok());
+    }
+  }
+
+  #[test]
+  fn bad_witness() {
+    let poseidon = Poseidon::bls12_381_fr();
+    let f = &poseidon.f;
+    let inputs = vec![f.elem(&1u8), f.elem(&2u8)];
+    let mut cb = PoseidonGadget::new(&poseidon).build(&inputs);
+
+    // claim a different digest
+    let digest = cb.witness.get(&Term::Out).unwrap() + f.elem(&1u8);
+    cb.witness.insert(Term::Out, digest);
+
+    let tmpl = R1CSTmpl::new(f, &cb.gates);
+    let r1cs = R1CS::from_tmpl(f, &tmpl, &cb.witness).unwrap();
+    assert!(r1cs.validate().is_err());
+  }
+}
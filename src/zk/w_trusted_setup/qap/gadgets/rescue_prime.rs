@@ -0,0 +1,112 @@
+use crate::building_block::{
+  field::prime_field_elem::PrimeFieldElem,
+  hasher::rescue_prime::RescuePrime,
+};
+use crate::zk::w_trusted_setup::qap::gadgets::circuit_builder::{
+  CircuitBuilder,
+  Signal,
+};
+
+// constrains the Rescue-Prime sponge hash computed by RescuePrime::hash.
+// x^(1/alpha) is computed outside of the circuit and constrained by y^alpha = x
+pub struct RescuePrimeGadget<'a> {
+  rescue: &'a RescuePrime,
+}
+
+impl<'a> RescuePrimeGadget<'a> {
+  pub fn new(rescue: &'a RescuePrime) -> Self {
+    RescuePrimeGadget { rescue }
+  }
+
+  // builds a circuit whose output is the first element of the hash of inputs named in0, in1, ...
+  pub fn build(&self, inputs: &[PrimeFieldElem]) -> CircuitBuilder {
+    let mut cb = CircuitBuilder::new(&self.rescue.f);
+    let inputs: Vec<Signal> = inputs.iter().enumerate()
+      .map(|(i, x)| cb.input(&format!("in{}", i), x))
+      .collect();
+    let digest = self.hash(&mut cb, &inputs);
+    cb.output(&digest[0]);
+    cb
+  }
+
+  fn permute(&self, cb: &mut CircuitBuilder, state: &mut Vec<Signal>) {
+    let m = self.rescue.m;
+    for round in 0..self.rescue.rounds() {
+      for half in 0..2 {
+        let sboxed: Vec<Signal> = state.iter().map(|x| {
+          if half == 0 {
+            cb.pow(x, self.rescue.alpha)
+          } else {
+            cb.root(x, self.rescue.alpha, &self.rescue.alpha_inv)
+          }
+        }).collect();
+
+        let rcs = &self.rescue.round_constants[(2 * round + half) * m..(2 * round + half + 1) * m];
+        *state = self.rescue.mds.iter().zip(rcs.iter())
+          .map(|(row, rc)| cb.linear_combination(row, &sboxed, rc))
+          .collect();
+      }
+    }
+  }
+
+  // same sponge construction as RescuePrime::hash
+  pub fn hash(&self, cb: &mut CircuitBuilder, inputs: &[Signal]) -> Vec<Signal> {
+    let rate = self.rescue.rate();
+    let mut padded = inputs.to_vec();
+    padded.push(cb.constant(&cb.f.elem(&1u8)));
+    while padded.len() % rate != 0 {
+      padded.push(cb.constant(&cb.f.elem(&0u8)));
+    }
+
+    let mut state = vec![cb.constant(&cb.f.elem(&0u8)); self.rescue.m];
+    for block in padded.chunks(rate) {
+      for (i, x) in block.iter().enumerate() {
+        state[i] = cb.sum(&[state[i].clone(), x.clone()]);
+      }
+      self.permute(cb, &mut state);
+    }
+    state[..rate].to_vec()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::zk::w_trusted_setup::qap::{
+    r1cs::R1CS,
+    r1cs_tmpl::R1CSTmpl,
+    term::Term,
+  };
+
+  #[test]
+  fn matches_native_hash() {
+    let rescue = RescuePrime::bls12_381_fr();
+    let f = &rescue.f;
+
+    for n in [1u32, 2, 3] {
+      let inputs: Vec<PrimeFieldElem> = (1..=n).map(|x| f.elem(&x)).collect();
+      let cb = RescuePrimeGadget::new(&rescue).build(&inputs);
+      assert_eq!(cb.witness.get(&Term::Out).unwrap(), &rescue.hash(&inputs)[0]);
+
+      let tmpl = R1CSTmpl::new(f, &cb.gates);
+      let r1cs = R1CS::from_tmpl(f, &tmpl, &cb.witness).unwrap();
+      assert!(r1cs.validate().is_ok());
+    }
+  }
+
+  #[test]
+  fn bad_witness() {
+    let rescue = RescuePrime::bls12_381_fr();
+    let f = &rescue.f;
+    let inputs = vec![f.elem(&1u8), f.elem(&2u8)];
+    let mut cb = RescuePrimeGadget::new(&rescue).build(&inputs);
+
+    // claim a different digest
+    let digest = cb.witness.get(&Term::Out).unwrap() + f.elem(&1u8);
+    cb.witness.insert(Term::Out, digest);
+
+    let tmpl = R1CSTmpl::new(f, &cb.gates);
+    let r1cs = R1CS::from_tmpl(f, &tmpl, &cb.witness).unwrap();
+    assert!(r1cs.validate().is_err());
+  }
+}
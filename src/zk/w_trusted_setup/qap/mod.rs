@@ -1,6 +1,7 @@
 pub mod config;
 pub mod constraint;
 pub mod equation_parser;
+pub mod gadgets;
 pub mod gate;
 pub mod gates;
 pub mod qap;
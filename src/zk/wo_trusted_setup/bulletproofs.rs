@@ -7,15 +7,22 @@ use crate::building_block::{
     affine_point::AffinePoint,
     affine_points::AffinePoints,
   },
+  random_number::RandomNumber,
 };
+use rand::{CryptoRng, RngCore};
 
 // implementation based on https://eprint.iacr.org/2017/1066.pdf
+//
+// the functions below run the prover and the verifier together w/ the verifier
+// challenges drawn from rng, so they only demonstrate the protocols. use
+// RangeProof and InnerProductProof to produce proofs checked by a separate verifier
 
 pub struct Bulletproofs();
 
 impl Bulletproofs {
   // P = g^a h^b u^<a,b>
-  #[allow(non_snake_case)]
+  #[allow(non_snake_case, deprecated)]
+  #[deprecated(note = "use InnerProductProof::prove and InnerProductProof::verify instead")]
   pub fn inner_product_argument(
     n: &usize,
     gg: &AffinePoints,
@@ -24,6 +31,23 @@ impl Bulletproofs {
     P: &AffinePoint,
     a: &PrimeFieldElems,
     b: &PrimeFieldElems,
+  ) -> bool {
+    Bulletproofs::inner_product_argument_with_rng(
+      n, gg, hh, u, P, a, b, &mut RandomNumber::new().gen)
+  }
+
+  // verifier challenges are drawn from rng
+  #[allow(non_snake_case, deprecated)]
+  #[deprecated(note = "use InnerProductProof::prove and InnerProductProof::verify instead")]
+  pub fn inner_product_argument_with_rng(
+    n: &usize,
+    gg: &AffinePoints,
+    hh: &AffinePoints,
+    u: &AffinePoint,
+    P: &AffinePoint,
+    a: &PrimeFieldElems,
+    b: &PrimeFieldElems,
+    rng: &mut (impl RngCore + CryptoRng),
   ) -> bool {
     if n == &1 {
         let c = (a * b).sum();
@@ -39,7 +63,7 @@ impl Bulletproofs {
       let L = (gg.from(np) * a.to(np)).sum() + (hh.to(np) * b.from(np)).sum() + u * cL;
       let R = (gg.to(np) * a.from(np)).sum() + (hh.from(np) * b.to(np)).sum() + u * cR;
 
-      let x = &AffinePoint::curve_group().rand_elem(true);
+      let x = &AffinePoint::curve_group().rand_elem_with_rng(true, rng);
 
       let ggp = (gg.to(np) * x.inv()) + (gg.from(np) * x);
       let hhp = (hh.to(np) * x) + (hh.from(np) * x.inv());
@@ -49,12 +73,13 @@ impl Bulletproofs {
       let ap = a.to(np) * x + a.from(np) * x.inv();
       let bp = b.to(np) * x.inv() + b.from(np) * x;
 
-      Bulletproofs::inner_product_argument(
-        &np, &ggp, &hhp, u, &Pp, &ap, &bp)
+      Bulletproofs::inner_product_argument_with_rng(
+        &np, &ggp, &hhp, u, &Pp, &ap, &bp, rng)
     }
   }
 
-  #[allow(non_snake_case)]
+  #[allow(non_snake_case, deprecated)]
+  #[deprecated(note = "use RangeProof::prove and RangeProof::verify instead")]
   pub fn range_proof(
     n: &usize,
     V: &AffinePoint,
@@ -65,6 +90,25 @@ impl Bulletproofs {
     gg: &AffinePoints,
     hh: &AffinePoints,
     use_inner_product_argument: bool,
+  ) -> bool {
+    Bulletproofs::range_proof_with_rng(
+      n, V, aL, gamma, g, h, gg, hh, use_inner_product_argument, &mut RandomNumber::new().gen)
+  }
+
+  // blinding factors and verifier challenges are drawn from rng
+  #[allow(non_snake_case, deprecated)]
+  #[deprecated(note = "use RangeProof::prove_with_rng and RangeProof::verify instead")]
+  pub fn range_proof_with_rng(
+    n: &usize,
+    V: &AffinePoint,
+    aL: &PrimeFieldElems,
+    gamma: &PrimeFieldElem,
+    g: &AffinePoint,
+    h: &AffinePoint,
+    gg: &AffinePoints,
+    hh: &AffinePoints,
+    use_inner_product_argument: bool,
+    rng: &mut (impl RngCore + CryptoRng),
   ) -> bool {
     let f_n = AffinePoint::curve_group();
 
@@ -74,16 +118,16 @@ impl Bulletproofs {
     let two_n = &two.pow_seq(n);
 
     let aR = &(aL - one_n);
-    let alpha = &f_n.rand_elem(true);
+    let alpha = &f_n.rand_elem_with_rng(true, rng);
     let A = h * alpha + (gg * aL).sum() + (hh * aR).sum();
 
-    let sL = &f_n.rand_elems(n, true);
-    let sR = &f_n.rand_elems(n, true);
-    let rho = &f_n.rand_elem(true);
+    let sL = &f_n.rand_elems_with_rng(n, true, rng);
+    let sR = &f_n.rand_elems_with_rng(n, true, rng);
+    let rho = &f_n.rand_elem_with_rng(true, rng);
     let S = h * rho + (gg * sL).sum() + (hh * sR).sum();
 
-    let y = &f_n.rand_elem(true);
-    let z = &f_n.rand_elem(true);
+    let y = &f_n.rand_elem_with_rng(true, rng);
+    let z = &f_n.rand_elem_with_rng(true, rng);
 
     let y_n = &y.pow_seq(n);
     let l0 = &(aL - (one_n * z));
@@ -95,12 +139,12 @@ impl Bulletproofs {
     let t1 = &((l1 * r0).sum() + (l0 * r1).sum());
     let t2 = &(l1 * r1).sum();
 
-    let tau1 = &f_n.rand_elem(true);
-    let tau2 = &f_n.rand_elem(true);
+    let tau1 = &f_n.rand_elem_with_rng(true, rng);
+    let tau2 = &f_n.rand_elem_with_rng(true, rng);
     let T1 = g * t1 + h * tau1;
     let T2 = g * t2 + h * tau2;
 
-    let x = &f_n.rand_elem(true);
+    let x = &f_n.rand_elem_with_rng(true, rng);
 
     let t_hat = &(t0 + (t1 * x) + (t2 * x.sq()));
     let tau_x = &(tau2 * x.sq() + (tau1 * x) + (z.sq() * gamma));
@@ -129,9 +173,9 @@ impl Bulletproofs {
       + (hhp * ((y_n * z) + (two_n * z.sq()))).sum();
 
     if use_inner_product_argument {
-      let u = AffinePoint::rand_point(true);
+      let u = AffinePoint::rand_point_with_rng(true, rng);
       let Pp = &(P + h * mu.negate() + &u * (l * r).sum());
-      Bulletproofs::inner_product_argument(&n, gg, hhp, &u, Pp, l, r)
+      Bulletproofs::inner_product_argument_with_rng(n, gg, hhp, &u, Pp, l, r, rng)
 
     } else {
       let rhs_66_67 = ((h * mu) + (gg * l).sum()) + (hhp * r).sum();
@@ -246,7 +290,7 @@ mod tests {
   }
 
   #[test]
-  #[allow(non_snake_case)]
+  #[allow(non_snake_case, deprecated)]
   fn test_range_proof() {
     let curve_group = &AffinePoint::curve_group();
 
@@ -280,4 +324,31 @@ mod tests {
       assert!(res == true);
     }
   }
+
+  #[test]
+  #[allow(non_snake_case, deprecated)]
+  fn test_range_proof_with_seeded_rng() {
+    use rand::SeedableRng;
+    use rand_chacha::ChaChaRng;
+
+    let curve_group = &AffinePoint::curve_group();
+    let rng = &mut ChaChaRng::seed_from_u64(1);
+
+    let aL = PrimeFieldElems::new(&vec![
+      curve_group.elem(&1u8),
+      curve_group.elem(&1u8),
+    ]);
+    let n = aL.len();
+    let upsilon = curve_group.elem(&3u8);
+    let gamma = curve_group.rand_elem_with_rng(true, rng);
+    let g = AffinePoint::rand_point_with_rng(true, rng);
+    let h = AffinePoint::rand_point_with_rng(true, rng);
+    let gg = AffinePoints::rand_points_with_rng(true, &n, rng);
+    let hh = AffinePoints::rand_points_with_rng(true, &n, rng);
+    let V = (&h * &gamma) + (&g * &upsilon);
+
+    let res = Bulletproofs::range_proof_with_rng(
+      &n, &V, &aL, &gamma, &g, &h, &gg, &hh, true, rng);
+    assert!(res);
+  }
 }
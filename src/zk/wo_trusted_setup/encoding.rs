@@ -0,0 +1,64 @@
+use crate::building_block::{
+  curves::secp256k1::affine_point::AffinePoint,
+  field::prime_field_elem::PrimeFieldElem,
+};
+use num_bigint::BigUint;
+
+// fixed-size encodings of the proof elements. points are SEC1 compressed
+// and the point at infinity is encoded as zeros. scalars are 32-byte big-endian
+
+pub const POINT_LEN: usize = 33;
+pub const SCALAR_LEN: usize = 32;
+
+pub fn write_point(buf: &mut Vec<u8>, p: &AffinePoint) {
+  match p {
+    AffinePoint::AtInfinity => buf.extend_from_slice(&[0u8; POINT_LEN]),
+    AffinePoint::Rational { x: _, y: _ } => buf.extend_from_slice(&p.to_compressed_bytes()),
+  }
+}
+
+pub fn write_scalar(buf: &mut Vec<u8>, x: &PrimeFieldElem) {
+  buf.extend_from_slice(&AffinePoint::write_biguint_to_32_byte_buf(&x.e));
+}
+
+pub struct Reader<'a> {
+  buf: &'a [u8],
+  pos: usize,
+}
+
+impl<'a> Reader<'a> {
+  pub fn new(buf: &'a [u8]) -> Self {
+    Reader { buf, pos: 0 }
+  }
+
+  pub fn remaining(&self) -> usize {
+    self.buf.len() - self.pos
+  }
+
+  fn read(&mut self, len: usize) -> Result<&'a [u8], String> {
+    if self.remaining() < len {
+      return Err(format!("Expected {} more bytes, but only {} left", len, self.remaining()));
+    }
+    let bytes = &self.buf[self.pos..self.pos + len];
+    self.pos += len;
+    Ok(bytes)
+  }
+
+  pub fn read_point(&mut self) -> Result<AffinePoint, String> {
+    let bytes: [u8; POINT_LEN] = self.read(POINT_LEN)?.try_into().unwrap();
+    if bytes == [0u8; POINT_LEN] {
+      return Ok(AffinePoint::AtInfinity);
+    }
+    AffinePoint::from_compressed_bytes(&bytes)
+  }
+
+  // scalars need to be less than the group order
+  pub fn read_scalar(&mut self) -> Result<PrimeFieldElem, String> {
+    let f = AffinePoint::curve_group();
+    let x = BigUint::from_bytes_be(self.read(SCALAR_LEN)?);
+    if &x >= f.order_ref() {
+      return Err("Scalar is not less than the group order".to_string());
+    }
+    Ok(f.elem(&x))
+  }
+}
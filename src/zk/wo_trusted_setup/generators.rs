@@ -0,0 +1,100 @@
+use crate::building_block::{
+  curves::secp256k1::{
+    affine_point::AffinePoint,
+    affine_points::AffinePoints,
+  },
+  hasher::{
+    hasher::Hasher,
+    sha256::Sha256,
+  },
+};
+use num_bigint::BigUint;
+
+// generators shared by the prover and the verifier. g is the standard
+// generator and the others are derived by hashing so that nobody knows
+// the discrete logarithm relations among them
+
+pub struct Generators {
+  pub g: AffinePoint,
+  pub h: AffinePoint,
+  pub gg: AffinePoints,
+  pub hh: AffinePoints,
+}
+
+impl Generators {
+  pub fn new(n: usize) -> Self {
+    let gg = (0..n as u64)
+      .map(|i| Self::hash_to_point(&[b"Bulletproofs.G", &i.to_be_bytes()[..]].concat()))
+      .collect();
+    let hh = (0..n as u64)
+      .map(|i| Self::hash_to_point(&[b"Bulletproofs.H", &i.to_be_bytes()[..]].concat()))
+      .collect();
+    Generators {
+      g: AffinePoint::g(),
+      h: Self::hash_to_point(b"Bulletproofs.h"),
+      gg: AffinePoints::new(&gg),
+      hh: AffinePoints::new(&hh),
+    }
+  }
+
+  // try-and-increment: x = SHA-256(seed || counter) for counter = 0, 1, ...
+  // until x is the x-coordinate of a point
+  pub fn hash_to_point(seed: &[u8]) -> AffinePoint {
+    let mut counter = 0u64;
+    loop {
+      let x = Sha256().get_digest(&[seed, &counter.to_be_bytes()[..]].concat());
+      if let Ok(p) = AffinePoint::lift_x(&BigUint::from_bytes_be(&x)) {
+        return p;
+      }
+      counter += 1;
+    }
+  }
+
+  // number of vector generators
+  pub fn len(&self) -> usize {
+    self.gg.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.gg.is_empty()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::building_block::curves::rational_point::RationalPoint;
+
+  fn x_of(p: &AffinePoint) -> BigUint {
+    match p {
+      AffinePoint::Rational { x, y: _ } => x.e.clone(),
+      AffinePoint::AtInfinity => panic!("Unexpected point at infinity"),
+    }
+  }
+
+  #[test]
+  fn new() {
+    let gens = Generators::new(2);
+    assert_eq!(gens.len(), 2);
+    assert!(gens.g == AffinePoint::g());
+
+    let parse = |s: &str| BigUint::parse_bytes(s.as_bytes(), 16).unwrap();
+    assert_eq!(x_of(&gens.h), parse("6c01b9a66d764aec285fa8a5864af2dbfe29cff57e465dae244c57a756b30d41"));
+    assert_eq!(x_of(&gens.gg[1]), parse("b325b3044b480e8e90a48a92ddcb87aea0660c1dbe02e562d071b21bcf5d7659"));
+    assert_eq!(x_of(&gens.hh[0]), parse("7c341f3f3e6d9cb817c116987ae9c2984247e675db9a5b87929ae935976d61"));
+
+    let points = [&gens.h, &gens.gg[0], &gens.gg[1], &gens.hh[0], &gens.hh[1]];
+    for (i, p) in points.iter().enumerate() {
+      assert!(p.is_rational_point());
+      assert!(p.has_even_y());
+      for q in &points[i + 1..] {
+        assert!(p != q);
+      }
+    }
+
+    // prefix of a larger set of generators
+    let gens4 = Generators::new(4);
+    assert!(gens4.gg.to(2) == gens.gg);
+    assert!(gens4.hh.to(2) == gens.hh);
+  }
+}
@@ -0,0 +1,275 @@
+#![allow(non_snake_case)]
+
+use crate::building_block::{
+  curves::secp256k1::{
+    affine_point::AffinePoint,
+    affine_points::AffinePoints,
+  },
+  field::{
+    prime_field_elem::PrimeFieldElem,
+    prime_field_elems::PrimeFieldElems,
+  },
+  transcript::Transcript,
+};
+use crate::zk::wo_trusted_setup::encoding::{
+  Reader,
+  write_point,
+  write_scalar,
+  POINT_LEN,
+  SCALAR_LEN,
+};
+use num_bigint::BigUint;
+
+// non-interactive proof of knowledge of a and b s.t. P = g^a h^b u^<a,b>
+// consisting of 2 log2(n) points and 2 scalars.
+// implementation based on protocol 2 of https://eprint.iacr.org/2017/1066.pdf
+// w/ the verifier challenges derived from the transcript
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InnerProductProof {
+  pub L_vec: Vec<AffinePoint>,
+  pub R_vec: Vec<AffinePoint>,
+  pub a: PrimeFieldElem,
+  pub b: PrimeFieldElem,
+}
+
+impl InnerProductProof {
+  fn append_statement(transcript: &mut Transcript<32>, n: usize) {
+    transcript.append_message(b"dom-sep", b"inner-product");
+    transcript.append_u64(b"n", n as u64);
+  }
+
+  // the statement P needs to be bound to the transcript by the caller
+  pub fn prove(
+    transcript: &mut Transcript<32>,
+    u: &AffinePoint,
+    gg: &AffinePoints,
+    hh: &AffinePoints,
+    a: &PrimeFieldElems,
+    b: &PrimeFieldElems,
+  ) -> Result<InnerProductProof, String> {
+    let n = a.len();
+    if n == 0 || !n.is_power_of_two() {
+      return Err(format!("Vector length needs to be a power of 2, but got {}", n));
+    }
+    if b.len() != n || gg.len() != n || hh.len() != n {
+      return Err("Vectors and generators need to be of the same length".to_string());
+    }
+    InnerProductProof::append_statement(transcript, n);
+
+    let mut gg = AffinePoints::new(gg);
+    let mut hh = AffinePoints::new(hh);
+    let mut a = a.clone();
+    let mut b = b.clone();
+    let mut L_vec = vec![];
+    let mut R_vec = vec![];
+
+    let mut n = n;
+    while n > 1 {
+      let np = n / 2;
+
+      let cL = (a.to(np) * b.from(np)).sum();
+      let cR = (a.from(np) * b.to(np)).sum();
+
+      let L = (gg.from(np) * a.to(np)).sum() + (hh.to(np) * b.from(np)).sum() + u * cL;
+      let R = (gg.to(np) * a.from(np)).sum() + (hh.from(np) * b.to(np)).sum() + u * cR;
+
+      transcript.append_point(b"L", &L);
+      transcript.append_point(b"R", &R);
+      let x = &transcript.challenge_field_elem(b"x", &AffinePoint::curve_group());
+      let x_inv = &x.inv();
+
+      gg = (gg.to(np) * x_inv) + (gg.from(np) * x);
+      hh = (hh.to(np) * x) + (hh.from(np) * x_inv);
+      a = a.to(np) * x + a.from(np) * x_inv;
+      b = b.to(np) * x_inv + b.from(np) * x;
+
+      L_vec.push(L);
+      R_vec.push(R);
+      n = np;
+    }
+    Ok(InnerProductProof { L_vec, R_vec, a: a[0].clone(), b: b[0].clone() })
+  }
+
+  // instead of folding the generators round by round, the verifier computes
+  // g_i^(a s_i) and h_i^(b / s_i) where s_i is the product of x_j or x_j^-1
+  // depending on the bits of i. the whole check becomes a single multi-scalar
+  // multiplication:
+  // P = g^(a s) h^(b / s) u^(ab) prod_j L_j^(-x_j^2) R_j^(-x_j^-2)
+  pub fn verify(
+    &self,
+    transcript: &mut Transcript<32>,
+    u: &AffinePoint,
+    gg: &AffinePoints,
+    hh: &AffinePoints,
+    P: &AffinePoint,
+  ) -> bool {
+    let n = gg.len();
+    let k = self.L_vec.len();
+    if n == 0 || !n.is_power_of_two() || hh.len() != n
+      || self.R_vec.len() != k || n.trailing_zeros() as usize != k {
+      return false;
+    }
+    InnerProductProof::append_statement(transcript, n);
+
+    let f_n = AffinePoint::curve_group();
+    let mut xs = vec![];
+    for (L, R) in self.L_vec.iter().zip(self.R_vec.iter()) {
+      transcript.append_point(b"L", L);
+      transcript.append_point(b"R", R);
+      xs.push(transcript.challenge_field_elem(b"x", &f_n));
+    }
+    let x_invs: Vec<PrimeFieldElem> = xs.iter().map(|x| x.inv()).collect();
+
+    // the j-th challenge halves the vectors by the (k-1-j)-th bit of the index
+    let s: Vec<PrimeFieldElem> = (0..n).map(|i| {
+      (0..k).fold(f_n.elem(&1u8), |acc, j| {
+        if (i >> (k - 1 - j)) & 1 == 1 { acc * &xs[j] } else { acc * &x_invs[j] }
+      })
+    }).collect();
+
+    let mut points: Vec<AffinePoint> = vec![];
+    let mut scalars: Vec<BigUint> = vec![];
+    for (g, s_i) in gg.iter().zip(s.iter()) {
+      points.push(g.clone());
+      scalars.push((&self.a * s_i).e);
+    }
+    for (h, s_i) in hh.iter().zip(s.iter()) {
+      points.push(h.clone());
+      scalars.push((&self.b * s_i.inv()).e);
+    }
+    points.push(u.clone());
+    scalars.push((&self.a * &self.b).e);
+    for j in 0..k {
+      points.push(self.L_vec[j].clone());
+      scalars.push(xs[j].sq().negate().e);
+      points.push(self.R_vec[j].clone());
+      scalars.push(x_invs[j].sq().negate().e);
+    }
+    &AffinePoint::multi_scalar_mul(&points, &scalars) == P
+  }
+
+  // L_0 || R_0 || ... || L_(k-1) || R_(k-1) || a || b
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut buf = vec![];
+    for (L, R) in self.L_vec.iter().zip(self.R_vec.iter()) {
+      write_point(&mut buf, L);
+      write_point(&mut buf, R);
+    }
+    write_scalar(&mut buf, &self.a);
+    write_scalar(&mut buf, &self.b);
+    buf
+  }
+
+  pub fn from_bytes(buf: &[u8]) -> Result<InnerProductProof, String> {
+    if buf.len() < 2 * SCALAR_LEN || (buf.len() - 2 * SCALAR_LEN) % (2 * POINT_LEN) != 0 {
+      return Err(format!("Invalid inner product proof length {}", buf.len()));
+    }
+    let k = (buf.len() - 2 * SCALAR_LEN) / (2 * POINT_LEN);
+
+    let mut reader = Reader::new(buf);
+    let mut L_vec = vec![];
+    let mut R_vec = vec![];
+    for _ in 0..k {
+      L_vec.push(reader.read_point()?);
+      R_vec.push(reader.read_point()?);
+    }
+    let a = reader.read_scalar()?;
+    let b = reader.read_scalar()?;
+    Ok(InnerProductProof { L_vec, R_vec, a, b })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::zk::wo_trusted_setup::generators::Generators;
+  use rand::SeedableRng;
+  use rand_chacha::ChaChaRng;
+
+  struct Statement {
+    gens: Generators,
+    u: AffinePoint,
+    a: PrimeFieldElems,
+    b: PrimeFieldElems,
+    P: AffinePoint,
+  }
+
+  fn statement(n: usize, seed: u64) -> Statement {
+    let rng = &mut ChaChaRng::seed_from_u64(seed);
+    let f_n = AffinePoint::curve_group();
+    let gens = Generators::new(n);
+    let u = Generators::hash_to_point(b"u");
+    let a = f_n.rand_elems_with_rng(&n, true, rng);
+    let b = f_n.rand_elems_with_rng(&n, true, rng);
+    let P = (&gens.gg * &a).sum() + (&gens.hh * &b).sum() + &u * (&a * &b).sum();
+    Statement { gens, u, a, b, P }
+  }
+
+  fn prove(st: &Statement) -> InnerProductProof {
+    let mut transcript = Transcript::new(b"test");
+    InnerProductProof::prove(&mut transcript, &st.u, &st.gens.gg, &st.gens.hh, &st.a, &st.b).unwrap()
+  }
+
+  fn verify(st: &Statement, proof: &InnerProductProof, P: &AffinePoint) -> bool {
+    let mut transcript = Transcript::new(b"test");
+    proof.verify(&mut transcript, &st.u, &st.gens.gg, &st.gens.hh, P)
+  }
+
+  #[test]
+  fn prove_and_verify() {
+    for n in [1, 2, 4, 8] {
+      let st = statement(n, n as u64);
+      let proof = prove(&st);
+      assert_eq!(proof.L_vec.len(), n.trailing_zeros() as usize);
+      assert!(verify(&st, &proof, &st.P));
+    }
+  }
+
+  #[test]
+  fn bad_proof() {
+    let st = statement(4, 1);
+    let proof = prove(&st);
+    let one = AffinePoint::curve_group().elem(&1u8);
+
+    // different statement
+    assert!(!verify(&st, &proof, &(&st.P + &st.u)));
+
+    let mut transcript = Transcript::new(b"other");
+    assert!(!proof.verify(&mut transcript, &st.u, &st.gens.gg, &st.gens.hh, &st.P));
+
+    // tampered proof
+    let mut bad_proof = proof.clone();
+    bad_proof.a = &bad_proof.a + &one;
+    assert!(!verify(&st, &bad_proof, &st.P));
+
+    let mut bad_proof = proof.clone();
+    bad_proof.L_vec.swap(0, 1);
+    assert!(!verify(&st, &bad_proof, &st.P));
+
+    let mut bad_proof = proof.clone();
+    bad_proof.R_vec.pop();
+    assert!(!verify(&st, &bad_proof, &st.P));
+
+    // invalid input
+    let mut transcript = Transcript::new(b"test");
+    let a = &st.a.to(3);
+    assert!(InnerProductProof::prove(&mut transcript, &st.u, &st.gens.gg.to(3), &st.gens.hh.to(3), a, &st.b.to(3)).is_err());
+    assert!(InnerProductProof::prove(&mut transcript, &st.u, &st.gens.gg, &st.gens.hh, &st.a, &st.b.to(2)).is_err());
+  }
+
+  #[test]
+  fn serialization() {
+    let st = statement(8, 3);
+    let proof = prove(&st);
+
+    let buf = proof.to_bytes();
+    assert_eq!(buf.len(), 2 * 3 * 33 + 2 * 32);
+    let proof2 = InnerProductProof::from_bytes(&buf).unwrap();
+    assert_eq!(proof, proof2);
+    assert!(verify(&st, &proof2, &st.P));
+
+    assert!(InnerProductProof::from_bytes(&buf[1..]).is_err());
+    assert!(InnerProductProof::from_bytes(&buf[..63]).is_err());
+  }
+}
@@ -1,3 +1,8 @@
 pub mod bulletproofs;
+pub mod encoding;
+pub mod generators;
+pub mod inner_product_proof;
+pub mod r1cs_proof;
+pub mod range_proof;
 
 
@@ -0,0 +1,719 @@
+#![allow(non_snake_case)]
+
+use crate::building_block::{
+  curves::secp256k1::affine_point::AffinePoint,
+  field::{
+    prime_field_elem::PrimeFieldElem,
+    prime_field::PrimeField,
+    prime_field_elems::PrimeFieldElems,
+    sparse_matrix::SparseMatrix,
+    sparse_vec::SparseVec,
+  },
+  random_number::RandomNumber,
+  transcript::Transcript,
+};
+use crate::zk::w_trusted_setup::qap::r1cs::{
+  ConstraintMatrices,
+  R1CS,
+};
+use crate::zk::wo_trusted_setup::{
+  encoding::{
+    Reader,
+    write_point,
+    write_scalar,
+    POINT_LEN,
+    SCALAR_LEN,
+  },
+  generators::Generators,
+  inner_product_proof::InnerProductProof,
+};
+use rand::{CryptoRng, RngCore};
+
+// non-interactive proof of knowledge of a witness satisfying an R1CS where
+// the values at the given witness indices are hidden behind the commitments
+// V_j = g^v_j h^gamma_j. implementation based on section 5 of
+// https://eprint.iacr.org/2017/1066.pdf w/ the verifier challenges derived
+// from the transcript. the proof consists of 2 log2(n) + 8 points and
+// 5 scalars where n is the number of multiplication gates
+//
+// the witness [1, inputs, Out, mid] is split into:
+// - committed values at the given indices
+// - public values at the other indices less than mid_beg
+// - private values at the other indices
+// the i-th R1CS constraint <A_i, w> * <B_i, w> = <C_i, w> becomes the i-th
+// multiplication gate a_L[i] * a_R[i] = a_O[i] and the linear constraints
+// tying a_L[i], a_R[i] and a_O[i] to <A_i, w>, <B_i, w> and <C_i, w>.
+// each private value w_k is assigned to a_L of a gate w_k * 1 = w_k of its own
+// so that the linear constraints can refer to it
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct R1CSProof {
+  pub A_I: AffinePoint,
+  pub A_O: AffinePoint,
+  pub S: AffinePoint,
+  pub T1: AffinePoint,
+  pub T3: AffinePoint,
+  pub T4: AffinePoint,
+  pub T5: AffinePoint,
+  pub T6: AffinePoint,
+  pub tau_x: PrimeFieldElem,
+  pub mu: PrimeFieldElem,
+  pub t_hat: PrimeFieldElem,
+  pub ipp: InnerProductProof,
+}
+
+enum Var {
+  Public(PrimeFieldElem),
+  Committed(usize),  // index of the commitment
+  Private(usize),  // index of the gate
+}
+
+// W_L a_L + W_R a_R + W_O a_O = W_V v + c where each row is a linear
+// constraint. the weights are kept sparse since each row refers to only
+// a few of the gates and commitments
+struct ConstraintSystem {
+  n: usize,  // number of gates padded to a power of 2
+  q: usize,  // number of linear constraints
+  vars: Vec<Var>,
+  W_L: SparseMatrix,
+  W_R: SparseMatrix,
+  W_O: SparseMatrix,
+  W_V: SparseMatrix,
+  c: SparseVec,
+}
+
+impl ConstraintSystem {
+  fn new(
+    matrices: &ConstraintMatrices,
+    witness_io: &SparseVec,
+    committed: &[usize],
+  ) -> Result<Self, String> {
+    let f_n = AffinePoint::curve_group();
+    if witness_io.f != *f_n {
+      return Err("R1CS needs to be defined over the secp256k1 scalar field".to_string());
+    }
+    let width = matrices.a.width.to_usize();
+    let height = matrices.a.height.to_usize();
+    let mid_beg = witness_io.size_in_usize();
+    if mid_beg > width {
+      return Err(format!("Public witness is longer than the witness of size {}", width));
+    }
+    for (j, k) in committed.iter().enumerate() {
+      if *k == 0 || *k >= width {
+        return Err(format!("Committed index needs to be in [1, {}), but got {}", width, k));
+      }
+      if committed[..j].contains(k) {
+        return Err(format!("Index {} is committed more than once", k));
+      }
+    }
+
+    let mut vars = vec![];
+    let mut num_gates = height;
+    for k in 0..width {
+      let var = if let Some(j) = committed.iter().position(|x| *x == k) {
+        Var::Committed(j)
+      } else if k < mid_beg {
+        Var::Public(witness_io.get(&k).clone())
+      } else {
+        num_gates += 1;
+        Var::Private(num_gates - 1)
+      };
+      vars.push(var);
+    }
+    let n = num_gates.next_power_of_two();
+    let m = committed.len();
+    let q = 3 * height + num_gates - height;
+
+    let f: &PrimeField = &f_n;
+    let mut W_L = SparseMatrix::new(f, &n, &q);
+    let mut W_R = SparseMatrix::new(f, &n, &q);
+    let mut W_O = SparseMatrix::new(f, &n, &q);
+    let mut W_V = SparseMatrix::new(f, &m, &q);
+    let mut c = SparseVec::new(f, &q);
+
+    // a_L[i] = <A_i, w>, a_R[i] = <B_i, w> and a_O[i] = <C_i, w> in rows 3i, 3i + 1 and 3i + 2
+    for i in 0..height {
+      for (target, matrix) in [&matrices.a, &matrices.b, &matrices.c].iter().enumerate() {
+        let row = 3 * i + target;
+        let W = match target {
+          0 => &mut W_L,
+          1 => &mut W_R,
+          _ => &mut W_O,
+        };
+        W.set(&i, &row, &1u8);
+
+        let r1cs_row = matrix.get_row(&i);
+        for k in r1cs_row.indices() {
+          let coeff = r1cs_row.get(&k);
+          match &vars[k.to_usize()] {
+            Var::Public(x) => c.set(&row, &(c.get(&row) + coeff * x)),
+            Var::Committed(j) => W_V.set(j, &row, coeff),
+            Var::Private(g) => W_L.set(g, &row, &coeff.negate()),
+          }
+        }
+      }
+    }
+
+    // a_R = 1 for the gates of the private values in the remaining rows
+    for (row, g) in (3 * height..q).zip(height..num_gates) {
+      W_R.set(&g, &row, &1u8);
+      c.set(&row, &1u8);
+    }
+    Ok(ConstraintSystem { n, q, vars, W_L, W_R, W_O, W_V, c })
+  }
+
+  // non-zero entries (row, column, weight) in the order of rows and then columns
+  fn entries(W: &SparseMatrix) -> Vec<(usize, usize, PrimeFieldElem)> {
+    if W.width.is_zero() {
+      return vec![];
+    }
+    let mut entries = vec![];
+    for y in 0..W.height.to_usize() {
+      let row = W.get_row(&y);
+      let mut xs = row.indices();
+      xs.sort();
+      for x in xs {
+        entries.push((y, x.to_usize(), row.get(&x).clone()));
+      }
+    }
+    entries
+  }
+
+  fn append_statement(&self, transcript: &mut Transcript<32>, Vs: &[AffinePoint]) {
+    transcript.append_message(b"dom-sep", b"r1cs-proof");
+    transcript.append_u64(b"n", self.n as u64);
+    transcript.append_u64(b"q", self.q as u64);
+    transcript.append_u64(b"m", Vs.len() as u64);
+
+    // row || column || weight for each non-zero entry. the public values
+    // are bound through c
+    for (label, W) in [(b"W_L", &self.W_L), (b"W_R", &self.W_R), (b"W_O", &self.W_O), (b"W_V", &self.W_V)] {
+      let mut buf = vec![];
+      for (y, x, v) in ConstraintSystem::entries(W) {
+        buf.extend_from_slice(&(y as u64).to_be_bytes());
+        buf.extend_from_slice(&(x as u64).to_be_bytes());
+        write_scalar(&mut buf, &v);
+      }
+      transcript.append_message(label, &buf);
+    }
+    let mut ys = self.c.indices();
+    ys.sort();
+    let mut buf = vec![];
+    for y in ys {
+      buf.extend_from_slice(&(y.to_usize() as u64).to_be_bytes());
+      write_scalar(&mut buf, self.c.get(&y));
+    }
+    transcript.append_message(b"c", &buf);
+
+    for V in Vs {
+      transcript.append_point(b"V", V);
+    }
+  }
+
+  // z^Q W where z^Q = (z, z^2, ..., z^Q)
+  fn flatten(W: &SparseMatrix, z_Q: &PrimeFieldElems) -> PrimeFieldElems {
+    let mut xs = AffinePoint::curve_group().elem(&0u8).repeat(&W.width);
+    for (y, x, v) in ConstraintSystem::entries(W) {
+      xs.0[x] = &xs.0[x] + &z_Q[y] * v;
+    }
+    xs
+  }
+
+  // <z^Q, c>
+  fn flatten_c(&self, z_Q: &PrimeFieldElems) -> PrimeFieldElem {
+    let zero = AffinePoint::curve_group().elem(&0u8);
+    self.c.indices().iter().fold(zero, |acc, y| acc + &z_Q[y.to_usize()] * self.c.get(y))
+  }
+
+  // returns a_L, a_R, a_O and v
+  fn assign(
+    &self,
+    matrices: &ConstraintMatrices,
+    witness: &SparseVec,
+    committed: &[usize],
+  ) -> Result<(PrimeFieldElems, PrimeFieldElems, PrimeFieldElems, PrimeFieldElems), String> {
+    let f_n = AffinePoint::curve_group();
+    let zero = &f_n.elem(&0u8);
+    let one = &f_n.elem(&1u8);
+    let eval = |row: &SparseVec| {
+      row.indices().iter().fold(zero.clone(), |acc, k| acc + row.get(k) * witness.get(k))
+    };
+
+    let mut aL = zero.repeat(&self.n);
+    let mut aR = zero.repeat(&self.n);
+    let mut aO = zero.repeat(&self.n);
+    for i in 0..matrices.a.height.to_usize() {
+      aL.0[i] = eval(&matrices.a.get_row(&i));
+      aR.0[i] = eval(&matrices.b.get_row(&i));
+      aO.0[i] = eval(&matrices.c.get_row(&i));
+      if &aL[i] * &aR[i] != aO[i] {
+        return Err(format!("Witness doesn't satisfy constraint {}", i));
+      }
+    }
+    for (k, var) in self.vars.iter().enumerate() {
+      if let Var::Private(g) = var {
+        aL.0[*g] = witness.get(&k).clone();
+        aR.0[*g] = one.clone();
+        aO.0[*g] = witness.get(&k).clone();
+      }
+    }
+    let v = PrimeFieldElems(committed.iter().map(|k| witness.get(k).clone()).collect());
+    Ok((aL, aR, aO, v))
+  }
+}
+
+fn inner_product(a: &PrimeFieldElems, b: &PrimeFieldElems) -> PrimeFieldElem {
+  let zero = AffinePoint::curve_group().elem(&0u8);
+  a.iter().zip(b.iter()).fold(zero, |acc, (x, y)| acc + x * y)
+}
+
+impl R1CSProof {
+  // returns the proof and the commitments V_j to the witness values
+  // at the committed indices
+  pub fn prove(
+    transcript: &mut Transcript<32>,
+    gens: &Generators,
+    r1cs: &R1CS,
+    committed: &[usize],
+    gammas: &[PrimeFieldElem],
+  ) -> Result<(R1CSProof, Vec<AffinePoint>), String> {
+    R1CSProof::prove_with_rng(transcript, gens, r1cs, committed, gammas, &mut RandomNumber::new().gen)
+  }
+
+  // blinding factors are drawn from rng
+  pub fn prove_with_rng(
+    transcript: &mut Transcript<32>,
+    gens: &Generators,
+    r1cs: &R1CS,
+    committed: &[usize],
+    gammas: &[PrimeFieldElem],
+    rng: &mut (impl RngCore + CryptoRng),
+  ) -> Result<(R1CSProof, Vec<AffinePoint>), String> {
+    let f_n = AffinePoint::curve_group();
+    let matrices = &r1cs.to_constraint_matrices();
+    let witness_io = &r1cs.witness.slice(&r1cs.witness.f.elem(&0u8), &r1cs.mid_beg);
+    let cs = ConstraintSystem::new(matrices, witness_io, committed)?;
+    let (n, q, m) = (cs.n, cs.q, committed.len());
+    if gammas.len() != m {
+      return Err(format!("{} blinding factors are required, but got {}", m, gammas.len()));
+    }
+    if gens.len() < n {
+      return Err(format!("{} generators are required, but got {}", n, gens.len()));
+    }
+    let (aL, aR, aO, v) = &cs.assign(matrices, &r1cs.witness, committed)?;
+
+    let (g, h) = (&gens.g, &gens.h);
+    let gg = &gens.gg.to(n);
+    let hh = &gens.hh.to(n);
+
+    let Vs: Vec<AffinePoint> = v.iter().zip(gammas.iter())
+      .map(|(v, gamma)| g * v + h * gamma)
+      .collect();
+    cs.append_statement(transcript, &Vs);
+
+    let alpha = &f_n.rand_elem_with_rng(true, rng);
+    let beta = &f_n.rand_elem_with_rng(true, rng);
+    let rho = &f_n.rand_elem_with_rng(true, rng);
+    let A_I = h * alpha + (gg * aL).sum() + (hh * aR).sum();
+    let A_O = h * beta + (gg * aO).sum();
+
+    let sL = &f_n.rand_elems_with_rng(&n, true, rng);
+    let sR = &f_n.rand_elems_with_rng(&n, true, rng);
+    let S = h * rho + (gg * sL).sum() + (hh * sR).sum();
+
+    transcript.append_point(b"A_I", &A_I);
+    transcript.append_point(b"A_O", &A_O);
+    transcript.append_point(b"S", &S);
+    let y = &transcript.challenge_field_elem(b"y", &f_n);
+    let z = &transcript.challenge_field_elem(b"z", &f_n);
+
+    let y_n = &y.pow_seq(&n);
+    let y_inv_n = &y.inv().pow_seq(&n);
+    let z_Q = &(z.pow_seq(&q) * z);
+    let wL = &ConstraintSystem::flatten(&cs.W_L, z_Q);
+    let wR = &ConstraintSystem::flatten(&cs.W_R, z_Q);
+    let wO = &ConstraintSystem::flatten(&cs.W_O, z_Q);
+    let wV = &ConstraintSystem::flatten(&cs.W_V, z_Q);
+
+    // l(X) = l1 X + l2 X^2 + l3 X^3 and r(X) = r0 + r1 X + r3 X^3
+    let l1 = &(aL + (y_inv_n * wR));
+    let l2 = aO;
+    let l3 = sL;
+    let r0 = &(wO - y_n);
+    let r1 = &(wL + &(y_n * aR));
+    let r3 = &(y_n * sR);
+
+    // t_2 doesn't need to be committed since the verifier checks it against V_j
+    let t1 = &(l1 * r0).sum();
+    let t3 = &((l2 * r1).sum() + (l3 * r0).sum());
+    let t4 = &((l1 * r3).sum() + (l3 * r1).sum());
+    let t5 = &(l2 * r3).sum();
+    let t6 = &(l3 * r3).sum();
+
+    let taus = f_n.rand_elems_with_rng(&5, true, rng);
+    let T1 = g * t1 + h * &taus[0];
+    let T3 = g * t3 + h * &taus[1];
+    let T4 = g * t4 + h * &taus[2];
+    let T5 = g * t5 + h * &taus[3];
+    let T6 = g * t6 + h * &taus[4];
+
+    transcript.append_point(b"T1", &T1);
+    transcript.append_point(b"T3", &T3);
+    transcript.append_point(b"T4", &T4);
+    transcript.append_point(b"T5", &T5);
+    transcript.append_point(b"T6", &T6);
+    let x = &transcript.challenge_field_elem(b"x", &f_n);
+
+    let x2 = &x.sq();
+    let x3 = &(x2 * x);
+    let l = (l1 * x) + (l2 * x2) + (l3 * x3);
+    let r = r0 + (r1 * x) + (r3 * x3);
+    let t_hat = (&l * &r).sum();
+    let tau_x = &taus[0] * x
+      + (&taus[1] * x3)
+      + (&taus[2] * x3 * x)
+      + (&taus[3] * x3 * x2)
+      + (&taus[4] * x3 * x3)
+      + (inner_product(wV, &PrimeFieldElems::new(gammas)) * x2);
+    let mu = alpha * x + (beta * x2) + (rho * x3);
+
+    transcript.append_field_elem(b"tau_x", &tau_x);
+    transcript.append_field_elem(b"mu", &mu);
+    transcript.append_field_elem(b"t_hat", &t_hat);
+
+    // u = g^w to prove <l, r> = t_hat
+    let w = &transcript.challenge_field_elem(b"w", &f_n);
+    let u = &(g * w);
+    let hhp = &(hh * y_inv_n);
+    let ipp = InnerProductProof::prove(transcript, u, gg, hhp, &l, &r)?;
+
+    let proof = R1CSProof { A_I, A_O, S, T1, T3, T4, T5, T6, tau_x, mu, t_hat, ipp };
+    Ok((proof, Vs))
+  }
+
+  // witness_io holds the public witness values [1, inputs, Out]. the values
+  // at the committed indices are ignored
+  pub fn verify(
+    &self,
+    transcript: &mut Transcript<32>,
+    gens: &Generators,
+    matrices: &ConstraintMatrices,
+    witness_io: &SparseVec,
+    committed: &[usize],
+    Vs: &[AffinePoint],
+  ) -> bool {
+    let cs = match ConstraintSystem::new(matrices, witness_io, committed) {
+      Ok(cs) => cs,
+      Err(_) => return false,
+    };
+    let (n, q, m) = (cs.n, cs.q, committed.len());
+    if Vs.len() != m || gens.len() < n {
+      return false;
+    }
+    let f_n = AffinePoint::curve_group();
+    let (g, h) = (&gens.g, &gens.h);
+    let gg = &gens.gg.to(n);
+    let hh = &gens.hh.to(n);
+
+    cs.append_statement(transcript, Vs);
+    transcript.append_point(b"A_I", &self.A_I);
+    transcript.append_point(b"A_O", &self.A_O);
+    transcript.append_point(b"S", &self.S);
+    let y = &transcript.challenge_field_elem(b"y", &f_n);
+    let z = &transcript.challenge_field_elem(b"z", &f_n);
+    transcript.append_point(b"T1", &self.T1);
+    transcript.append_point(b"T3", &self.T3);
+    transcript.append_point(b"T4", &self.T4);
+    transcript.append_point(b"T5", &self.T5);
+    transcript.append_point(b"T6", &self.T6);
+    let x = &transcript.challenge_field_elem(b"x", &f_n);
+    transcript.append_field_elem(b"tau_x", &self.tau_x);
+    transcript.append_field_elem(b"mu", &self.mu);
+    transcript.append_field_elem(b"t_hat", &self.t_hat);
+    let w = &transcript.challenge_field_elem(b"w", &f_n);
+
+    let y_n = &y.pow_seq(&n);
+    let y_inv_n = &y.inv().pow_seq(&n);
+    let z_Q = &(z.pow_seq(&q) * z);
+    let wL = &ConstraintSystem::flatten(&cs.W_L, z_Q);
+    let wR = &ConstraintSystem::flatten(&cs.W_R, z_Q);
+    let wO = &ConstraintSystem::flatten(&cs.W_O, z_Q);
+    let wV = &ConstraintSystem::flatten(&cs.W_V, z_Q);
+    let wc = &cs.flatten_c(z_Q);
+    let y_inv_wR = &(y_inv_n * wR);
+
+    // (92)
+    let x2 = &x.sq();
+    let x3 = &(x2 * x);
+    let delta = inner_product(y_inv_wR, wL);
+    let lhs_92 = (g * &self.t_hat) + (h * &self.tau_x);
+    let rhs_92 = Vs.iter().zip(wV.iter()).fold(AffinePoint::AtInfinity, |acc, (V, wV_j)| acc + V * (wV_j * x2))
+      + g * ((delta + wc) * x2)
+      + &self.T1 * x
+      + &self.T3 * x3
+      + &self.T4 * (x3 * x)
+      + &self.T5 * (x3 * x2)
+      + &self.T6 * (x3 * x3);
+    if lhs_92 != rhs_92 {
+      return false;
+    }
+
+    // (93)-(96) w/ P = A_I^x A_O^x^2 h'^-y^n W_L^x W_R^x W_O S^x^3
+    let hhp = &(hh * y_inv_n);
+    let P =
+      &self.A_I * x
+      + &self.A_O * x2
+      + (gg * (y_inv_wR * x)).sum()
+      + (hhp * (&(&(wL * x) + wO) - y_n)).sum()
+      + &self.S * x3;
+
+    // (97)-(99) w/ the inner product argument for P h^-mu u^t_hat
+    let u = &(g * w);
+    let Pp = P + h * self.mu.negate() + u * &self.t_hat;
+    self.ipp.verify(transcript, u, gg, hhp, &Pp)
+  }
+
+  // A_I || A_O || S || T1 || T3 || T4 || T5 || T6 || tau_x || mu || t_hat
+  // || inner product proof
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut buf = vec![];
+    for p in [&self.A_I, &self.A_O, &self.S, &self.T1, &self.T3, &self.T4, &self.T5, &self.T6] {
+      write_point(&mut buf, p);
+    }
+    for x in [&self.tau_x, &self.mu, &self.t_hat] {
+      write_scalar(&mut buf, x);
+    }
+    buf.extend(self.ipp.to_bytes());
+    buf
+  }
+
+  pub fn from_bytes(buf: &[u8]) -> Result<R1CSProof, String> {
+    let fixed_len = 8 * POINT_LEN + 3 * SCALAR_LEN;
+    if buf.len() < fixed_len {
+      return Err(format!("Invalid R1CS proof length {}", buf.len()));
+    }
+    let mut reader = Reader::new(buf);
+    let A_I = reader.read_point()?;
+    let A_O = reader.read_point()?;
+    let S = reader.read_point()?;
+    let T1 = reader.read_point()?;
+    let T3 = reader.read_point()?;
+    let T4 = reader.read_point()?;
+    let T5 = reader.read_point()?;
+    let T6 = reader.read_point()?;
+    let tau_x = reader.read_scalar()?;
+    let mu = reader.read_scalar()?;
+    let t_hat = reader.read_scalar()?;
+    let ipp = InnerProductProof::from_bytes(&buf[fixed_len..])?;
+
+    Ok(R1CSProof { A_I, A_O, S, T1, T3, T4, T5, T6, tau_x, mu, t_hat, ipp })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::building_block::field::prime_field::PrimeField;
+  use crate::zk::w_trusted_setup::qap::{
+    equation_parser::EquationParser,
+    gadgets::circuit_builder::CircuitBuilder,
+    gate::Gate,
+    r1cs_tmpl::R1CSTmpl,
+    term::Term,
+  };
+  use rand::SeedableRng;
+  use rand_chacha::ChaChaRng;
+  use std::collections::HashMap;
+
+  // witness [1, x, Out, t1, t2, t3, t4] of (x * x * x) + x + 5 == 35
+  fn build_r1cs(x: u8, out: u8) -> R1CS {
+    let f: &PrimeField = &AffinePoint::curve_group();
+    let eq = EquationParser::parse(f, "(x * x * x) + x + 5 == 35").unwrap();
+    let gates = &Gate::build(f, &eq);
+    let tmpl = &R1CSTmpl::new(f, gates);
+
+    let x = f.elem(&x);
+    let t1 = &x * &x;
+    let t2 = &t1 * &x;
+    let t3 = &x + f.elem(&5u8);
+    let t4 = &t2 + &t3;
+    let witness = HashMap::<Term, PrimeFieldElem>::from([
+      (Term::One, f.elem(&1u8)),
+      (Term::Var("x".to_string()), x),
+      (Term::Out, f.elem(&out)),
+      (Term::TmpVar(1), t1),
+      (Term::TmpVar(2), t2),
+      (Term::TmpVar(3), t3),
+      (Term::TmpVar(4), t4),
+    ]);
+    R1CS::from_tmpl(f, tmpl, &witness).unwrap()
+  }
+
+  fn witness_io(r1cs: &R1CS) -> SparseVec {
+    r1cs.witness.slice(&r1cs.witness.f.elem(&0u8), &r1cs.mid_beg)
+  }
+
+  fn prove(gens: &Generators, r1cs: &R1CS, committed: &[usize], rng: &mut ChaChaRng) -> (R1CSProof, Vec<AffinePoint>) {
+    let gammas = AffinePoint::curve_group().rand_elems_with_rng(&committed.len(), true, rng);
+    let mut transcript = Transcript::new(b"test");
+    R1CSProof::prove_with_rng(&mut transcript, gens, r1cs, committed, &gammas, rng).unwrap()
+  }
+
+  fn verify(
+    gens: &Generators,
+    r1cs: &R1CS,
+    io: &SparseVec,
+    committed: &[usize],
+    proof: &R1CSProof,
+    Vs: &[AffinePoint],
+  ) -> bool {
+    let mut transcript = Transcript::new(b"test");
+    proof.verify(&mut transcript, gens, &r1cs.to_constraint_matrices(), io, committed, Vs)
+  }
+
+  #[test]
+  fn prove_and_verify() {
+    let gens = &Generators::new(16);
+    let rng = &mut ChaChaRng::seed_from_u64(1);
+    let r1cs = &build_r1cs(3, 35);
+    let io = &witness_io(r1cs);
+
+    // x is committed and Out is public
+    let (proof, Vs) = prove(gens, r1cs, &[1], rng);
+    assert_eq!(Vs.len(), 1);
+    assert!(verify(gens, r1cs, io, &[1], &proof, &Vs));
+
+    // the verifier doesn't need to know x
+    let mut io2 = io.clone();
+    io2.set(&1u8, &0u8);
+    assert!(verify(gens, r1cs, &io2, &[1], &proof, &Vs));
+
+    // different Out
+    let mut io2 = io.clone();
+    io2.set(&2u8, &36u8);
+    assert!(!verify(gens, r1cs, &io2, &[1], &proof, &Vs));
+
+    // different commitment
+    let bad_Vs = vec![&Vs[0] + &gens.g];
+    assert!(!verify(gens, r1cs, io, &[1], &proof, &bad_Vs));
+
+    // all values public
+    let (proof, Vs) = prove(gens, r1cs, &[], rng);
+    assert!(Vs.is_empty());
+    assert!(verify(gens, r1cs, io, &[], &proof, &Vs));
+    assert!(!verify(gens, r1cs, io, &[1], &proof, &Vs));
+  }
+
+  #[test]
+  fn larger_circuit() {
+    let gens = &Generators::new(64);
+    let rng = &mut ChaChaRng::seed_from_u64(4);
+    let f_n = AffinePoint::curve_group();
+    let f: &PrimeField = &f_n;
+
+    // x^4095 w/ 22 multiplications
+    let mut cb = CircuitBuilder::new(&f_n);
+    let x = cb.input("x", &f_n.elem(&3u8));
+    let y = cb.pow(&x, 4095);
+    cb.output(&y);
+    let tmpl = &R1CSTmpl::new(f, &cb.gates);
+    let r1cs = &R1CS::from_tmpl(f, tmpl, &cb.witness).unwrap();
+    let io = &witness_io(r1cs);
+
+    let (proof, Vs) = prove(gens, r1cs, &[1], rng);
+    assert_eq!(proof.ipp.L_vec.len(), 6);
+    assert!(verify(gens, r1cs, io, &[1], &proof, &Vs));
+
+    let mut io2 = io.clone();
+    io2.set(&2u8, &(&y.value + f_n.elem(&1u8)));
+    assert!(!verify(gens, r1cs, &io2, &[1], &proof, &Vs));
+  }
+
+  #[test]
+  fn bad_proof() {
+    let gens = &Generators::new(16);
+    let rng = &mut ChaChaRng::seed_from_u64(2);
+    let r1cs = &build_r1cs(3, 35);
+    let io = &witness_io(r1cs);
+    let (proof, Vs) = prove(gens, r1cs, &[1, 3], rng);
+    let f_n = AffinePoint::curve_group();
+    assert!(verify(gens, r1cs, io, &[1, 3], &proof, &Vs));
+
+    // different transcript
+    let mut transcript = Transcript::new(b"other");
+    assert!(!proof.verify(&mut transcript, gens, &r1cs.to_constraint_matrices(), io, &[1, 3], &Vs));
+
+    // commitments in a different order
+    let swapped = vec![Vs[1].clone(), Vs[0].clone()];
+    assert!(!verify(gens, r1cs, io, &[1, 3], &proof, &swapped));
+
+    // tampered proof
+    let mut bad_proof = proof.clone();
+    bad_proof.t_hat = &bad_proof.t_hat + f_n.elem(&1u8);
+    assert!(!verify(gens, r1cs, io, &[1, 3], &bad_proof, &Vs));
+
+    let mut bad_proof = proof.clone();
+    bad_proof.A_O = &bad_proof.A_O + &gens.h;
+    assert!(!verify(gens, r1cs, io, &[1, 3], &bad_proof, &Vs));
+
+    let mut bad_proof = proof.clone();
+    bad_proof.ipp.b = &bad_proof.ipp.b + f_n.elem(&1u8);
+    assert!(!verify(gens, r1cs, io, &[1, 3], &bad_proof, &Vs));
+  }
+
+  #[test]
+  fn invalid_input() {
+    let gens = &Generators::new(16);
+    let gammas: Vec<PrimeFieldElem> = (1..=2u8).map(|i| AffinePoint::curve_group().elem(&i)).collect();
+    let r1cs = &build_r1cs(3, 35);
+    let mut transcript = Transcript::new(b"test");
+
+    // witness not satisfying the constraints
+    let mut bad_r1cs = build_r1cs(3, 35);
+    bad_r1cs.witness.set(&3u8, &8u8);
+    assert!(R1CSProof::prove(&mut transcript, gens, &bad_r1cs, &[1], &gammas[..1]).is_err());
+
+    // invalid committed indices
+    assert!(R1CSProof::prove(&mut transcript, gens, r1cs, &[0], &gammas[..1]).is_err());
+    assert!(R1CSProof::prove(&mut transcript, gens, r1cs, &[7], &gammas[..1]).is_err());
+    assert!(R1CSProof::prove(&mut transcript, gens, r1cs, &[1, 1], &gammas).is_err());
+
+    // number of blinding factors different from that of committed values
+    assert!(R1CSProof::prove(&mut transcript, gens, r1cs, &[1], &gammas).is_err());
+
+    // not enough generators
+    assert!(R1CSProof::prove(&mut transcript, &Generators::new(8), r1cs, &[1], &gammas[..1]).is_err());
+
+    // R1CS over another field
+    let f = &PrimeField::new(&3911u16);
+    let eq = EquationParser::parse(f, "x * x == 4").unwrap();
+    let tmpl = &R1CSTmpl::new(f, &Gate::build(f, &eq));
+    let witness = HashMap::<Term, PrimeFieldElem>::from([
+      (Term::One, f.elem(&1u8)),
+      (Term::Var("x".to_string()), f.elem(&2u8)),
+      (Term::Out, eq.rhs),
+      (Term::TmpVar(1), f.elem(&4u8)),
+    ]);
+    let other_r1cs = &R1CS::from_tmpl(f, tmpl, &witness).unwrap();
+    assert!(R1CSProof::prove(&mut transcript, gens, other_r1cs, &[], &[]).is_err());
+  }
+
+  #[test]
+  fn serialization() {
+    let gens = &Generators::new(16);
+    let rng = &mut ChaChaRng::seed_from_u64(3);
+    let r1cs = &build_r1cs(3, 35);
+    let io = &witness_io(r1cs);
+    let (proof, Vs) = prove(gens, r1cs, &[1], rng);
+
+    let buf = proof.to_bytes();
+    let k = proof.ipp.L_vec.len();
+    assert_eq!(buf.len(), (8 + 2 * k) * 33 + (3 + 2) * 32);
+    let proof2 = R1CSProof::from_bytes(&buf).unwrap();
+    assert_eq!(proof, proof2);
+    assert!(verify(gens, r1cs, io, &[1], &proof2, &Vs));
+
+    assert!(R1CSProof::from_bytes(&buf[..buf.len() - 1]).is_err());
+    assert!(R1CSProof::from_bytes(&buf[..8 * 33]).is_err());
+  }
+}
@@ -0,0 +1,457 @@
+#![allow(non_snake_case)]
+
+use crate::building_block::{
+  curves::secp256k1::{
+    affine_point::AffinePoint,
+    affine_points::AffinePoints,
+  },
+  field::{
+    prime_field_elem::PrimeFieldElem,
+    prime_field_elems::PrimeFieldElems,
+  },
+  random_number::RandomNumber,
+  transcript::Transcript,
+};
+use crate::zk::wo_trusted_setup::{
+  encoding::{
+    Reader,
+    write_point,
+    write_scalar,
+    POINT_LEN,
+    SCALAR_LEN,
+  },
+  generators::Generators,
+  inner_product_proof::InnerProductProof,
+};
+use rand::{CryptoRng, RngCore};
+
+// non-interactive proof that the values committed in V_j = g^v_j h^gamma_j
+// for j = 0, ..., m-1 are in [0, 2^n).
+// implementation based on sections 4.2 and 4.3 of https://eprint.iacr.org/2017/1066.pdf
+// w/ the verifier challenges derived from the transcript. l and r are proven
+// w/ the inner product argument, so the proof consists of 2 log2(nm) + 4 points
+// and 5 scalars
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeProof {
+  pub A: AffinePoint,
+  pub S: AffinePoint,
+  pub T1: AffinePoint,
+  pub T2: AffinePoint,
+  pub tau_x: PrimeFieldElem,
+  pub mu: PrimeFieldElem,
+  pub t_hat: PrimeFieldElem,
+  pub ipp: InnerProductProof,
+}
+
+impl RangeProof {
+  fn validate_params(gens: &Generators, n: usize, m: usize) -> Result<(), String> {
+    if n == 0 || n > 64 || !n.is_power_of_two() {
+      return Err(format!("Bit length needs to be a power of 2 up to 64, but got {}", n));
+    }
+    if m == 0 || !m.is_power_of_two() {
+      return Err(format!("Number of values needs to be a power of 2, but got {}", m));
+    }
+    if gens.len() < n * m {
+      return Err(format!("{} generators are required, but got {}", n * m, gens.len()));
+    }
+    Ok(())
+  }
+
+  fn append_statement(transcript: &mut Transcript<32>, n: usize, Vs: &[AffinePoint]) {
+    transcript.append_message(b"dom-sep", b"range-proof");
+    transcript.append_u64(b"n", n as u64);
+    transcript.append_u64(b"m", Vs.len() as u64);
+    for V in Vs {
+      transcript.append_point(b"V", V);
+    }
+  }
+
+  // returns the proof and the commitment V
+  pub fn prove(
+    transcript: &mut Transcript<32>,
+    gens: &Generators,
+    n: usize,
+    v: u64,
+    gamma: &PrimeFieldElem,
+  ) -> Result<(RangeProof, AffinePoint), String> {
+    RangeProof::prove_with_rng(transcript, gens, n, v, gamma, &mut RandomNumber::new().gen)
+  }
+
+  // blinding factors are drawn from rng
+  pub fn prove_with_rng(
+    transcript: &mut Transcript<32>,
+    gens: &Generators,
+    n: usize,
+    v: u64,
+    gamma: &PrimeFieldElem,
+    rng: &mut (impl RngCore + CryptoRng),
+  ) -> Result<(RangeProof, AffinePoint), String> {
+    let (proof, Vs) = RangeProof::prove_multiple_with_rng(
+      transcript, gens, n, &[v], std::slice::from_ref(gamma), rng)?;
+    Ok((proof, Vs[0].clone()))
+  }
+
+  pub fn verify(
+    &self,
+    transcript: &mut Transcript<32>,
+    gens: &Generators,
+    n: usize,
+    V: &AffinePoint,
+  ) -> bool {
+    self.verify_multiple(transcript, gens, n, std::slice::from_ref(V))
+  }
+
+  // returns the proof and the commitments V_j
+  pub fn prove_multiple(
+    transcript: &mut Transcript<32>,
+    gens: &Generators,
+    n: usize,
+    values: &[u64],
+    gammas: &[PrimeFieldElem],
+  ) -> Result<(RangeProof, Vec<AffinePoint>), String> {
+    RangeProof::prove_multiple_with_rng(transcript, gens, n, values, gammas, &mut RandomNumber::new().gen)
+  }
+
+  // blinding factors are drawn from rng
+  pub fn prove_multiple_with_rng(
+    transcript: &mut Transcript<32>,
+    gens: &Generators,
+    n: usize,
+    values: &[u64],
+    gammas: &[PrimeFieldElem],
+    rng: &mut (impl RngCore + CryptoRng),
+  ) -> Result<(RangeProof, Vec<AffinePoint>), String> {
+    let m = values.len();
+    RangeProof::validate_params(gens, n, m)?;
+    if gammas.len() != m {
+      return Err(format!("{} blinding factors are required, but got {}", m, gammas.len()));
+    }
+    if let Some(v) = values.iter().find(|v| n < 64 && *v >> n != 0) {
+      return Err(format!("Values need to be less than 2^{}, but got {}", n, v));
+    }
+    let f_n = AffinePoint::curve_group();
+    let (g, h) = (&gens.g, &gens.h);
+    let nm = n * m;
+    let gg = &gens.gg.to(nm);
+    let hh = &gens.hh.to(nm);
+
+    let Vs: Vec<AffinePoint> = values.iter().zip(gammas.iter())
+      .map(|(v, gamma)| g * f_n.elem(v) + h * gamma)
+      .collect();
+    RangeProof::append_statement(transcript, n, &Vs);
+
+    let one_nm = &f_n.elem(&1u8).pow_seq(&nm);
+    let two_n = &f_n.elem(&2u8).pow_seq(&n);
+
+    // (39)-(44) w/ the bits of all the values concatenated
+    let aL = &PrimeFieldElems(
+      values.iter().flat_map(|v| (0..n).map(move |i| (v >> i) & 1)).map(|b| f_n.elem(&b)).collect()
+    );
+    let aR = &(aL - one_nm);
+    let alpha = &f_n.rand_elem_with_rng(true, rng);
+    let A = h * alpha + (gg * aL).sum() + (hh * aR).sum();
+
+    let sL = &f_n.rand_elems_with_rng(&nm, true, rng);
+    let sR = &f_n.rand_elems_with_rng(&nm, true, rng);
+    let rho = &f_n.rand_elem_with_rng(true, rng);
+    let S = h * rho + (gg * sL).sum() + (hh * sR).sum();
+
+    transcript.append_point(b"A", &A);
+    transcript.append_point(b"S", &S);
+    let y = &transcript.challenge_field_elem(b"y", &f_n);
+    let z = &transcript.challenge_field_elem(b"z", &f_n);
+
+    // (70), (71). the j-th block of 2^n is weighted by z^(j+2)
+    let y_nm = &y.pow_seq(&nm);
+    let z_m = &(z.pow_seq(&m) * z.sq());
+    let d = PrimeFieldElems(
+      z_m.iter().flat_map(|zj| two_n.iter().map(move |t| t * zj)).collect()
+    );
+    let l0 = &(aL - (one_nm * z));
+    let l1 = sL;
+    let r0 = &((y_nm * (aR + (one_nm * z))) + d);
+    let r1 = &(y_nm * sR);
+
+    let t1 = &((l1 * r0).sum() + (l0 * r1).sum());
+    let t2 = &(l1 * r1).sum();
+
+    let tau1 = &f_n.rand_elem_with_rng(true, rng);
+    let tau2 = &f_n.rand_elem_with_rng(true, rng);
+    let T1 = g * t1 + h * tau1;
+    let T2 = g * t2 + h * tau2;
+
+    transcript.append_point(b"T1", &T1);
+    transcript.append_point(b"T2", &T2);
+    let x = &transcript.challenge_field_elem(b"x", &f_n);
+
+    // (58)-(62) and (73)
+    let l = l0 + (l1 * x);
+    let r = r0 + (r1 * x);
+    let t_hat = (&l * &r).sum();
+    let tau_x = tau2 * x.sq() + (tau1 * x) + (z_m * &PrimeFieldElems::new(gammas)).sum();
+    let mu = alpha + (rho * x);
+
+    transcript.append_field_elem(b"tau_x", &tau_x);
+    transcript.append_field_elem(b"mu", &mu);
+    transcript.append_field_elem(b"t_hat", &t_hat);
+
+    // (64) and u = g^w to prove <l, r> = t_hat
+    let w = &transcript.challenge_field_elem(b"w", &f_n);
+    let u = &(g * w);
+    let hhp = &(hh * &y.inv().pow_seq(&nm));
+    let ipp = InnerProductProof::prove(transcript, u, gg, hhp, &l, &r)?;
+
+    let proof = RangeProof { A, S, T1, T2, tau_x, mu, t_hat, ipp };
+    Ok((proof, Vs))
+  }
+
+  pub fn verify_multiple(
+    &self,
+    transcript: &mut Transcript<32>,
+    gens: &Generators,
+    n: usize,
+    Vs: &[AffinePoint],
+  ) -> bool {
+    let m = Vs.len();
+    if RangeProof::validate_params(gens, n, m).is_err() {
+      return false;
+    }
+    let f_n = AffinePoint::curve_group();
+    let (g, h) = (&gens.g, &gens.h);
+    let nm = n * m;
+    let gg = &gens.gg.to(nm);
+    let hh = &gens.hh.to(nm);
+
+    RangeProof::append_statement(transcript, n, Vs);
+    transcript.append_point(b"A", &self.A);
+    transcript.append_point(b"S", &self.S);
+    let y = &transcript.challenge_field_elem(b"y", &f_n);
+    let z = &transcript.challenge_field_elem(b"z", &f_n);
+    transcript.append_point(b"T1", &self.T1);
+    transcript.append_point(b"T2", &self.T2);
+    let x = &transcript.challenge_field_elem(b"x", &f_n);
+    transcript.append_field_elem(b"tau_x", &self.tau_x);
+    transcript.append_field_elem(b"mu", &self.mu);
+    transcript.append_field_elem(b"t_hat", &self.t_hat);
+    let w = &transcript.challenge_field_elem(b"w", &f_n);
+
+    let one_nm = &f_n.elem(&1u8).pow_seq(&nm);
+    let two_n = &f_n.elem(&2u8).pow_seq(&n);
+    let y_nm = &y.pow_seq(&nm);
+    let z_m = &(z.pow_seq(&m) * z.sq());
+
+    // (72)
+    let delta_yz = &((z - z.sq()) * y_nm.sum()) - (z * z_m.sum() * two_n.sum());
+    let lhs_72 = (g * &self.t_hat) + (h * &self.tau_x);
+    let rhs_72 =
+      (AffinePoints::new(&Vs.to_vec()) * z_m).sum()
+      + g * delta_yz
+      + &self.T1 * x
+      + &self.T2 * x.sq();
+    if lhs_72 != rhs_72 {
+      return false;
+    }
+
+    // (64), (66) w/ the j-th block of h' weighted by z^(j+2) 2^n
+    let hhp = &(hh * &y.inv().pow_seq(&nm));
+    let d = PrimeFieldElems(
+      z_m.iter().flat_map(|zj| two_n.iter().map(move |t| t * zj)).collect()
+    );
+    let P =
+      &self.A
+      + &self.S * x
+      + (gg * (one_nm * z.negate())).sum()
+      + (hhp * ((y_nm * z) + d)).sum();
+
+    // (67), (68) w/ the inner product argument for P h^-mu u^t_hat
+    let u = &(g * w);
+    let Pp = P + h * self.mu.negate() + u * &self.t_hat;
+    self.ipp.verify(transcript, u, gg, hhp, &Pp)
+  }
+
+  // A || S || T1 || T2 || tau_x || mu || t_hat || inner product proof
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut buf = vec![];
+    for p in [&self.A, &self.S, &self.T1, &self.T2] {
+      write_point(&mut buf, p);
+    }
+    for x in [&self.tau_x, &self.mu, &self.t_hat] {
+      write_scalar(&mut buf, x);
+    }
+    buf.extend(self.ipp.to_bytes());
+    buf
+  }
+
+  pub fn from_bytes(buf: &[u8]) -> Result<RangeProof, String> {
+    let fixed_len = 4 * POINT_LEN + 3 * SCALAR_LEN;
+    if buf.len() < fixed_len {
+      return Err(format!("Invalid range proof length {}", buf.len()));
+    }
+    let mut reader = Reader::new(buf);
+    let A = reader.read_point()?;
+    let S = reader.read_point()?;
+    let T1 = reader.read_point()?;
+    let T2 = reader.read_point()?;
+    let tau_x = reader.read_scalar()?;
+    let mu = reader.read_scalar()?;
+    let t_hat = reader.read_scalar()?;
+    let ipp = InnerProductProof::from_bytes(&buf[fixed_len..])?;
+
+    Ok(RangeProof { A, S, T1, T2, tau_x, mu, t_hat, ipp })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use rand::SeedableRng;
+  use rand_chacha::ChaChaRng;
+
+  fn prove(gens: &Generators, n: usize, v: u64, rng: &mut ChaChaRng) -> (RangeProof, AffinePoint) {
+    let gamma = AffinePoint::curve_group().rand_elem_with_rng(true, rng);
+    let mut transcript = Transcript::new(b"test");
+    RangeProof::prove_with_rng(&mut transcript, gens, n, v, &gamma, rng).unwrap()
+  }
+
+  fn verify(gens: &Generators, n: usize, proof: &RangeProof, V: &AffinePoint) -> bool {
+    let mut transcript = Transcript::new(b"test");
+    proof.verify(&mut transcript, gens, n, V)
+  }
+
+  #[test]
+  fn prove_and_verify() {
+    let gens = &Generators::new(8);
+    let rng = &mut ChaChaRng::seed_from_u64(1);
+
+    for v in [0, 1, 200, 255] {
+      let (proof, V) = prove(gens, 8, v, rng);
+      assert!(verify(gens, 8, &proof, &V));
+    }
+    let (proof, V) = prove(gens, 4, 9, rng);
+    assert!(verify(gens, 4, &proof, &V));
+
+    // w/ random blinding factors
+    let gamma = AffinePoint::curve_group().rand_elem(true);
+    let (proof, V) = RangeProof::prove(&mut Transcript::new(b"test"), gens, 2, 3, &gamma).unwrap();
+    assert!(verify(gens, 2, &proof, &V));
+  }
+
+  #[test]
+  fn out_of_range() {
+    let gens = &Generators::new(8);
+    let gamma = &AffinePoint::curve_group().elem(&1u8);
+    let mut transcript = Transcript::new(b"test");
+    assert!(RangeProof::prove(&mut transcript, gens, 8, 256, gamma).is_err());
+    assert!(RangeProof::prove(&mut transcript, gens, 16, 1, gamma).is_err());
+    assert!(RangeProof::prove(&mut transcript, gens, 6, 1, gamma).is_err());
+  }
+
+  #[test]
+  fn bad_proof() {
+    let gens = &Generators::new(4);
+    let rng = &mut ChaChaRng::seed_from_u64(2);
+    let (proof, V) = prove(gens, 4, 5, rng);
+    let f_n = AffinePoint::curve_group();
+
+    // different commitment
+    assert!(!verify(gens, 4, &proof, &(&V + &gens.g)));
+
+    // different transcript
+    let mut transcript = Transcript::new(b"other");
+    assert!(!proof.verify(&mut transcript, gens, 4, &V));
+
+    // different bit length
+    assert!(!verify(gens, 2, &proof, &V));
+
+    // tampered proof
+    let mut bad_proof = proof.clone();
+    bad_proof.t_hat = &bad_proof.t_hat + f_n.elem(&1u8);
+    assert!(!verify(gens, 4, &bad_proof, &V));
+
+    let mut bad_proof = proof.clone();
+    bad_proof.ipp.a = &bad_proof.ipp.a + f_n.elem(&1u8);
+    assert!(!verify(gens, 4, &bad_proof, &V));
+
+    let mut bad_proof = proof.clone();
+    bad_proof.A = &bad_proof.A + &gens.h;
+    assert!(!verify(gens, 4, &bad_proof, &V));
+  }
+
+  fn prove_multiple(gens: &Generators, n: usize, values: &[u64], rng: &mut ChaChaRng) -> (RangeProof, Vec<AffinePoint>) {
+    let gammas = AffinePoint::curve_group().rand_elems_with_rng(&values.len(), true, rng);
+    let mut transcript = Transcript::new(b"test");
+    RangeProof::prove_multiple_with_rng(&mut transcript, gens, n, values, &gammas, rng).unwrap()
+  }
+
+  fn verify_multiple(gens: &Generators, n: usize, proof: &RangeProof, Vs: &[AffinePoint]) -> bool {
+    let mut transcript = Transcript::new(b"test");
+    proof.verify_multiple(&mut transcript, gens, n, Vs)
+  }
+
+  #[test]
+  fn aggregated() {
+    let gens = &Generators::new(8);
+    let rng = &mut ChaChaRng::seed_from_u64(4);
+
+    let (proof, Vs) = prove_multiple(gens, 4, &[3, 15], rng);
+    assert!(verify_multiple(gens, 4, &proof, &Vs));
+    // same size as a single proof for 8 bits
+    assert_eq!(proof.ipp.L_vec.len(), 3);
+
+    let (proof, Vs) = prove_multiple(gens, 2, &[0, 1, 2, 3], rng);
+    assert!(verify_multiple(gens, 2, &proof, &Vs));
+
+    // commitments in a different order
+    let swapped = vec![Vs[1].clone(), Vs[0].clone(), Vs[2].clone(), Vs[3].clone()];
+    assert!(!verify_multiple(gens, 2, &proof, &swapped));
+
+    // subset of the commitments
+    assert!(!verify_multiple(gens, 2, &proof, &Vs[..2]));
+    assert!(!verify_multiple(gens, 4, &proof, &Vs[..2]));
+
+    // single value proof is an aggregated proof of 1 value
+    let (proof, V) = prove(gens, 8, 77, rng);
+    assert!(verify_multiple(gens, 8, &proof, &[V]));
+  }
+
+  #[test]
+  fn aggregated_invalid_input() {
+    let gens = &Generators::new(8);
+    let f_n = AffinePoint::curve_group();
+    let gammas: Vec<PrimeFieldElem> = (1..=4u8).map(|i| f_n.elem(&i)).collect();
+    let mut transcript = Transcript::new(b"test");
+
+    // one of the values out of range
+    assert!(RangeProof::prove_multiple(&mut transcript, gens, 4, &[1, 16], &gammas[..2]).is_err());
+    // number of values not a power of 2
+    assert!(RangeProof::prove_multiple(&mut transcript, gens, 2, &[1, 2, 3], &gammas[..3]).is_err());
+    // number of blinding factors different from that of values
+    assert!(RangeProof::prove_multiple(&mut transcript, gens, 2, &[1, 2], &gammas[..1]).is_err());
+    // not enough generators
+    assert!(RangeProof::prove_multiple(&mut transcript, gens, 8, &[1, 2], &gammas[..2]).is_err());
+    assert!(RangeProof::prove_multiple(&mut transcript, gens, 8, &[], &[]).is_err());
+  }
+
+  #[test]
+  fn serialization() {
+    let gens = &Generators::new(4);
+    let rng = &mut ChaChaRng::seed_from_u64(3);
+    let (proof, V) = prove(gens, 4, 10, rng);
+
+    let buf = proof.to_bytes();
+    assert_eq!(buf.len(), (4 + 2 * 2) * 33 + (3 + 2) * 32);
+    let proof2 = RangeProof::from_bytes(&buf).unwrap();
+    assert_eq!(proof, proof2);
+    assert!(verify(gens, 4, &proof2, &V));
+
+    assert!(RangeProof::from_bytes(&buf[..buf.len() - 1]).is_err());
+    assert!(RangeProof::from_bytes(&buf[..4 * 33 + 3 * 32]).is_err());
+    assert!(RangeProof::from_bytes(&buf[..4 * 33]).is_err());
+
+    // scalar not less than the group order
+    let mut bad_buf = buf.clone();
+    bad_buf[4 * 33..4 * 33 + 32].copy_from_slice(&[0xff; 32]);
+    assert!(RangeProof::from_bytes(&bad_buf).is_err());
+  }
+}